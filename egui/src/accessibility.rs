@@ -0,0 +1,191 @@
+//! Describing the widgets to screen readers and other assistive technology.
+//!
+//! Egui only paints shapes, so the widgets also describe themselves each frame:
+//! their role, label, value and state. When enabled with
+//! [`Context::set_access_tree_enabled`](crate::Context::set_access_tree_enabled),
+//! these descriptions are collected into an [`AccessTree`] in
+//! [`Output::access_tree`](crate::Output::access_tree), which an integration
+//! can forward to the platform accessibility API (AT-SPI, ARIA, …).
+//!
+//! ```
+//! use egui::accessibility::AccessRole;
+//!
+//! let mut ctx = egui::CtxRef::default();
+//! ctx.set_access_tree_enabled(true);
+//! ctx.begin_frame(Default::default());
+//! egui::Window::new("Settings").show(&ctx, |ui| {
+//!     ui.checkbox(&mut true, "Dark mode");
+//!     egui::CollapsingHeader::new("Sound")
+//!         .default_open(true)
+//!         .show(ui, |ui| {
+//!             ui.add(egui::Slider::f32(&mut 0.5, 0.0..=1.0).text("Volume"));
+//!         });
+//! });
+//! let (output, _shapes) = ctx.end_frame();
+//!
+//! let tree = output.access_tree.unwrap();
+//! let window = &tree.roots[0];
+//! assert_eq!(window.role, AccessRole::Window);
+//! assert_eq!(window.label, "Settings");
+//! assert_eq!(window.children[0].role, AccessRole::Checkbox);
+//! assert_eq!(window.children[0].label, "Dark mode");
+//! assert_eq!(window.children[0].checked, Some(true));
+//!
+//! let sound = &window.children[1];
+//! assert_eq!(sound.role, AccessRole::CollapsingHeader);
+//! assert_eq!(sound.expanded, Some(true));
+//!
+//! let volume = tree.find_by_label("Volume").unwrap();
+//! assert_eq!(volume, &sound.children[0]);
+//! assert_eq!(volume.role, AccessRole::Slider);
+//! assert_eq!(volume.value.as_deref(), Some("0.50")); // as shown next to the slider
+//! ```
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{Id, Rect, Response, Sense};
+
+/// What kind of widget an [`AccessNode`] describes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AccessRole {
+    Button,
+    Checkbox,
+    RadioButton,
+    Slider,
+    TextEdit,
+    CollapsingHeader,
+    Window,
+}
+
+/// The description of one widget, as plain data.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccessNode {
+    /// The [`Id`] of the widget. Stable from one frame to the next.
+    pub id: Id,
+    pub role: AccessRole,
+    /// What a screen reader reads out, e.g. the text of a button or the title of a window.
+    pub label: String,
+    /// The current value of e.g. a [`crate::Slider`] or [`crate::TextEdit`].
+    pub value: Option<String>,
+    /// `Some` for checkboxes and radio buttons.
+    pub checked: Option<bool>,
+    /// `Some` for collapsing headers and windows.
+    pub expanded: Option<bool>,
+    /// Does the widget have keyboard focus?
+    pub focused: bool,
+    /// Can the user interact with the widget?
+    pub enabled: bool,
+    /// Where the widget is, in points.
+    pub rect: Rect,
+    /// Widgets contained in this one, e.g. the contents of a window.
+    pub children: Vec<AccessNode>,
+}
+
+impl AccessNode {
+    /// Describe the widget that produced the given [`Response`].
+    pub fn new(role: AccessRole, response: &Response, label: impl Into<String>) -> Self {
+        Self {
+            id: response.id,
+            role,
+            label: label.into(),
+            value: None,
+            checked: None,
+            expanded: None,
+            focused: response.has_kb_focus,
+            enabled: response.sense != Sense::hover(),
+            rect: response.rect,
+            children: vec![],
+        }
+    }
+
+    pub fn value(mut self, value: impl Into<String>) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    pub fn checked(mut self, checked: bool) -> Self {
+        self.checked = Some(checked);
+        self
+    }
+
+    pub fn expanded(mut self, expanded: bool) -> Self {
+        self.expanded = Some(expanded);
+        self
+    }
+
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Depth-first search for the first node (this one or a descendant) with the given label.
+    pub fn find_by_label(&self, label: &str) -> Option<&AccessNode> {
+        if self.label == label {
+            Some(self)
+        } else {
+            self.children
+                .iter()
+                .find_map(|child| child.find_by_label(label))
+        }
+    }
+}
+
+/// All the [`AccessNode`]s of a frame, in the order they were added.
+///
+/// Windows are roots. Widgets outside of any window or collapsing header are also roots.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AccessTree {
+    pub roots: Vec<AccessNode>,
+}
+
+impl AccessTree {
+    /// Depth-first search for the first node with the given label.
+    pub fn find_by_label(&self, label: &str) -> Option<&AccessNode> {
+        self.roots.iter().find_map(|root| root.find_by_label(label))
+    }
+
+    /// Total number of nodes in the tree.
+    pub fn len(&self) -> usize {
+        fn count(node: &AccessNode) -> usize {
+            1 + node.children.iter().map(count).sum::<usize>()
+        }
+        self.roots.iter().map(count).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+
+    /// Build the tree from nodes registered with the id of their (optional) parent.
+    pub(crate) fn from_nodes(nodes: Vec<(Option<Id>, AccessNode)>) -> Self {
+        let registered: HashSet<Id> = nodes.iter().map(|(_, node)| node.id).collect();
+
+        let mut roots = vec![];
+        let mut children: HashMap<Id, Vec<AccessNode>> = Default::default();
+        for (parent, node) in nodes {
+            match parent {
+                Some(parent) if parent != node.id && registered.contains(&parent) => {
+                    children.entry(parent).or_default().push(node);
+                }
+                _ => roots.push(node),
+            }
+        }
+
+        fn adopt(node: &mut AccessNode, children: &mut HashMap<Id, Vec<AccessNode>>) {
+            if let Some(mut own_children) = children.remove(&node.id) {
+                for child in &mut own_children {
+                    adopt(child, children);
+                }
+                node.children = own_children;
+            }
+        }
+        for root in &mut roots {
+            adopt(root, &mut children);
+        }
+
+        // Only left if there was a cycle (an `Id` clash), so keep them rather than losing them:
+        roots.extend(children.drain().flat_map(|(_, nodes)| nodes));
+
+        Self { roots }
+    }
+}
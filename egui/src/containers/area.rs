@@ -242,7 +242,7 @@ impl Prepared {
 
 fn mouse_pressed_on_area(ctx: &Context, layer_id: LayerId) -> bool {
     if let Some(mouse_pos) = ctx.input().mouse.pos {
-        ctx.input().mouse.pressed && ctx.layer_id_at(mouse_pos) == Some(layer_id)
+        ctx.input().mouse.any_pressed() && ctx.layer_id_at(mouse_pos) == Some(layer_id)
     } else {
         false
     }
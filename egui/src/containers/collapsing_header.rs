@@ -1,6 +1,7 @@
 use std::hash::Hash;
 
 use crate::{
+    accessibility::{AccessNode, AccessRole},
     paint::{Shape, TextStyle},
     widgets::Label,
     *,
@@ -202,6 +203,10 @@ impl CollapsingHeader {
         if header_response.clicked {
             state.toggle(ui);
         }
+        ui.register_access_node(|| {
+            AccessNode::new(AccessRole::CollapsingHeader, &header_response, &galley.text)
+                .expanded(state.open)
+        });
 
         let visuals = ui.style().interact(&header_response);
         let text_color = visuals.text_color();
@@ -254,7 +259,13 @@ impl CollapsingHeader {
                 header_response,
                 mut state,
             } = self.begin(ui);
-            let ret_response = state.add_contents(ui, id, |ui| ui.indent(id, add_contents).0);
+            let ret_response = state.add_contents(ui, id, |ui| {
+                ui.indent(id, |ui| {
+                    ui.set_access_parent(id);
+                    add_contents(ui)
+                })
+                .0
+            });
             ui.memory().collapsing_headers.insert(id, state);
 
             if let Some((ret, response)) = ret_response {
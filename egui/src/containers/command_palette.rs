@@ -0,0 +1,264 @@
+//! A searchable list of commands, opened with a keyboard shortcut.
+
+use std::hash::Hash;
+
+use crate::*;
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct State {
+    open: bool,
+    search: String,
+    /// The `search` of the previous frame, to know when to reset `selected`.
+    matched_search: String,
+    /// Index into the commands matching `search`.
+    selected: usize,
+}
+
+/// A command that can be run with its keyboard shortcut or from a [`CommandPalette`].
+#[derive(Clone, Debug)]
+pub struct Command {
+    pub name: String,
+    pub shortcut: Option<KeyboardShortcut>,
+}
+
+/// A registry of [`Command`]:s, with a searchable popup listing them
+/// which is opened with ⇧⌘P (Ctrl+Shift+P) by default.
+///
+/// Each command can also be run with its keyboard shortcut.
+/// The shortcuts are consumed (see [`InputState::consume_shortcut`]),
+/// so nothing else will react to them.
+///
+/// ```
+/// # let ui = &mut egui::Ui::__test();
+/// use egui::{Key, KeyboardShortcut, Modifiers};
+/// let palette = egui::CommandPalette::new("my_palette")
+///     .command("Save", Some(KeyboardShortcut::new(Modifiers::COMMAND, Key::S)))
+///     .command("Reset everything", None);
+/// match palette.show(ui.ctx()) {
+///     Some(0) => { /* save */ }
+///     Some(1) => { /* reset */ }
+///     _ => {}
+/// }
+/// ```
+pub struct CommandPalette {
+    id: Id,
+    open_shortcut: KeyboardShortcut,
+    commands: Vec<Command>,
+}
+
+impl CommandPalette {
+    pub fn new(id_source: impl Hash) -> Self {
+        Self {
+            id: Id::new(id_source),
+            open_shortcut: KeyboardShortcut::new(Modifiers::COMMAND | Modifiers::SHIFT, Key::P),
+            commands: vec![],
+        }
+    }
+
+    /// The shortcut that opens (and closes) the palette. ⇧⌘P (Ctrl+Shift+P) by default.
+    pub fn open_shortcut(mut self, open_shortcut: KeyboardShortcut) -> Self {
+        self.open_shortcut = open_shortcut;
+        self
+    }
+
+    /// Add a command. [`Self::show`] returns the index of the command to run,
+    /// in the order they were added.
+    pub fn command(mut self, name: impl Into<String>, shortcut: Option<KeyboardShortcut>) -> Self {
+        self.commands.push(Command {
+            name: name.into(),
+            shortcut,
+        });
+        self
+    }
+
+    /// Open the palette, e.g. from a menu.
+    pub fn open(&self, ctx: &CtxRef) {
+        let mut memory = ctx.memory();
+        let state = memory.command_palettes.entry(self.id).or_default();
+        *state = State {
+            open: true,
+            ..Default::default()
+        };
+    }
+
+    /// Check the shortcuts and show the palette if it is open.
+    ///
+    /// Returns the index of the command to run this frame, if any.
+    pub fn show(self, ctx: &CtxRef) -> Option<usize> {
+        let Self {
+            id,
+            open_shortcut,
+            commands,
+        } = self;
+
+        let mut state = ctx
+            .memory()
+            .command_palettes
+            .get(&id)
+            .cloned()
+            .unwrap_or_default();
+
+        if ctx.input().consume_shortcut(&open_shortcut) {
+            state = State {
+                open: !state.open,
+                ..Default::default()
+            };
+        }
+
+        let mut chosen = commands.iter().position(|command| match &command.shortcut {
+            Some(shortcut) => ctx.input().consume_shortcut(shortcut),
+            None => false,
+        });
+
+        if state.open {
+            if state.search != state.matched_search {
+                state.selected = 0;
+                state.matched_search = state.search.clone();
+            }
+            let search = state.search.to_lowercase();
+            let matches: Vec<usize> = (0..commands.len())
+                .filter(|&i| {
+                    let name = commands[i].name.to_lowercase();
+                    search.split_whitespace().all(|word| name.contains(word))
+                })
+                .collect();
+
+            let consume = |key| {
+                ctx.input()
+                    .consume_shortcut(&KeyboardShortcut::new(Modifiers::NONE, key))
+            };
+            if consume(Key::ArrowUp) {
+                state.selected = state.selected.saturating_sub(1);
+            }
+            if consume(Key::ArrowDown) {
+                state.selected += 1;
+            }
+            state.selected = state.selected.min(matches.len().saturating_sub(1));
+            if consume(Key::Enter) {
+                chosen = chosen.or_else(|| matches.get(state.selected).copied());
+                state.open = false;
+            }
+            if consume(Key::Escape) {
+                state.open = false;
+            }
+
+            if state.open {
+                let response = show_palette(ctx, id, &commands, &matches, &mut state, &mut chosen);
+                let clicked_elsewhere = ctx.input().mouse.click
+                    && !matches!(ctx.input().mouse.pos, Some(pos) if response.rect.contains(pos));
+                if chosen.is_some() || clicked_elsewhere {
+                    state.open = false;
+                }
+            }
+
+            if !state.open {
+                ctx.memory().surrender_kb_focus(id.with("search"));
+            }
+        }
+
+        ctx.memory().command_palettes.insert(id, state);
+        chosen
+    }
+}
+
+fn show_palette(
+    ctx: &CtxRef,
+    id: Id,
+    commands: &[Command],
+    matches: &[usize],
+    state: &mut State,
+    chosen: &mut Option<usize>,
+) -> Response {
+    let width = 320.0;
+    let screen_rect = ctx.input().screen_rect();
+    let pos = pos2(
+        screen_rect.center().x - width / 2.0,
+        screen_rect.top() + 0.1 * screen_rect.height(),
+    );
+
+    Area::new(id)
+        .order(Order::Foreground)
+        .fixed_pos(pos)
+        .show(ctx, |ui| {
+            Frame::popup(ui.style()).show(ui, |ui| {
+                ui.set_width(width);
+
+                let search_id = id.with("search");
+                ui.add(
+                    TextEdit::singleline(&mut state.search)
+                        .id(search_id)
+                        .desired_width(width),
+                );
+                ui.memory().request_kb_focus(search_id);
+
+                ScrollArea::from_max_height(200.0).show(ui, |ui| {
+                    if matches.is_empty() {
+                        ui.add(Label::new("No matching commands").text_color(Color32::GRAY));
+                    }
+                    for (i, &index) in matches.iter().enumerate() {
+                        let command = &commands[index];
+                        let selected = i == state.selected;
+                        ui.horizontal(|ui| {
+                            if ui.selectable_label(selected, &command.name).clicked {
+                                *chosen = Some(index);
+                            }
+                            if let Some(shortcut) = command.shortcut {
+                                ui.with_layout(Layout::right_to_left(), |ui| {
+                                    ui.add(
+                                        Label::new(shortcut.to_string()).text_color(Color32::GRAY),
+                                    );
+                                });
+                            }
+                        });
+                    }
+                });
+            });
+        })
+}
+
+#[test]
+fn test_command_palette() {
+    let palette = |ctx: &CtxRef| {
+        CommandPalette::new("palette")
+            .command(
+                "Save",
+                Some(KeyboardShortcut::new(Modifiers::COMMAND, Key::S)),
+            )
+            .command("Reset everything", None)
+            .command("Reset window positions", None)
+            .show(ctx)
+    };
+    let key = |key, modifiers| RawInput::default().with_key(key, modifiers);
+    let ctrl = Modifiers {
+        ctrl: true,
+        command: true,
+        ..Default::default()
+    };
+
+    let mut ctx = CtxRef::default();
+    assert_eq!(ctx.run_test_frame(key(Key::S, ctrl), palette), Some(0));
+    assert_eq!(
+        ctx.run_test_frame(key(Key::S, Modifiers::NONE), palette),
+        None
+    );
+
+    let ctrl_shift = ctrl | Modifiers::SHIFT;
+    assert_eq!(ctx.run_test_frame(key(Key::P, ctrl_shift), palette), None);
+    assert!(ctx.memory().command_palettes[&Id::new("palette")].open);
+    assert_eq!(
+        ctx.run_test_frame(
+            RawInput::default().with_events(vec![Event::Text("reset".to_owned())]),
+            palette
+        ),
+        None
+    );
+    assert_eq!(
+        ctx.run_test_frame(key(Key::ArrowDown, Modifiers::NONE), palette),
+        None
+    );
+    assert_eq!(
+        ctx.run_test_frame(key(Key::Enter, Modifiers::NONE), palette),
+        Some(2)
+    );
+    assert!(!ctx.memory().command_palettes[&Id::new("palette")].open);
+}
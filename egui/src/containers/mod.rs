@@ -5,21 +5,25 @@
 pub(crate) mod area;
 pub(crate) mod collapsing_header;
 mod combo_box;
+pub(crate) mod command_palette;
 pub(crate) mod frame;
 pub(crate) mod panel;
 pub(crate) mod popup;
 pub(crate) mod resize;
 pub(crate) mod scroll_area;
+pub(crate) mod table;
 pub(crate) mod window;
 
 pub use {
     area::Area,
     collapsing_header::*,
     combo_box::*,
+    command_palette::{Command, CommandPalette},
     frame::Frame,
     panel::{CentralPanel, SidePanel, TopPanel},
     popup::*,
     resize::Resize,
     scroll_area::ScrollArea,
+    table::{SortOrder, Table, TableBody, TableRow},
     window::Window,
 };
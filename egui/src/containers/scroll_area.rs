@@ -7,52 +7,94 @@ pub(crate) struct State {
     /// Positive offset means scrolling down/right
     offset: Vec2,
 
-    show_scroll: bool,
+    /// Were the horizontal and vertical scroll bars shown last frame?
+    /// (Not named `show_scroll`, which was a single `bool` in earlier versions.)
+    show_bars: [bool; 2],
 
     /// Momentum, used for kinetic scrolling
     #[cfg_attr(feature = "persistence", serde(skip))]
     pub vel: Vec2,
-    /// Mouse offset relative to the top of the handle when started moving the handle.
-    scroll_start_offset_from_top: Option<f32>,
+
+    /// Mouse offset relative to the top (or left) of the handle when started moving the handle.
+    scroll_start_offset_from_top_left: [Option<f32>; 2],
 }
 
 impl Default for State {
     fn default() -> Self {
         Self {
             offset: Vec2::zero(),
-            show_scroll: false,
+            show_bars: [false; 2],
             vel: Vec2::zero(),
-            scroll_start_offset_from_top: None,
+            scroll_start_offset_from_top_left: [None; 2],
         }
     }
 }
 
-// TODO: rename VScroll
-/// Add vertical scrolling to a contained [`Ui`].
+/// Add vertical and/or horizontal scrolling to a contained [`Ui`].
+///
+/// ```
+/// # let ui = &mut egui::Ui::__test();
+/// egui::ScrollArea::both().max_height(200.0).show(ui, |ui| {
+///     ui.label("This text can be scrolled in both directions if it gets too long or too wide.");
+/// });
+/// ```
 #[derive(Clone, Debug)]
 pub struct ScrollArea {
-    max_height: f32,
+    /// Do we have horizontal and vertical scrolling?
+    has_bar: [bool; 2],
+    max_size: Vec2,
     always_show_scroll: bool,
     id_source: Option<Id>,
-    offset: Option<Vec2>,
+    offset: [Option<f32>; 2],
 }
 
 impl ScrollArea {
-    /// Will make the area be as high as it is allowed to be (i.e. fill the [`Ui`] it is in)
+    /// Vertical scrolling. Will make the area be as high as it is allowed to be (i.e. fill the [`Ui`] it is in)
     pub fn auto_sized() -> Self {
         Self::from_max_height(f32::INFINITY)
     }
 
+    /// Vertical scrolling.
     /// Use `f32::INFINITY` if you want the scroll area to expand to fit the surrounding Ui
     pub fn from_max_height(max_height: f32) -> Self {
+        Self::new([false, true]).max_height(max_height)
+    }
+
+    /// Horizontal scrolling. Will be as wide as the surrounding [`Ui`] allows,
+    /// and as high as the contents.
+    pub fn horizontal() -> Self {
+        Self::new([true, false])
+    }
+
+    /// Scrolling in both directions, e.g. for a large image.
+    pub fn both() -> Self {
+        Self::new([true, true])
+    }
+
+    fn new(has_bar: [bool; 2]) -> Self {
         Self {
-            max_height,
+            has_bar,
+            max_size: Vec2::infinity(),
             always_show_scroll: false,
             id_source: None,
-            offset: None,
+            offset: [None; 2],
         }
     }
 
+    /// The scroll area will never be wider than this.
+    /// Default: as wide as the surrounding [`Ui`] allows.
+    pub fn max_width(mut self, max_width: f32) -> Self {
+        self.max_size.x = max_width;
+        self
+    }
+
+    /// The scroll area will never be higher than this.
+    /// Default: as high as the surrounding [`Ui`] allows.
+    pub fn max_height(mut self, max_height: f32) -> Self {
+        self.max_size.y = max_height;
+        self
+    }
+
     /// If `false` (default), the scroll bar will be hidden when not needed/
     /// If `true`, the scroll bar will always be displayed even if not needed.
     pub fn always_show_scroll(mut self, always_show_scroll: bool) -> Self {
@@ -71,7 +113,13 @@ impl ScrollArea {
     /// See also: [`Ui::scroll_to_cursor`](crate::ui::Ui::scroll_to_cursor) and
     /// [`Response::scroll_to_me`](crate::types::Response::scroll_to_me)
     pub fn scroll_offset(mut self, offset: f32) -> Self {
-        self.offset = Some(Vec2::new(0.0, offset));
+        self.offset[1] = Some(offset);
+        self
+    }
+
+    /// Set the horizontal scroll offset position.
+    pub fn horizontal_scroll_offset(mut self, offset: f32) -> Self {
+        self.offset[0] = Some(offset);
         self
     }
 }
@@ -79,7 +127,9 @@ impl ScrollArea {
 struct Prepared {
     id: Id,
     state: State,
-    current_scroll_bar_width: f32,
+    has_bar: [bool; 2],
+    /// Width of the vertical bar (`x`) and height of the horizontal bar (`y`), while animating.
+    current_bar_use: Vec2,
     always_show_scroll: bool,
     inner_rect: Rect,
     content_ui: Ui,
@@ -88,7 +138,8 @@ struct Prepared {
 impl ScrollArea {
     fn begin(self, ui: &mut Ui) -> Prepared {
         let Self {
-            max_height,
+            has_bar,
+            max_size,
             always_show_scroll,
             id_source,
             offset,
@@ -105,8 +156,10 @@ impl ScrollArea {
             .cloned()
             .unwrap_or_default();
 
-        if let Some(offset) = offset {
-            state.offset = offset;
+        for d in 0..2 {
+            if let Some(offset) = offset[d] {
+                state.offset[d] = offset;
+            }
         }
 
         // content: size of contents (generally large; that's why we want scroll bars)
@@ -115,40 +168,55 @@ impl ScrollArea {
 
         let max_scroll_bar_width = max_scroll_bar_width_with_margin(ui);
 
-        let current_scroll_bar_width = if always_show_scroll {
-            max_scroll_bar_width
-        } else {
-            max_scroll_bar_width * ui.ctx().animate_bool(id, state.show_scroll)
+        let bar_use = |d: usize| {
+            if !has_bar[d] {
+                0.0
+            } else if always_show_scroll {
+                max_scroll_bar_width
+            } else {
+                max_scroll_bar_width * ctx.animate_bool(bar_id(id, d), state.show_bars[d])
+            }
         };
+        let current_bar_use = vec2(bar_use(1), bar_use(0));
 
         let available_outer = ui.available_rect_before_wrap();
 
-        let outer_size = vec2(
-            available_outer.width(),
-            available_outer.height().at_most(max_height),
-        );
+        let outer_size = available_outer.size().min(max_size);
 
-        let inner_size = outer_size - vec2(current_scroll_bar_width, 0.0);
+        let inner_size = outer_size - current_bar_use;
         let inner_rect = Rect::from_min_size(available_outer.min, inner_size);
 
+        let mut content_max_size = inner_size;
+        for d in 0..2 {
+            if has_bar[d] {
+                content_max_size[d] = f32::INFINITY;
+            }
+        }
+
         let mut content_ui = ui.child_ui(
-            Rect::from_min_size(
-                inner_rect.min - state.offset,
-                vec2(inner_size.x, f32::INFINITY),
-            ),
+            Rect::from_min_size(inner_rect.min - state.offset, content_max_size),
             *ui.layout(),
         );
         let mut content_clip_rect = inner_rect.expand(ui.style().visuals.clip_rect_margin);
         content_clip_rect = content_clip_rect.intersect(ui.clip_rect());
-        content_clip_rect.max.x = ui.clip_rect().max.x - current_scroll_bar_width; // Nice handling of forced resizing beyond the possible
+        for d in 0..2 {
+            if !has_bar[d] {
+                // Nice handling of forced resizing beyond the possible
+                content_clip_rect.max[d] = ui.clip_rect().max[d] - current_bar_use[d];
+            } else if current_bar_use[d] > 0.0 {
+                // Don't paint under the scroll bar at the end of this axis:
+                content_clip_rect.max[d] = content_clip_rect.max[d].min(inner_rect.max[d]);
+            }
+        }
         content_ui.set_clip_rect(content_clip_rect);
 
         Prepared {
             id,
             state,
+            has_bar,
+            current_bar_use,
             always_show_scroll,
             inner_rect,
-            current_scroll_bar_width,
             content_ui,
         }
     }
@@ -159,6 +227,127 @@ impl ScrollArea {
         prepared.end(ui);
         ret
     }
+
+    /// Efficiently show only the visible rows of a long list,
+    /// where every row is `row_height_sans_spacing` high.
+    ///
+    /// `add_contents` is called with the range of rows that are visible,
+    /// and space is reserved for all the rest, so the scroll bar works as usual.
+    ///
+    /// The rows are added to a [`Ui`] whose auto-`Id`:s depend on which rows are visible,
+    /// so any widget with state (like a [`CollapsingHeader`]) needs an explicit id, e.g. based on the row index.
+    ///
+    /// ```
+    /// # let ui = &mut egui::Ui::__test();
+    /// let text_style = egui::TextStyle::Body;
+    /// let row_height = ui.fonts()[text_style].row_height();
+    /// let total_rows = 10_000;
+    /// egui::ScrollArea::from_max_height(200.0).show_rows(ui, row_height, total_rows, |ui, row_range| {
+    ///     assert!(row_range.end - row_range.start < 100);
+    ///     for row in row_range {
+    ///         ui.label(format!("Row {}/{}", row + 1, total_rows));
+    ///     }
+    /// });
+    /// ```
+    pub fn show_rows<R>(
+        self,
+        ui: &mut Ui,
+        row_height_sans_spacing: f32,
+        total_rows: usize,
+        add_contents: impl FnOnce(&mut Ui, std::ops::Range<usize>) -> R,
+    ) -> R {
+        let spacing = ui.style().spacing.item_spacing.y;
+        let row_height_with_spacing = row_height_sans_spacing + spacing;
+        self.show_visible_rows(ui, add_contents, |viewport| {
+            let max_row =
+                ((viewport.end() / row_height_with_spacing).ceil() as usize + 1).min(total_rows);
+            let min_row = ((viewport.start() / row_height_with_spacing)
+                .floor()
+                .max(0.0) as usize)
+                .min(max_row);
+            let total_height = (total_rows as f32 * row_height_with_spacing - spacing).max(0.0);
+            (
+                min_row..max_row,
+                min_row as f32 * row_height_with_spacing,
+                total_height,
+            )
+        })
+    }
+
+    /// Like [`Self::show_rows`], but each row can have a different height.
+    ///
+    /// `row_height_sans_spacing(row)` should return the height of the given row.
+    /// It is called for every row each frame, so it should be cheap.
+    ///
+    /// ```
+    /// # let ui = &mut egui::Ui::__test();
+    /// let row_height = |row: usize| if row % 10 == 0 { 40.0 } else { 20.0 };
+    /// egui::ScrollArea::from_max_height(200.0).show_variable_rows(ui, 10_000, row_height, |ui, row_range| {
+    ///     for row in row_range {
+    ///         let (_, rect) = ui.allocate_space(egui::vec2(100.0, row_height(row)));
+    ///         ui.painter().rect_stroke(rect, 0.0, (1.0, egui::Color32::WHITE));
+    ///     }
+    /// });
+    /// ```
+    pub fn show_variable_rows<R>(
+        self,
+        ui: &mut Ui,
+        total_rows: usize,
+        row_height_sans_spacing: impl Fn(usize) -> f32,
+        add_contents: impl FnOnce(&mut Ui, std::ops::Range<usize>) -> R,
+    ) -> R {
+        let spacing = ui.style().spacing.item_spacing.y;
+        self.show_visible_rows(ui, add_contents, |viewport| {
+            let mut min_row = None;
+            let mut max_row = total_rows;
+            let mut first_row_top = 0.0;
+            let mut y = 0.0;
+            for row in 0..total_rows {
+                let row_top = y;
+                y += row_height_sans_spacing(row) + spacing;
+                if min_row.is_none() && y > *viewport.start() {
+                    min_row = Some(row);
+                    first_row_top = row_top;
+                }
+                if row_top > *viewport.end() && max_row == total_rows {
+                    max_row = row;
+                }
+            }
+            let total_height = (y - spacing).max(0.0);
+            let min_row = min_row.unwrap_or(total_rows).min(max_row);
+            (min_row..max_row, first_row_top, total_height)
+        })
+    }
+
+    /// `visible_rows` gets the visible y range (in content coordinates, i.e. zero at the top of the contents)
+    /// and returns the range of rows to show, where the first of those rows start,
+    /// and the height of all the rows together.
+    fn show_visible_rows<R>(
+        self,
+        ui: &mut Ui,
+        add_contents: impl FnOnce(&mut Ui, std::ops::Range<usize>) -> R,
+        visible_rows: impl FnOnce(std::ops::RangeInclusive<f32>) -> (std::ops::Range<usize>, f32, f32),
+    ) -> R {
+        let mut prepared = self.begin(ui);
+        let viewport_top = prepared.state.offset.y;
+        let viewport = viewport_top..=viewport_top + prepared.inner_rect.height();
+        let (row_range, first_row_top, total_height) = visible_rows(viewport);
+
+        let content_ui = &mut prepared.content_ui;
+        content_ui.set_min_height(total_height);
+        let content_rect = content_ui.max_rect();
+        let rows_rect = Rect::from_min_max(
+            pos2(content_rect.left(), content_rect.top() + first_row_top),
+            content_rect.max,
+        );
+        let mut rows_ui =
+            content_ui.child_ui_with_id_source(rows_rect, *content_ui.layout(), "rows");
+        let ret = add_contents(&mut rows_ui, row_range);
+        content_ui.expand_to_include_rect(rows_ui.min_rect());
+
+        prepared.end(ui);
+        ret
+    }
 }
 
 impl Prepared {
@@ -166,55 +355,71 @@ impl Prepared {
         let Prepared {
             id,
             mut state,
-            inner_rect,
+            has_bar,
+            mut current_bar_use,
             always_show_scroll,
-            mut current_scroll_bar_width,
+            inner_rect,
             content_ui,
         } = self;
 
         let content_size = content_ui.min_size();
 
-        // We take the scroll target so only this ScrollArea will use it.
-        let scroll_target = content_ui.ctx().frame_state().scroll_target.take();
-        if let Some((scroll_y, align)) = scroll_target {
-            let center_factor = align.to_factor();
+        if has_bar[1] {
+            // We take the scroll target so only this ScrollArea will use it.
+            let scroll_target = content_ui.ctx().frame_state().scroll_target.take();
+            if let Some((scroll_y, align)) = scroll_target {
+                let center_factor = align.to_factor();
 
-            let top = content_ui.min_rect().top();
-            let visible_range = top..=top + content_ui.clip_rect().height();
-            let offset_y = scroll_y - lerp(visible_range, center_factor);
+                let top = content_ui.min_rect().top();
+                let visible_range = top..=top + content_ui.clip_rect().height();
+                let offset_y = scroll_y - lerp(visible_range, center_factor);
 
-            let mut spacing = ui.style().spacing.item_spacing.y;
+                let mut spacing = ui.style().spacing.item_spacing.y;
 
-            // Depending on the alignment we need to add or subtract the spacing
-            spacing *= remap(center_factor, 0.0..=1.0, -1.0..=1.0);
+                // Depending on the alignment we need to add or subtract the spacing
+                spacing *= remap(center_factor, 0.0..=1.0, -1.0..=1.0);
 
-            state.offset.y = offset_y + spacing;
+                state.offset.y = offset_y + spacing;
+            }
         }
 
-        let width = if inner_rect.width().is_finite() {
+        let width = if has_bar[0] {
+            inner_rect.width()
+        } else if inner_rect.width().is_finite() {
             inner_rect.width().max(content_size.x) // Expand width to fit content
         } else {
             // ScrollArea is in an infinitely wide parent
             content_size.x
         };
+        let height = if has_bar[1] {
+            inner_rect.height()
+        } else {
+            content_size.y // Follow the height of the content
+        };
 
-        let inner_rect = Rect::from_min_size(inner_rect.min, vec2(width, inner_rect.height()));
+        let inner_rect = Rect::from_min_size(inner_rect.min, vec2(width, height));
 
-        let outer_rect = Rect::from_min_size(
-            inner_rect.min,
-            inner_rect.size() + vec2(current_scroll_bar_width, 0.0),
-        );
+        let outer_rect = Rect::from_min_size(inner_rect.min, inner_rect.size() + current_bar_use);
 
-        let content_is_too_small = content_size.y > inner_rect.height();
+        let content_is_too_large = [
+            has_bar[0] && content_size.x > inner_rect.width(),
+            has_bar[1] && content_size.y > inner_rect.height(),
+        ];
 
-        if content_is_too_small {
+        if content_is_too_large[0] || content_is_too_large[1] {
             // Drag contents to scroll (for touch screens mostly):
             let content_response = ui.interact(inner_rect, id.with("area"), Sense::drag());
 
             let input = ui.input();
             if content_response.active {
-                state.offset.y -= input.mouse.delta.y;
-                state.vel = input.mouse.velocity;
+                for (d, &too_large) in content_is_too_large.iter().enumerate() {
+                    if too_large {
+                        state.offset[d] -= input.mouse.delta[d];
+                        state.vel[d] = input.mouse.velocity[d];
+                    } else {
+                        state.vel[d] = 0.0;
+                    }
+                }
             } else {
                 let stop_speed = 20.0; // Pixels per second.
                 let friction_coeff = 1000.0; // Pixels per second squared.
@@ -227,100 +432,124 @@ impl Prepared {
                     state.vel -= friction * state.vel.normalized();
                     // Offset has an inverted coordinate system compared to
                     // the velocity, so we subtract it instead of adding it
-                    state.offset.y -= state.vel.y * dt;
+                    state.offset -= state.vel * dt;
                     ui.ctx().request_repaint();
                 }
             }
         }
 
-        let max_offset = content_size.y - inner_rect.height();
+        let max_offset = content_size - inner_rect.size();
         if ui.rect_contains_mouse(outer_rect) {
             let mut frame_state = ui.ctx().frame_state();
-            let scroll_delta = frame_state.scroll_delta;
-
-            let scrolling_up = state.offset.y > 0.0 && scroll_delta.y > 0.0;
-            let scrolling_down = state.offset.y < max_offset && scroll_delta.y < 0.0;
-
-            if scrolling_up || scrolling_down {
-                state.offset.y -= scroll_delta.y;
-                // Clear scroll delta so no parent scroll will use it.
-                frame_state.scroll_delta = Vec2::zero();
+            for d in 0..2 {
+                if has_bar[d] {
+                    let scroll_delta = frame_state.scroll_delta[d];
+
+                    let scrolling_up = state.offset[d] > 0.0 && scroll_delta > 0.0;
+                    let scrolling_down = state.offset[d] < max_offset[d] && scroll_delta < 0.0;
+
+                    if scrolling_up || scrolling_down {
+                        state.offset[d] -= scroll_delta;
+                        // Clear scroll delta so no parent scroll will use it.
+                        frame_state.scroll_delta[d] = 0.0;
+                    }
+                }
             }
         }
 
-        let show_scroll_this_frame = content_is_too_small || always_show_scroll;
+        let show_scroll_this_frame = [
+            content_is_too_large[0] || (has_bar[0] && always_show_scroll),
+            content_is_too_large[1] || (has_bar[1] && always_show_scroll),
+        ];
 
         let max_scroll_bar_width = max_scroll_bar_width_with_margin(ui);
 
-        if show_scroll_this_frame && current_scroll_bar_width <= 0.0 {
-            // Avoid frame delay; start showing scroll bar right away:
-            current_scroll_bar_width = max_scroll_bar_width * ui.ctx().animate_bool(id, true);
+        for d in 0..2 {
+            // Width of the vertical bar, or height of the horizontal bar:
+            let bar_use = &mut current_bar_use[1 - d];
+            if show_scroll_this_frame[d] && *bar_use <= 0.0 {
+                // Avoid frame delay; start showing scroll bar right away:
+                *bar_use = max_scroll_bar_width * ui.ctx().animate_bool(bar_id(id, d), true);
+            }
         }
 
-        if current_scroll_bar_width > 0.0 {
-            let animation_t = current_scroll_bar_width / max_scroll_bar_width;
+        for d in 0..2 {
+            let bar_use = current_bar_use[1 - d];
+            if bar_use <= 0.0 {
+                continue;
+            }
+
+            let animation_t = bar_use / max_scroll_bar_width;
             // margin between contents and scroll bar
-            let margin = animation_t * ui.style().spacing.item_spacing.x;
-            let left = inner_rect.right() + margin;
-            let right = outer_rect.right();
-            let corner_radius = (right - left) / 2.0;
-            let top = inner_rect.top();
-            let bottom = inner_rect.bottom();
-
-            let outer_scroll_rect = Rect::from_min_max(
-                pos2(left, inner_rect.top()),
-                pos2(right, inner_rect.bottom()),
-            );
+            let margin = animation_t * ui.style().spacing.item_spacing[1 - d];
 
-            let from_content =
-                |content_y| remap_clamp(content_y, 0.0..=content_size.y, top..=bottom);
+            // "main" is along the direction of scrolling.
+            let (outer_scroll_rect, interact_id) = if d == 0 {
+                let rect = Rect::from_min_max(
+                    pos2(inner_rect.left(), inner_rect.bottom() + margin),
+                    pos2(inner_rect.right(), inner_rect.bottom() + bar_use),
+                );
+                (rect, id.with("horizontal"))
+            } else {
+                let rect = Rect::from_min_max(
+                    pos2(inner_rect.right() + margin, inner_rect.top()),
+                    pos2(inner_rect.right() + bar_use, inner_rect.bottom()),
+                );
+                (rect, id.with("vertical"))
+            };
+            let main_min = outer_scroll_rect.min[d];
+            let main_max = outer_scroll_rect.max[d];
+            let corner_radius = outer_scroll_rect.size()[1 - d] / 2.0;
+            let inner_main_size = inner_rect.size()[d];
 
-            let handle_rect = Rect::from_min_max(
-                pos2(left, from_content(state.offset.y)),
-                pos2(right, from_content(state.offset.y + inner_rect.height())),
-            );
+            let from_content =
+                |content| remap_clamp(content, 0.0..=content_size[d], main_min..=main_max);
+            let handle_rect = |offset: f32| {
+                let mut rect = outer_scroll_rect;
+                rect.min[d] = from_content(offset);
+                rect.max[d] = from_content(offset + inner_main_size);
+                rect
+            };
 
-            let interact_id = id.with("vertical");
             let response = ui.interact(outer_scroll_rect, interact_id, Sense::click_and_drag());
 
             if response.active {
                 if let Some(mouse_pos) = ui.input().mouse.pos {
-                    let scroll_start_offset_from_top =
-                        state.scroll_start_offset_from_top.get_or_insert_with(|| {
+                    let handle_rect = handle_rect(state.offset[d]);
+                    let handle_main_size = handle_rect.size()[d];
+                    let scroll_start_offset_from_top_left =
+                        state.scroll_start_offset_from_top_left[d].get_or_insert_with(|| {
                             if handle_rect.contains(mouse_pos) {
-                                mouse_pos.y - handle_rect.top()
+                                mouse_pos[d] - handle_rect.min[d]
                             } else {
-                                let handle_top_pos_at_bottom = bottom - handle_rect.height();
+                                let handle_top_pos_at_bottom = main_max - handle_main_size;
                                 // Calculate the new handle top position, centering the handle on the mouse.
                                 let new_handle_top_pos = clamp(
-                                    mouse_pos.y - handle_rect.height() / 2.0,
-                                    top..=handle_top_pos_at_bottom,
+                                    mouse_pos[d] - handle_main_size / 2.0,
+                                    main_min..=handle_top_pos_at_bottom,
                                 );
-                                mouse_pos.y - new_handle_top_pos
+                                mouse_pos[d] - new_handle_top_pos
                             }
                         });
 
-                    let new_handle_top = mouse_pos.y - *scroll_start_offset_from_top;
-                    state.offset.y = remap(new_handle_top, top..=bottom, 0.0..=content_size.y);
+                    let new_handle_top = mouse_pos[d] - *scroll_start_offset_from_top_left;
+                    state.offset[d] =
+                        remap(new_handle_top, main_min..=main_max, 0.0..=content_size[d]);
                 }
             } else {
-                state.scroll_start_offset_from_top = None;
+                state.scroll_start_offset_from_top_left[d] = None;
             }
 
-            state.offset.y = state.offset.y.max(0.0);
-            state.offset.y = state.offset.y.min(max_offset);
+            state.offset[d] = state.offset[d].max(0.0);
+            state.offset[d] = state.offset[d].min(max_offset[d]);
 
             // Avoid frame-delay by calculating a new handle rect:
-            let mut handle_rect = Rect::from_min_max(
-                pos2(left, from_content(state.offset.y)),
-                pos2(right, from_content(state.offset.y + inner_rect.height())),
-            );
-            let min_handle_height = (2.0 * corner_radius).max(8.0);
-            if handle_rect.size().y < min_handle_height {
-                handle_rect = Rect::from_center_size(
-                    handle_rect.center(),
-                    vec2(handle_rect.size().x, min_handle_height),
-                );
+            let mut handle_rect = handle_rect(state.offset[d]);
+            let min_handle_size = (2.0 * corner_radius).max(8.0);
+            if handle_rect.size()[d] < min_handle_size {
+                let mut size = handle_rect.size();
+                size[d] = min_handle_size;
+                handle_rect = Rect::from_center_size(handle_rect.center(), size);
             }
 
             let visuals = ui.style().interact(&response);
@@ -342,24 +571,92 @@ impl Prepared {
             });
         }
 
-        let size = vec2(
-            outer_rect.size().x,
-            outer_rect.size().y.min(content_size.y), // shrink if content is so small that we don't need scroll bars
-        );
+        let outer_rect = Rect::from_min_size(inner_rect.min, inner_rect.size() + current_bar_use);
+        let mut size = outer_rect.size();
+        for d in 0..2 {
+            if has_bar[d] {
+                // shrink if content is so small that we don't need scroll bars
+                size[d] = size[d].min(content_size[d] + current_bar_use[1 - d]);
+            }
+        }
         ui.advance_cursor_after_rect(Rect::from_min_size(outer_rect.min, size));
 
-        if show_scroll_this_frame != state.show_scroll {
+        if show_scroll_this_frame != state.show_bars {
             ui.ctx().request_repaint();
         }
 
-        state.offset.y = state.offset.y.min(content_size.y - inner_rect.height());
-        state.offset.y = state.offset.y.max(0.0);
-        state.show_scroll = show_scroll_this_frame;
+        state.offset = state.offset.min(content_size - inner_rect.size());
+        state.offset = state.offset.max(Vec2::zero());
+        state.show_bars = show_scroll_this_frame;
 
         ui.memory().scroll_areas.insert(id, state);
     }
 }
 
+/// The `Id` used for animating the showing/hiding of the horizontal (`d == 0`) or vertical (`d == 1`) scroll bar.
+fn bar_id(id: Id, d: usize) -> Id {
+    if d == 0 {
+        id.with("horizontal_bar")
+    } else {
+        id
+    }
+}
+
 fn max_scroll_bar_width_with_margin(ui: &Ui) -> f32 {
     ui.style().spacing.item_spacing.x + 16.0
 }
+
+#[test]
+fn test_horizontal_offset_is_clamped() {
+    /// Returns the horizontal offset and where the contents are.
+    fn show(ctx: &CtxRef, offset: Option<f32>) -> (f32, Rect) {
+        let mut scroll_area = ScrollArea::horizontal().max_width(100.0);
+        if let Some(offset) = offset {
+            scroll_area = scroll_area.horizontal_scroll_offset(offset);
+        }
+        let mut content_rect = Rect::nothing();
+        CentralPanel::default().show(ctx, |ui| {
+            scroll_area.show(ui, |ui| {
+                content_rect = ui.allocate_space(vec2(300.0, 20.0)).1;
+            });
+        });
+        let states: Vec<State> = ctx.memory().scroll_areas.values().cloned().collect();
+        assert_eq!(states.len(), 1);
+        (states[0].offset.x, content_rect)
+    }
+
+    let mut ctx = CtxRef::default();
+    let (offset, content_rect) = ctx.run_test_frame(RawInput::default(), |ctx| show(ctx, None));
+    assert_eq!(offset, 0.0);
+    let mouse_pos = content_rect.left_center() + vec2(50.0, 0.0);
+
+    // Shift + mouse wheel scrolls to the right, but not past the end of the contents:
+    let shift_wheel = RawInput {
+        scroll_delta: vec2(0.0, -1000.0),
+        modifiers: Modifiers {
+            shift: true,
+            ..Default::default()
+        },
+        ..RawInput::default().with_mouse_pos(mouse_pos)
+    };
+    let (offset, _) = ctx.run_test_frame(shift_wheel, |ctx| show(ctx, None));
+    assert_eq!(
+        offset,
+        300.0 - 100.0,
+        "the area is at most `max_width` wide"
+    );
+
+    // Scrolling left stops at the start:
+    let wheel_left = RawInput {
+        scroll_delta: vec2(1000.0, 0.0),
+        ..RawInput::default().with_mouse_pos(mouse_pos)
+    };
+    let (offset, _) = ctx.run_test_frame(wheel_left, |ctx| show(ctx, None));
+    assert_eq!(offset, 0.0);
+
+    // So does a set offset:
+    let (offset, _) = ctx.run_test_frame(RawInput::default(), |ctx| show(ctx, Some(1000.0)));
+    assert_eq!(offset, 200.0);
+    let (offset, _) = ctx.run_test_frame(RawInput::default(), |ctx| show(ctx, Some(-50.0)));
+    assert_eq!(offset, 0.0);
+}
@@ -0,0 +1,411 @@
+//! A table with a header, resizable and sortable columns, and virtualized rows.
+
+use std::hash::Hash;
+
+use crate::*;
+
+/// In which order the user wants a [`Table`] column sorted.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+impl SortOrder {
+    fn symbol(self) -> &'static str {
+        match self {
+            Self::Ascending => "⏶",
+            Self::Descending => "⏷",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "persistence", serde(default))]
+pub(crate) struct State {
+    /// Width of each column, after the user has resized them.
+    widths: Vec<f32>,
+
+    /// The column the user clicked to sort by, if any.
+    sort: Option<(usize, SortOrder)>,
+}
+
+#[derive(Clone, Debug)]
+struct Column {
+    title: String,
+    initial_width: f32,
+}
+
+/// A table with a header row that stays put while the rows scroll.
+///
+/// The user can resize the columns by dragging the lines between them in the header,
+/// and (if [`Self::sortable`]) click a column header to sort by it.
+/// The column widths and the sorting is remembered in [`Memory`].
+///
+/// Only the rows that are visible are laid out, so tables can have very many rows.
+/// Every row has the same height.
+///
+/// ```
+/// # let ui = &mut egui::Ui::__test();
+/// let mut files = vec![("notes.txt", 120), ("photo.png", 4096)];
+/// egui::Table::new("files")
+///     .column("Name", 120.0)
+///     .column("Size", 60.0)
+///     .sortable(true)
+///     .show(ui, |body| {
+///         if let Some((column, order)) = body.sort() {
+///             files.sort_by(|a, b| {
+///                 let ordering = if column == 0 { a.0.cmp(b.0) } else { a.1.cmp(&b.1) };
+///                 if order == egui::SortOrder::Descending {
+///                     ordering.reverse()
+///                 } else {
+///                     ordering
+///                 }
+///             });
+///         }
+///         body.rows(files.len(), |row_index, row| {
+///             let (name, size) = files[row_index];
+///             row.col(|ui| ui.label(name));
+///             row.col(|ui| ui.label(format!("{} B", size)));
+///         });
+///     });
+/// ```
+#[derive(Clone, Debug)]
+pub struct Table {
+    id_source: Id,
+    columns: Vec<Column>,
+    row_height: Option<f32>,
+    max_height: f32,
+    sortable: bool,
+    striped: bool,
+}
+
+impl Table {
+    pub fn new(id_source: impl Hash) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            columns: vec![],
+            row_height: None,
+            max_height: f32::INFINITY,
+            sortable: false,
+            striped: false,
+        }
+    }
+
+    /// Add a column with the given header text.
+    /// The user can make it wider or narrower.
+    pub fn column(mut self, title: impl Into<String>, initial_width: f32) -> Self {
+        self.columns.push(Column {
+            title: title.into(),
+            initial_width,
+        });
+        self
+    }
+
+    /// Height of each row. Default: [`Spacing::interact_size.y`].
+    pub fn row_height(mut self, row_height: f32) -> Self {
+        self.row_height = Some(row_height);
+        self
+    }
+
+    /// Maximum height of the rows, after which they scroll.
+    /// Default: as high as the surrounding [`Ui`] allows.
+    pub fn max_height(mut self, max_height: f32) -> Self {
+        self.max_height = max_height;
+        self
+    }
+
+    /// Let the user click a column header to sort by that column.
+    /// You need to do the actual sorting; see [`TableBody::sort`].
+    /// Default: `false`.
+    pub fn sortable(mut self, sortable: bool) -> Self {
+        self.sortable = sortable;
+        self
+    }
+
+    /// If `true`, add a subtle background color to every other row.
+    /// Default: `false`.
+    pub fn striped(mut self, striped: bool) -> Self {
+        self.striped = striped;
+        self
+    }
+}
+
+impl Table {
+    pub fn show<R>(self, ui: &mut Ui, add_body: impl FnOnce(&mut TableBody<'_>) -> R) -> R {
+        ui.vertical(|ui| self.show_impl(ui, add_body)).0
+    }
+
+    fn show_impl<R>(self, ui: &mut Ui, add_body: impl FnOnce(&mut TableBody<'_>) -> R) -> R {
+        let Self {
+            id_source,
+            columns,
+            row_height,
+            max_height,
+            sortable,
+            striped,
+        } = self;
+
+        let id = ui.make_persistent_id(id_source);
+        let mut state = ui.memory().tables.get(&id).cloned().unwrap_or_default();
+        if state.widths.len() != columns.len() {
+            state.widths = columns.iter().map(|column| column.initial_width).collect();
+        }
+
+        let row_height = row_height.unwrap_or_else(|| ui.style().spacing.interact_size.y);
+        let cell_padding = ui.style().spacing.button_padding.x;
+        let grab_radius = ui.style().interaction.resize_grab_radius_side;
+        let min_width = 2.0 * grab_radius + 2.0 * cell_padding;
+
+        // Header:
+        let total_width: f32 = state.widths.iter().sum();
+        let (_, header_rect) = ui.allocate_space(vec2(total_width, row_height));
+        ui.painter().rect_filled(
+            header_rect,
+            0.0,
+            ui.style().visuals.widgets.noninteractive.bg_fill,
+        );
+
+        // Resize the columns first, so that the new widths are used right away:
+        let mut right = header_rect.left();
+        let mut separators = vec![];
+        for (i, width) in state.widths.iter_mut().enumerate() {
+            let handle_rect = Rect::from_center_size(
+                pos2(right + *width, header_rect.center().y),
+                vec2(2.0 * grab_radius, header_rect.height()),
+            );
+            let response = ui.interact(handle_rect, id.with("resize").with(i), Sense::drag());
+            if response.active {
+                *width = (*width + ui.input().mouse.delta.x).at_least(min_width);
+            }
+            if response.hovered || response.active {
+                ui.output().cursor_icon = CursorIcon::ResizeHorizontal;
+            }
+            right += *width;
+            separators.push((right, response));
+        }
+
+        let mut left = header_rect.left();
+        for (i, (column, width)) in columns.iter().zip(&state.widths).enumerate() {
+            let cell_rect = Rect::from_min_size(
+                pos2(left, header_rect.top()),
+                vec2(*width, header_rect.height()),
+            );
+            left += width;
+
+            let sense = if sortable {
+                Sense::click()
+            } else {
+                Sense::hover()
+            };
+            let response = ui.interact(
+                cell_rect.shrink2(vec2(grab_radius, 0.0)),
+                id.with("header").with(i),
+                sense,
+            );
+            if response.clicked {
+                state.sort = match state.sort {
+                    Some((sorted, SortOrder::Ascending)) if sorted == i => {
+                        Some((i, SortOrder::Descending))
+                    }
+                    _ => Some((i, SortOrder::Ascending)),
+                };
+            }
+
+            let text = match state.sort {
+                Some((sorted, order)) if sorted == i => {
+                    format!("{} {}", column.title, order.symbol())
+                }
+                _ => column.title.clone(),
+            };
+            let visuals = ui.style().interact(&response);
+            let painter = ui.painter().sub_region(cell_rect);
+            if sortable && response.hovered {
+                painter.rect_filled(cell_rect, 0.0, visuals.bg_fill);
+            }
+            painter.text(
+                cell_rect.left_center() + vec2(cell_padding, 0.0),
+                Align2::LEFT_CENTER,
+                text,
+                TextStyle::Button,
+                visuals.text_color(),
+            );
+        }
+
+        for (x, response) in separators {
+            let stroke = if response.hovered || response.active {
+                ui.style().interact(&response).fg_stroke
+            } else {
+                ui.style().visuals.widgets.noninteractive.bg_stroke
+            };
+            ui.painter().line_segment(
+                [pos2(x, header_rect.top()), pos2(x, header_rect.bottom())],
+                stroke,
+            );
+        }
+
+        // Rows:
+        let ret = ScrollArea::from_max_height(max_height)
+            .id_source(id.with("body"))
+            .show(ui, |ui| {
+                let mut body = TableBody {
+                    ui,
+                    widths: &state.widths,
+                    row_height,
+                    cell_padding,
+                    striped,
+                    sort: state.sort,
+                };
+                add_body(&mut body)
+            });
+
+        ui.memory().tables.insert(id, state);
+        ret
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// The rows of a [`Table`].
+pub struct TableBody<'a> {
+    ui: &'a mut Ui,
+    widths: &'a [f32],
+    row_height: f32,
+    cell_padding: f32,
+    striped: bool,
+    sort: Option<(usize, SortOrder)>,
+}
+
+impl<'a> TableBody<'a> {
+    /// The column (index) the user wants the rows sorted by, if any.
+    ///
+    /// Sort your data accordingly before calling [`Self::rows`].
+    pub fn sort(&self) -> Option<(usize, SortOrder)> {
+        self.sort
+    }
+
+    /// Add `num_rows` rows. `add_row` is only called for the rows that are visible,
+    /// with the index of the row and a [`TableRow`] to add the cells to.
+    pub fn rows(&mut self, num_rows: usize, mut add_row: impl FnMut(usize, &mut TableRow<'_>)) {
+        let Self {
+            ui,
+            widths,
+            row_height,
+            cell_padding,
+            striped,
+            sort: _,
+        } = self;
+        let row_height = *row_height;
+
+        let total_width: f32 = widths.iter().sum();
+        let (_, rows_rect) = ui.allocate_space(vec2(total_width, row_height * num_rows as f32));
+
+        let clip_rect = ui.clip_rect();
+        let first_row = ((clip_rect.top() - rows_rect.top()) / row_height)
+            .floor()
+            .max(0.0) as usize;
+        let end_row = ((clip_rect.bottom() - rows_rect.top()) / row_height)
+            .ceil()
+            .max(0.0) as usize;
+
+        for row_index in first_row..end_row.min(num_rows) {
+            let row_rect = Rect::from_min_size(
+                pos2(
+                    rows_rect.left(),
+                    rows_rect.top() + row_index as f32 * row_height,
+                ),
+                vec2(total_width, row_height),
+            );
+            if *striped && row_index % 2 == 1 {
+                ui.painter()
+                    .rect_filled(row_rect, 0.0, Rgba::from_white_alpha(0.0075));
+            }
+
+            let mut row = TableRow {
+                ui,
+                widths,
+                rect: row_rect,
+                cell_padding: *cell_padding,
+                row_index,
+                col: 0,
+                left: row_rect.left(),
+            };
+            add_row(row_index, &mut row);
+        }
+    }
+}
+
+/// A row of a [`Table`]. Add one cell per column with [`Self::col`].
+pub struct TableRow<'a> {
+    ui: &'a mut Ui,
+    widths: &'a [f32],
+    rect: Rect,
+    cell_padding: f32,
+    row_index: usize,
+    col: usize,
+    left: f32,
+}
+
+impl<'a> TableRow<'a> {
+    /// Add the contents of the next cell.
+    ///
+    /// Returns `None` (and shows an error) if the row already has a cell in every column.
+    pub fn col<R>(&mut self, add_contents: impl FnOnce(&mut Ui) -> R) -> Option<R> {
+        if self.col >= self.widths.len() {
+            self.ui.painter().error(
+                self.rect.right_top(),
+                format!("More cells than the {} columns", self.widths.len()),
+            );
+            return None;
+        }
+        let width = self.widths[self.col];
+        let cell_rect = Rect::from_min_size(
+            pos2(self.left, self.rect.top()),
+            vec2(width, self.rect.height()),
+        );
+
+        let mut cell_ui = self.ui.child_ui_with_id_source(
+            cell_rect.shrink2(vec2(self.cell_padding, 0.0)),
+            Layout::left_to_right(),
+            (self.row_index, self.col),
+        );
+        cell_ui.set_clip_rect(cell_rect.intersect(self.ui.clip_rect()));
+        let ret = add_contents(&mut cell_ui);
+
+        self.left += width;
+        self.col += 1;
+        Some(ret)
+    }
+}
+
+#[test]
+fn test_table_layout() {
+    let mut ui = Ui::__test();
+    let mut cells = vec![];
+    Table::new("table")
+        .column("A", 100.0)
+        .column("B", 50.0)
+        .row_height(20.0)
+        .show(&mut ui, |body| {
+            body.rows(3, |row_index, row| {
+                for col in 0..3 {
+                    let added = row.col(|ui| cells.push((row_index, col, ui.max_rect())));
+                    assert_eq!(added.is_some(), col < 2, "The extra cell is skipped");
+                }
+            });
+        });
+
+    assert_eq!(cells.len(), 6);
+    let (_, _, first) = cells[0];
+    let padding = ui.style().spacing.button_padding.x;
+    for &(row_index, col, rect) in &cells {
+        let left = first.left() - padding + if col == 0 { 0.0 } else { 100.0 };
+        let width = if col == 0 { 100.0 } else { 50.0 };
+        assert_eq!(rect.left(), left + padding);
+        assert_eq!(rect.width(), width - 2.0 * padding);
+        assert_eq!(rect.top(), first.top() + 20.0 * row_index as f32);
+        assert_eq!(rect.height(), 20.0);
+    }
+}
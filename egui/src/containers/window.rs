@@ -1,6 +1,11 @@
 // WARNING: the code in here is horrible. It is a behemoth that needs breaking up into simpler parts.
 
-use crate::{paint::*, widgets::*, *};
+use crate::{
+    accessibility::{AccessNode, AccessRole},
+    paint::*,
+    widgets::*,
+    *,
+};
 
 use super::*;
 
@@ -230,6 +235,7 @@ impl<'open> Window<'open> {
 
         let area_id = area.id;
         let area_layer_id = area.layer();
+        let title = title_label.text().to_owned();
         let resize_id = area_id.with("resize");
         let collapsing_id = area_id.with("collapsing");
 
@@ -284,6 +290,7 @@ impl<'open> Window<'open> {
         let hover_interaction = resize_hover(ctx, possible, area_layer_id, last_frame_outer_rect);
 
         let mut area_content_ui = area.content_ui(ctx);
+        area_content_ui.set_access_parent(area_id);
 
         {
             // BEGIN FRAME --------------------------------
@@ -370,6 +377,18 @@ impl<'open> Window<'open> {
         }
         let full_response = area.end(ctx, area_content_ui);
 
+        ctx.register_access_node(None, || {
+            let node = AccessNode {
+                id: area_id, // the parent of the contents
+                ..AccessNode::new(AccessRole::Window, &full_response, title).enabled(true)
+            };
+            if with_title_bar && collapsible {
+                node.expanded(collapsing_header::State::is_open(ctx, collapsing_id).unwrap_or(true))
+            } else {
+                node
+            }
+        });
+
         Some(full_response)
     }
 }
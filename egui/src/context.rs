@@ -42,6 +42,13 @@ pub(crate) struct FrameState {
     /// Cleared by the first `ScrollArea` that makes use of it.
     pub(crate) scroll_delta: Vec2,
     pub(crate) scroll_target: Option<(f32, Align)>,
+
+    /// Set when the button of a [`menu::sub_menu`] was clicked this frame,
+    /// so that the menu it is in stays open.
+    pub(crate) sub_menu_clicked: bool,
+
+    /// Widgets described for screen readers this frame, with the id of their parent (if any).
+    pub(crate) access_nodes: Vec<(Option<Id>, accessibility::AccessNode)>,
 }
 
 impl Default for FrameState {
@@ -54,6 +61,8 @@ impl Default for FrameState {
             tooltip_rect: None,
             scroll_delta: Vec2::zero(),
             scroll_target: None,
+            sub_menu_clicked: false,
+            access_nodes: vec![],
         }
     }
 }
@@ -68,6 +77,8 @@ impl FrameState {
             tooltip_rect,
             scroll_delta,
             scroll_target,
+            sub_menu_clicked,
+            access_nodes,
         } = self;
 
         used_ids.clear();
@@ -76,7 +87,17 @@ impl FrameState {
         *used_by_panels = Rect::nothing();
         *tooltip_rect = None;
         *scroll_delta = input.scroll_delta;
+        if input.modifiers.shift {
+            // Shift + mouse wheel scrolls horizontally:
+            *scroll_delta = vec2(scroll_delta.x + scroll_delta.y, 0.0);
+        }
+        if let Some(multi_touch) = input.multi_touch() {
+            // Pan with two fingers to scroll:
+            *scroll_delta += multi_touch.translation_delta;
+        }
         *scroll_target = None;
+        *sub_menu_clicked = false;
+        access_nodes.clear();
     }
 
     /// How much space is still available after panels has been added.
@@ -173,6 +194,19 @@ impl CtxRef {
         *self = Self(Arc::new(self_));
     }
 
+    /// Run a whole frame with the given input, for use in tests.
+    #[cfg(test)]
+    pub(crate) fn run_test_frame<R>(
+        &mut self,
+        new_input: RawInput,
+        run_ui: impl FnOnce(&CtxRef) -> R,
+    ) -> R {
+        self.begin_frame(new_input);
+        let result = run_ui(self);
+        let _ = self.end_frame();
+        result
+    }
+
     // ---------------------------------------------------------------------
 
     /// If the given [`Id`] is not unique, an error will be printed at the given position.
@@ -255,6 +289,8 @@ impl CtxRef {
                 hovered,
                 clicked: false,
                 double_clicked: false,
+                secondary_clicked: false,
+                middle_clicked: false,
                 active: false,
                 has_kb_focus,
                 lost_kb_focus,
@@ -271,7 +307,7 @@ impl CtxRef {
         let active =
             memory.interaction.click_id == Some(id) || memory.interaction.drag_id == Some(id);
 
-        if self.input.mouse.pressed {
+        let mut response = if self.input.mouse.pressed {
             if hovered {
                 let mut response = Response {
                     ctx: self.clone(),
@@ -282,6 +318,8 @@ impl CtxRef {
                     hovered: true,
                     clicked: false,
                     double_clicked: false,
+                    secondary_clicked: false,
+                    middle_clicked: false,
                     active: false,
                     has_kb_focus,
                     lost_kb_focus,
@@ -315,6 +353,8 @@ impl CtxRef {
                     hovered,
                     clicked: false,
                     double_clicked: false,
+                    secondary_clicked: false,
+                    middle_clicked: false,
                     active: false,
                     has_kb_focus,
                     lost_kb_focus,
@@ -331,6 +371,8 @@ impl CtxRef {
                 hovered,
                 clicked,
                 double_clicked: clicked && self.input.mouse.double_click,
+                secondary_clicked: false,
+                middle_clicked: false,
                 active,
                 has_kb_focus,
                 lost_kb_focus,
@@ -345,6 +387,8 @@ impl CtxRef {
                 hovered: hovered && active,
                 clicked: false,
                 double_clicked: false,
+                secondary_clicked: false,
+                middle_clicked: false,
                 active,
                 has_kb_focus,
                 lost_kb_focus,
@@ -359,11 +403,21 @@ impl CtxRef {
                 hovered,
                 clicked: false,
                 double_clicked: false,
+                secondary_clicked: false,
+                middle_clicked: false,
                 active,
                 has_kb_focus,
                 lost_kb_focus,
             }
-        }
+        };
+
+        // Only the primary button starts clicks and drags, so no need to be `active`:
+        let secondary = self.input.mouse.button(MouseButton::Secondary);
+        response.secondary_clicked = hovered && sense.click && secondary.click;
+        let middle = self.input.mouse.button(MouseButton::Middle);
+        response.middle_clicked = hovered && sense.click && middle.click;
+
+        response
     }
 
     pub fn debug_painter(&self) -> Painter {
@@ -490,6 +544,29 @@ impl Context {
         self.memory().options.style = style.into();
     }
 
+    /// Collect an [`accessibility::AccessTree`] into [`Output::access_tree`] each frame.
+    /// Off by default.
+    pub fn set_access_tree_enabled(&self, enabled: bool) {
+        self.memory().options.access_tree = enabled;
+    }
+
+    pub fn access_tree_enabled(&self) -> bool {
+        self.memory().options.access_tree
+    }
+
+    /// Describe a widget for screen readers, as a child of `parent`.
+    /// Does nothing (and doesn't call `node`) unless [`Self::access_tree_enabled`].
+    pub fn register_access_node(
+        &self,
+        parent: Option<Id>,
+        node: impl FnOnce() -> accessibility::AccessNode,
+    ) {
+        if self.access_tree_enabled() {
+            let node = node();
+            self.frame_state().access_nodes.push((parent, node));
+        }
+    }
+
     /// The number of physical pixels for each logical point.
     pub fn pixels_per_point(&self) -> f32 {
         self.input.pixels_per_point()
@@ -577,6 +654,7 @@ impl Context {
                 font_definitions,
             )));
         }
+        self.fonts().begin_frame();
 
         // Ensure we register the background area so panels and background ui can catch clicks:
         let screen_rect = self.input.screen_rect();
@@ -603,6 +681,10 @@ impl Context {
         self.memory().end_frame(&self.frame_state().used_ids);
 
         let mut output: Output = std::mem::take(&mut self.output());
+        let access_nodes = std::mem::take(&mut self.frame_state().access_nodes);
+        if self.access_tree_enabled() {
+            output.access_tree = Some(accessibility::AccessTree::from_nodes(access_nodes));
+        }
         if self.repaint_requests.load(SeqCst) > 0 {
             self.repaint_requests.fetch_sub(1, SeqCst);
             output.needs_repaint = true;
@@ -623,7 +705,9 @@ impl Context {
         tessellation_options.aa_size = 1.0 / self.pixels_per_point();
         let paint_stats = PaintStats::from_shapes(&shapes); // TODO: internal allocations
         let paint_jobs = tessellator::tessellate_shapes(shapes, tessellation_options, self.fonts());
-        *self.paint_stats.lock() = paint_stats.with_paint_jobs(&paint_jobs);
+        *self.paint_stats.lock() = paint_stats
+            .with_paint_jobs(&paint_jobs)
+            .with_font_atlas(self.fonts().atlas_stats());
         paint_jobs
     }
 
@@ -871,3 +955,39 @@ impl Context {
         self.set_style(style);
     }
 }
+
+#[test]
+fn test_secondary_click() {
+    let button = |ctx: &CtxRef| {
+        let mut response = None;
+        CentralPanel::default().show(ctx, |ui| response = Some(ui.button("Click me")));
+        response.unwrap()
+    };
+
+    let mut ctx = CtxRef::default();
+    let pos = ctx
+        .run_test_frame(RawInput::default(), button)
+        .rect
+        .center();
+    let click = |ctx: &mut CtxRef, mouse_button| {
+        let input = |down| {
+            RawInput::default()
+                .with_mouse_pos(pos)
+                .with_button(mouse_button, down)
+        };
+        ctx.run_test_frame(input(true), button);
+        ctx.run_test_frame(input(false), button)
+    };
+
+    let response = click(&mut ctx, MouseButton::Secondary);
+    assert!(response.secondary_clicked);
+    assert!(!response.clicked && !response.middle_clicked);
+
+    let response = click(&mut ctx, MouseButton::Middle);
+    assert!(response.middle_clicked);
+    assert!(!response.clicked && !response.secondary_clicked);
+
+    let response = click(&mut ctx, MouseButton::Primary);
+    assert!(response.clicked);
+    assert!(!response.secondary_clicked && !response.middle_clicked);
+}
@@ -1,8 +1,15 @@
 //! The input needed by Egui.
 
-use std::collections::HashSet;
+use std::{
+    collections::HashSet,
+    sync::atomic::{AtomicBool, Ordering::Relaxed},
+};
 
-use crate::{math::*, util::History};
+use crate::{
+    math::*,
+    touch_state::{MultiTouchInfo, TouchState},
+    util::History,
+};
 
 /// If mouse moves more than this, it is no longer a click (but maybe a drag)
 const MAX_CLICK_DIST: f32 = 6.0;
@@ -15,18 +22,26 @@ const MAX_CLICK_DELAY: f64 = 0.3;
 ///
 /// All coordinates are in points (logical pixels) with origin (0, 0) in the top left corner.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "persistence", serde(default))]
 pub struct RawInput {
-    /// Is the button currently down?
-    /// NOTE: Egui currently only supports the primary mouse button.
-    pub mouse_down: bool,
+    /// Which mouse buttons are currently down, indexed by [`MouseButton`].
+    ///
+    /// ```
+    /// # let mut raw_input = egui::RawInput::default();
+    /// raw_input.mouse_down[egui::MouseButton::Secondary as usize] = true;
+    /// ```
+    pub mouse_down: [bool; NUM_MOUSE_BUTTONS],
 
     /// Current position of the mouse in points.
     pub mouse_pos: Option<Pos2>,
 
-    /// How many points (logical pixels) the user scrolled
+    /// How many points (logical pixels) the user scrolled.
+    /// Positive `y` means scrolling up (showing what is above), and positive `x` scrolling left.
     pub scroll_delta: Vec2,
 
     #[deprecated = "Use screen_rect instead: `Some(Rect::from_pos_size(Default::default(), vec2(window_width, window_height)))`"]
+    #[cfg_attr(feature = "persistence", serde(skip))]
     pub screen_size: Vec2,
 
     /// Position and size of the area that Egui should use.
@@ -57,13 +72,20 @@ pub struct RawInput {
 
     /// In-order events received this frame
     pub events: Vec<Event>,
+
+    /// Files being dragged over the window, but not yet dropped.
+    /// Set by the integration until the files are dropped or dragged away.
+    pub hovered_files: Vec<HoveredFile>,
+
+    /// Files dropped on the window this frame.
+    pub dropped_files: Vec<DroppedFile>,
 }
 
 impl Default for RawInput {
     fn default() -> Self {
         #![allow(deprecated)] // for screen_size
         Self {
-            mouse_down: false,
+            mouse_down: [false; NUM_MOUSE_BUTTONS],
             mouse_pos: None,
             scroll_delta: Vec2::zero(),
             screen_size: Default::default(),
@@ -73,6 +95,8 @@ impl Default for RawInput {
             predicted_dt: 1.0 / 60.0,
             modifiers: Modifiers::default(),
             events: vec![],
+            hovered_files: vec![],
+            dropped_files: vec![],
         }
     }
 }
@@ -92,10 +116,46 @@ impl RawInput {
             predicted_dt: self.predicted_dt,
             modifiers: self.modifiers,
             events: std::mem::take(&mut self.events),
+            hovered_files: self.hovered_files.clone(),
+            dropped_files: std::mem::take(&mut self.dropped_files),
         }
     }
 }
 
+/// A file being dragged over the window. See [`RawInput::hovered_files`].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "persistence", serde(default))]
+pub struct HoveredFile {
+    /// Where the file is. Only known on native (`egui_glium`).
+    pub path: Option<std::path::PathBuf>,
+
+    /// A guess of the MIME type of the file, e.g. `"image/png"`.
+    /// Empty if unknown.
+    pub mime: String,
+}
+
+/// A file dropped on the window. See [`RawInput::dropped_files`].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "persistence", serde(default))]
+pub struct DroppedFile {
+    /// Where the file is. Only known on native (`egui_glium`).
+    pub path: Option<std::path::PathBuf>,
+
+    /// The name of the file, e.g. `"image.png"`.
+    pub name: String,
+
+    /// A guess of the MIME type of the file, e.g. `"image/png"`.
+    /// Empty if unknown.
+    pub mime: String,
+
+    /// The contents of the file.
+    /// Set on the web (`egui_web`), where there is no `path` to read the file from.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    pub bytes: Option<std::sync::Arc<[u8]>>,
+}
+
 /// Input state that Egui updates each frame.
 #[derive(Clone, Debug)]
 pub struct InputState {
@@ -104,6 +164,9 @@ pub struct InputState {
 
     pub mouse: MouseInput,
 
+    /// The fingers on the touch screen (if any).
+    touch: TouchState,
+
     /// How many pixels the user scrolled
     pub scroll_delta: Vec2,
 
@@ -130,8 +193,39 @@ pub struct InputState {
     // The keys that are currently being held down.
     pub keys_down: HashSet<Key>,
 
-    /// In-order events received this frame
-    pub events: Vec<Event>,
+    /// In-order events received this frame. Read them with [`Self::events`].
+    events: Vec<Event>,
+
+    /// Which of the `events` have been removed by [`Self::consume_shortcut`].
+    consumed: ConsumedEvents,
+
+    /// Files being dragged over the window, but not yet dropped.
+    pub hovered_files: Vec<HoveredFile>,
+
+    /// Files dropped on the window this frame.
+    pub dropped_files: Vec<DroppedFile>,
+}
+
+/// One flag per event, set when it is consumed.
+/// Atomic so that events can be consumed through a shared `&InputState`.
+#[derive(Debug, Default)]
+struct ConsumedEvents(Vec<AtomicBool>);
+
+impl ConsumedEvents {
+    fn new(num_events: usize) -> Self {
+        Self((0..num_events).map(|_| AtomicBool::new(false)).collect())
+    }
+}
+
+impl Clone for ConsumedEvents {
+    fn clone(&self) -> Self {
+        Self(
+            self.0
+                .iter()
+                .map(|consumed| AtomicBool::new(consumed.load(Relaxed)))
+                .collect(),
+        )
+    }
 }
 
 impl Default for InputState {
@@ -139,6 +233,7 @@ impl Default for InputState {
         Self {
             raw: Default::default(),
             mouse: Default::default(),
+            touch: Default::default(),
             scroll_delta: Default::default(),
             screen_rect: Rect::from_min_size(Default::default(), vec2(10_000.0, 10_000.0)),
             pixels_per_point: 1.0,
@@ -148,22 +243,105 @@ impl Default for InputState {
             modifiers: Default::default(),
             keys_down: Default::default(),
             events: Default::default(),
+            consumed: Default::default(),
+            hovered_files: Default::default(),
+            dropped_files: Default::default(),
+        }
+    }
+}
+
+/// Mouse buttons that Egui knows about.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+pub enum MouseButton {
+    /// The primary mouse button is usually the left one.
+    Primary = 0,
+    /// The secondary mouse button is usually the right one,
+    /// and most often used for context menus or other optional things.
+    Secondary = 1,
+    /// The middle mouse button (often the scroll wheel).
+    Middle = 2,
+    /// The first extra mouse button, usually "back".
+    Extra1 = 3,
+    /// The second extra mouse button, usually "forward".
+    Extra2 = 4,
+}
+
+/// Number of variants of [`MouseButton`].
+pub const NUM_MOUSE_BUTTONS: usize = 5;
+
+impl MouseButton {
+    pub const ALL: [MouseButton; NUM_MOUSE_BUTTONS] = [
+        MouseButton::Primary,
+        MouseButton::Secondary,
+        MouseButton::Middle,
+        MouseButton::Extra1,
+        MouseButton::Extra2,
+    ];
+}
+
+/// The state of one mouse button. See [`MouseInput::button`].
+#[derive(Clone, Copy, Debug)]
+pub struct ButtonInput {
+    /// Is the button currently down?
+    /// true the frame when it is pressed,
+    /// false the frame it is released.
+    pub down: bool,
+
+    /// The button went from !down to down
+    pub pressed: bool,
+
+    /// The button went from down to !down
+    pub released: bool,
+
+    /// If the button is down, will it register as a click when released?
+    /// Set to true on press, set to false when mouse moves too much.
+    pub could_be_click: bool,
+
+    /// Was the button released this frame closely after going down?
+    pub click: bool,
+
+    /// Was there a double-click?
+    pub double_click: bool,
+
+    /// When did this button last click?
+    /// Used to check for double-clicks.
+    pub last_click_time: f64,
+
+    /// Where was the mouse when this button was pressed?
+    pub press_origin: Option<Pos2>,
+}
+
+impl Default for ButtonInput {
+    fn default() -> Self {
+        Self {
+            down: false,
+            pressed: false,
+            released: false,
+            could_be_click: false,
+            click: false,
+            double_click: false,
+            last_click_time: f64::NEG_INFINITY,
+            press_origin: None,
         }
     }
 }
 
 /// Mouse (or touch) state.
+///
+/// The button fields (`down`, `pressed`, `click`, …) are for the primary mouse button.
+/// Use [`Self::button`] for the other buttons.
 #[derive(Clone, Debug)]
 pub struct MouseInput {
-    /// Is the button currently down?
+    /// Is the primary button currently down?
     /// true the frame when it is pressed,
     /// false the frame it is released.
     pub down: bool,
 
-    /// The mouse went from !down to down
+    /// The primary button went from !down to down
     pub pressed: bool,
 
-    /// The mouse went from down to !down
+    /// The primary button went from down to !down
     pub released: bool,
 
     /// If the mouse is down, will it register as a click when released?
@@ -197,6 +375,9 @@ pub struct MouseInput {
     /// Recent movement of the mouse.
     /// Used for calculating velocity of mouse pointer.
     pos_history: History<Pos2>,
+
+    /// The state of each button, indexed by [`MouseButton`].
+    buttons: [ButtonInput; NUM_MOUSE_BUTTONS],
 }
 
 impl Default for MouseInput {
@@ -214,6 +395,7 @@ impl Default for MouseInput {
             delta: Vec2::zero(),
             velocity: Vec2::zero(),
             pos_history: History::new(1000, 0.1),
+            buttons: Default::default(),
         }
     }
 }
@@ -221,25 +403,77 @@ impl Default for MouseInput {
 /// An input event generated by the integration.
 ///
 /// This only covers events that Egui cares about.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
 pub enum Event {
     /// The integration detected a "copy" event (e.g. Cmd+C).
     Copy,
     /// The integration detected a "cut" event (e.g. Cmd+X).
     Cut,
-    /// Text input, e.g. via keyboard or paste action.
+    /// Text input, e.g. via keyboard.
     ///
     /// When the user presses enter/return, do not send a `Text` (just [`Key::Enter`]).
     Text(String),
+    /// The integration detected a "paste" event (e.g. Cmd+V), or answers [`crate::Output::request_paste`].
+    /// Contains the text on the clipboard.
+    Paste(String),
     Key {
         key: Key,
         pressed: bool,
         modifiers: Modifiers,
     },
+    /// An IME (input method editor) composition started,
+    /// e.g. because the user started typing Chinese, Japanese or Korean characters.
+    CompositionStart,
+    /// The text being composed changed. It should be shown to the user,
+    /// but is not yet part of the text.
+    CompositionUpdate(String),
+    /// The composition ended, and the given text should be inserted.
+    CompositionEnd(String),
+    /// A finger touched, moved on, or was lifted from a touch screen.
+    ///
+    /// Integrations should also keep emulating the mouse with the first finger,
+    /// so that single-finger taps and drags work as clicks and drags.
+    Touch {
+        /// Identifies the touch device, in case there are several. Can be left at zero.
+        device_id: TouchDeviceId,
+        /// Identifies the finger. Must be unique while the finger is down.
+        id: TouchId,
+        phase: TouchPhase,
+        /// Position of the finger in points.
+        pos: Pos2,
+        /// Pressure of the finger in the range `0.0..=1.0`, or `0.0` if unknown.
+        force: f32,
+    },
+}
+
+/// Identifies a touch device (e.g. a touch screen), see [`Event::Touch`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+pub struct TouchDeviceId(pub u64);
+
+/// Identifies a finger on a touch device, see [`Event::Touch`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+pub struct TouchId(pub u64);
+
+/// What happened to a finger, see [`Event::Touch`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+pub enum TouchPhase {
+    /// The finger touched the screen.
+    Start,
+    /// The finger moved.
+    Move,
+    /// The finger was lifted from the screen.
+    End,
+    /// The touch was aborted by the system (e.g. because a system gesture took over).
+    Cancel,
 }
 
 /// State of the modifier keys. These must be fed to Egui.
-#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
 pub struct Modifiers {
     /// Either of the alt keys are down (option ⌥ on Mac).
     pub alt: bool,
@@ -257,14 +491,93 @@ pub struct Modifiers {
     pub command: bool,
 }
 
+impl Modifiers {
+    pub const NONE: Self = Self {
+        alt: false,
+        ctrl: false,
+        shift: false,
+        mac_cmd: false,
+        command: false,
+    };
+
+    pub const ALT: Self = Self {
+        alt: true,
+        ..Self::NONE
+    };
+    pub const CTRL: Self = Self {
+        ctrl: true,
+        ..Self::NONE
+    };
+    pub const SHIFT: Self = Self {
+        shift: true,
+        ..Self::NONE
+    };
+    /// The ⌘ Command key on Mac, and Ctrl on other platforms.
+    pub const COMMAND: Self = Self {
+        command: true,
+        ..Self::NONE
+    };
+
+    /// Are these the modifiers of `pattern`?
+    ///
+    /// If `pattern` has [`Self::command`], then Ctrl on Windows/Linux and ⌘ on Mac will match.
+    /// All other modifiers must match exactly.
+    ///
+    /// ```
+    /// # use egui::Modifiers;
+    /// let ctrl_on_windows = Modifiers { ctrl: true, command: true, ..Default::default() };
+    /// let cmd_on_mac = Modifiers { mac_cmd: true, command: true, ..Default::default() };
+    /// assert!(ctrl_on_windows.matches(Modifiers::COMMAND));
+    /// assert!(cmd_on_mac.matches(Modifiers::COMMAND));
+    /// assert!(!cmd_on_mac.matches(Modifiers::COMMAND | Modifiers::SHIFT));
+    /// ```
+    pub fn matches(&self, pattern: Modifiers) -> bool {
+        if self.alt != pattern.alt || self.shift != pattern.shift {
+            return false;
+        }
+        if pattern.mac_cmd {
+            return self.mac_cmd && self.ctrl == pattern.ctrl;
+        }
+        if !pattern.ctrl && !pattern.command {
+            return !self.ctrl && !self.command;
+        }
+        // On Windows and Linux, Ctrl sets both `ctrl` and `command`:
+        (!pattern.ctrl || self.ctrl) && (!pattern.command || self.command)
+    }
+}
+
+impl std::ops::BitOr for Modifiers {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self {
+            alt: self.alt | rhs.alt,
+            ctrl: self.ctrl | rhs.ctrl,
+            shift: self.shift | rhs.shift,
+            mac_cmd: self.mac_cmd | rhs.mac_cmd,
+            command: self.command | rhs.command,
+        }
+    }
+}
+
 /// Keyboard keys.
 ///
 /// Includes all keys Egui is interested in (such as `Home` and `End`)
 /// plus a few that are useful for detecting keyboard shortcuts.
 ///
-/// Many keys are omitted because they are not always physical keys (depending on keyboard language), e.g. `;` and `§`,
-/// and are therefor unsuitable as keyboard shortcuts if you want your app to be portable.
+/// These are *logical* keys: a key is named after what it types, not where it is on the keyboard.
+/// The numpad keys are reported as the same keys as their counterparts on the main keyboard
+/// (e.g. numpad `+` is [`Key::Plus`]), so you can't tell them apart.
+///
+/// The punctuation keys depend on the keyboard layout, e.g. on a US keyboard `+` is typed with Shift and `=`.
+/// How that is reported depends on the integration: `egui_glium` reports the unshifted key
+/// ([`Key::Equals`] with `shift` set), while `egui_web` reports what was typed ([`Key::Plus`] with `shift` set).
+/// So for e.g. zooming in, check for both [`Key::Plus`] and [`Key::Equals`].
+///
+/// Some punctuation keys are missing on some layouts (e.g. `;` and `[` on a French keyboard),
+/// so they are not good choices for keyboard shortcuts that must work everywhere.
 #[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd, Hash)]
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
 pub enum Key {
     ArrowDown,
     ArrowLeft,
@@ -331,6 +644,228 @@ pub enum Key {
     X,
     Y,
     Z, // Used for cmd+Z (undo)
+
+    /// `-`, from the main row or from the numpad.
+    Minus,
+    /// `+`, from the numpad or from a key of its own (e.g. on German keyboards). See also [`Key`].
+    Plus,
+    /// `=`, from the main row or from the numpad.
+    Equals,
+    /// `*`, from the numpad or from a key of its own.
+    Asterisk,
+    /// `/`, from the main keyboard or from the numpad.
+    Slash,
+    /// `\`
+    Backslash,
+    /// `.`, from the main keyboard or the numpad decimal point.
+    Period,
+    /// `,`, from the main keyboard or the numpad (on some keyboards).
+    Comma,
+    /// `;`
+    Semicolon,
+    /// `:`
+    Colon,
+    /// `'`
+    Quote,
+    /// `` ` ``
+    Backtick,
+    /// `[`
+    OpenBracket,
+    /// `]`
+    CloseBracket,
+
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    F13,
+    F14,
+    F15,
+    F16,
+    F17,
+    F18,
+    F19,
+    F20,
+}
+
+impl Key {
+    /// Human-readable name, e.g. `"A"`, `"Enter"` or `"⬆"`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Key::ArrowDown => "⬇",
+            Key::ArrowLeft => "⬅",
+            Key::ArrowRight => "➡",
+            Key::ArrowUp => "⬆",
+            Key::Escape => "Escape",
+            Key::Tab => "Tab",
+            Key::Backspace => "Backspace",
+            Key::Enter => "Enter",
+            Key::Space => "Space",
+            Key::Insert => "Insert",
+            Key::Delete => "Delete",
+            Key::Home => "Home",
+            Key::End => "End",
+            Key::PageUp => "PageUp",
+            Key::PageDown => "PageDown",
+            Key::Num0 => "0",
+            Key::Num1 => "1",
+            Key::Num2 => "2",
+            Key::Num3 => "3",
+            Key::Num4 => "4",
+            Key::Num5 => "5",
+            Key::Num6 => "6",
+            Key::Num7 => "7",
+            Key::Num8 => "8",
+            Key::Num9 => "9",
+            Key::A => "A",
+            Key::B => "B",
+            Key::C => "C",
+            Key::D => "D",
+            Key::E => "E",
+            Key::F => "F",
+            Key::G => "G",
+            Key::H => "H",
+            Key::I => "I",
+            Key::J => "J",
+            Key::K => "K",
+            Key::L => "L",
+            Key::M => "M",
+            Key::N => "N",
+            Key::O => "O",
+            Key::P => "P",
+            Key::Q => "Q",
+            Key::R => "R",
+            Key::S => "S",
+            Key::T => "T",
+            Key::U => "U",
+            Key::V => "V",
+            Key::W => "W",
+            Key::X => "X",
+            Key::Y => "Y",
+            Key::Z => "Z",
+            Key::Minus => "-",
+            Key::Plus => "+",
+            Key::Equals => "=",
+            Key::Asterisk => "*",
+            Key::Slash => "/",
+            Key::Backslash => "\\",
+            Key::Period => ".",
+            Key::Comma => ",",
+            Key::Semicolon => ";",
+            Key::Colon => ":",
+            Key::Quote => "'",
+            Key::Backtick => "`",
+            Key::OpenBracket => "[",
+            Key::CloseBracket => "]",
+            Key::F1 => "F1",
+            Key::F2 => "F2",
+            Key::F3 => "F3",
+            Key::F4 => "F4",
+            Key::F5 => "F5",
+            Key::F6 => "F6",
+            Key::F7 => "F7",
+            Key::F8 => "F8",
+            Key::F9 => "F9",
+            Key::F10 => "F10",
+            Key::F11 => "F11",
+            Key::F12 => "F12",
+            Key::F13 => "F13",
+            Key::F14 => "F14",
+            Key::F15 => "F15",
+            Key::F16 => "F16",
+            Key::F17 => "F17",
+            Key::F18 => "F18",
+            Key::F19 => "F19",
+            Key::F20 => "F20",
+        }
+    }
+}
+
+/// A keyboard shortcut: a [`Key`] together with some [`Modifiers`].
+///
+/// ```
+/// # use egui::{Key, KeyboardShortcut, Modifiers};
+/// const SAVE: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::S);
+/// assert_eq!(SAVE.format(true), "⌘S");
+/// assert_eq!(SAVE.format(false), "Ctrl+S");
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+pub struct KeyboardShortcut {
+    pub modifiers: Modifiers,
+    pub key: Key,
+}
+
+impl KeyboardShortcut {
+    pub const fn new(modifiers: Modifiers, key: Key) -> Self {
+        Self { modifiers, key }
+    }
+
+    /// E.g. `"⇧⌘S"` on Mac, and `"Ctrl+Shift+S"` on other platforms.
+    pub fn format(&self, is_mac: bool) -> String {
+        let Modifiers {
+            alt,
+            ctrl,
+            shift,
+            mac_cmd,
+            command,
+        } = self.modifiers;
+        if is_mac {
+            let mut s = String::new();
+            for (down, symbol) in [
+                (ctrl, "⌃"),
+                (alt, "⌥"),
+                (shift, "⇧"),
+                (mac_cmd || command, "⌘"),
+            ] {
+                if down {
+                    s += symbol;
+                }
+            }
+            s + self.key.name()
+        } else {
+            let mut s = String::new();
+            for (down, name) in [
+                (ctrl || command, "Ctrl"),
+                (alt, "Alt"),
+                (shift, "Shift"),
+                (mac_cmd, "Cmd"),
+            ] {
+                if down {
+                    s += name;
+                    s += "+";
+                }
+            }
+            s + self.key.name()
+        }
+    }
+
+    /// Is this event a press of this shortcut?
+    fn is_pressed_by(&self, event: &Event) -> bool {
+        matches!(
+            event,
+            Event::Key {
+                key,
+                pressed: true,
+                modifiers,
+            } if *key == self.key && modifiers.matches(self.modifiers)
+        )
+    }
+}
+
+/// Formatted for the platform Egui was compiled for (see [`KeyboardShortcut::format`]).
+impl std::fmt::Display for KeyboardShortcut {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.format(cfg!(target_os = "macos")))
+    }
 }
 
 impl InputState {
@@ -350,6 +885,8 @@ impl InputState {
             }
         });
         let mouse = self.mouse.begin_frame(time, &new);
+        let mut touch = self.touch;
+        touch.begin_frame(time, &new.events);
         let mut keys_down = self.keys_down;
         for event in &new.events {
             if let Event::Key { key, pressed, .. } = event {
@@ -362,6 +899,7 @@ impl InputState {
         }
         InputState {
             mouse,
+            touch,
             scroll_delta: new.scroll_delta,
             screen_rect,
             pixels_per_point: new.pixels_per_point.unwrap_or(self.pixels_per_point),
@@ -371,6 +909,9 @@ impl InputState {
             modifiers: new.modifiers,
             keys_down,
             events: new.events.clone(), // TODO: remove clone() and use raw.events
+            consumed: ConsumedEvents::new(new.events.len()),
+            hovered_files: new.hovered_files.clone(),
+            dropped_files: new.dropped_files.clone(),
             raw: new,
         }
     }
@@ -380,16 +921,49 @@ impl InputState {
     }
 
     pub fn wants_repaint(&self) -> bool {
-        self.mouse.pressed
-            || self.mouse.released
+        self.mouse.any_pressed()
+            || self.mouse.any_released()
             || self.mouse.delta != Vec2::zero()
             || self.scroll_delta != Vec2::zero()
             || !self.events.is_empty()
     }
 
+    /// In-order events received this frame,
+    /// except for the ones removed by [`Self::consume_shortcut`].
+    ///
+    /// All events are in [`Self::raw`].
+    pub fn events(&self) -> impl Iterator<Item = &Event> {
+        self.events
+            .iter()
+            .zip(&self.consumed.0)
+            .filter(|(_, consumed)| !consumed.load(Relaxed))
+            .map(|(event, _)| event)
+    }
+
+    /// Was the given shortcut pressed this frame? If so, the key press is removed from [`Self::events`],
+    /// so that nothing else (e.g. a [`crate::TextEdit`] or another `consume_shortcut`) will react to it.
+    ///
+    /// ```
+    /// # let ui = egui::Ui::__test();
+    /// use egui::{Key, KeyboardShortcut, Modifiers};
+    /// const UNDO: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::Z);
+    /// if ui.input().consume_shortcut(&UNDO) {
+    ///     // undo
+    /// }
+    /// ```
+    pub fn consume_shortcut(&self, shortcut: &KeyboardShortcut) -> bool {
+        let mut pressed = false;
+        for (event, consumed) in self.events.iter().zip(&self.consumed.0) {
+            if shortcut.is_pressed_by(event) && !consumed.swap(true, Relaxed) {
+                pressed = true;
+            }
+        }
+        pressed
+    }
+
     /// Was the given key pressed this frame?
     pub fn key_pressed(&self, desired_key: Key) -> bool {
-        self.events.iter().any(|event| {
+        self.events().any(|event| {
             matches!(
                 event,
                 Event::Key {
@@ -408,7 +982,7 @@ impl InputState {
 
     /// Was the given key released this frame?
     pub fn key_released(&self, desired_key: Key) -> bool {
-        self.events.iter().any(|event| {
+        self.events().any(|event| {
             matches!(
                 event,
                 Event::Key {
@@ -420,6 +994,32 @@ impl InputState {
         })
     }
 
+    /// Information about the ongoing multi-touch gesture (two or more fingers), if any.
+    ///
+    /// ```
+    /// # let ui = egui::Ui::__test();
+    /// # let mut zoom = 1.0;
+    /// if let Some(multi_touch) = ui.input().multi_touch() {
+    ///     zoom *= multi_touch.zoom_delta;
+    /// }
+    /// # let _ = zoom;
+    /// ```
+    pub fn multi_touch(&self) -> Option<MultiTouchInfo> {
+        self.touch.info()
+    }
+
+    /// How much the user zoomed this frame with a pinch gesture.
+    /// `1.0` means no zoom, `> 1.0` means zooming in (fingers moving apart).
+    pub fn zoom_delta(&self) -> f32 {
+        self.multi_touch()
+            .map_or(1.0, |multi_touch| multi_touch.zoom_delta)
+    }
+
+    /// Is any finger touching the screen?
+    pub fn any_touches(&self) -> bool {
+        self.touch.any_touches()
+    }
+
     /// Also known as device pixel ratio, > 1 for HDPI screens.
     pub fn pixels_per_point(&self) -> f32 {
         self.pixels_per_point
@@ -438,16 +1038,18 @@ impl InputState {
     }
 }
 
-impl MouseInput {
+impl ButtonInput {
     #[must_use]
-    pub fn begin_frame(mut self, time: f64, new: &RawInput) -> MouseInput {
-        let delta = new
-            .mouse_pos
-            .and_then(|new| self.pos.map(|last| new - last))
-            .unwrap_or_default();
-        let pressed = !self.down && new.mouse_down;
-
-        let released = self.down && !new.mouse_down;
+    fn begin_frame(
+        self,
+        time: f64,
+        new_down: bool,
+        last_pos: Option<Pos2>,
+        new_pos: Option<Pos2>,
+    ) -> ButtonInput {
+        let pressed = !self.down && new_down;
+
+        let released = self.down && !new_down;
         let click = released && self.could_be_click;
         let double_click = click && (time - self.last_click_time) < MAX_CLICK_DELAY;
         let mut press_origin = self.press_origin;
@@ -458,19 +1060,45 @@ impl MouseInput {
         }
 
         if pressed {
-            press_origin = new.mouse_pos;
+            press_origin = new_pos;
             could_be_click = true;
-        } else if !self.down || self.pos.is_none() {
+        } else if !self.down || last_pos.is_none() {
             press_origin = None;
         }
 
-        if let (Some(press_origin), Some(mouse_pos)) = (new.mouse_pos, press_origin) {
+        if let (Some(press_origin), Some(mouse_pos)) = (new_pos, press_origin) {
             could_be_click &= press_origin.distance(mouse_pos) < MAX_CLICK_DIST;
         } else {
             could_be_click = false;
         }
 
-        if pressed {
+        ButtonInput {
+            down: new_down && new_pos.is_some(),
+            pressed,
+            released,
+            could_be_click,
+            click,
+            double_click,
+            last_click_time,
+            press_origin,
+        }
+    }
+}
+
+impl MouseInput {
+    #[must_use]
+    pub fn begin_frame(mut self, time: f64, new: &RawInput) -> MouseInput {
+        let delta = new
+            .mouse_pos
+            .and_then(|new| self.pos.map(|last| new - last))
+            .unwrap_or_default();
+
+        let mut buttons = self.buttons;
+        for (button, &new_down) in buttons.iter_mut().zip(new.mouse_down.iter()) {
+            *button = button.begin_frame(time, new_down, self.pos, new.mouse_pos);
+        }
+
+        if buttons.iter().any(|button| button.pressed) {
             // Start of a drag: we want to track the velocity for during the drag
             // and ignore any incoming movement
             self.pos_history.clear();
@@ -491,21 +1119,79 @@ impl MouseInput {
             Vec2::default()
         };
 
+        let primary = buttons[MouseButton::Primary as usize];
         MouseInput {
-            down: new.mouse_down && new.mouse_pos.is_some(),
-            pressed,
-            released,
-            could_be_click,
-            click,
-            double_click,
-            last_click_time,
+            down: primary.down,
+            pressed: primary.pressed,
+            released: primary.released,
+            could_be_click: primary.could_be_click,
+            click: primary.click,
+            double_click: primary.double_click,
+            last_click_time: primary.last_click_time,
             pos: new.mouse_pos,
-            press_origin,
+            press_origin: primary.press_origin,
             delta,
             velocity,
             pos_history: self.pos_history,
+            buttons,
         }
     }
+
+    /// The state of the given button.
+    ///
+    /// ```
+    /// # let ui = egui::Ui::__test();
+    /// if ui.input().mouse.button(egui::MouseButton::Middle).click {
+    ///     // …
+    /// }
+    /// ```
+    pub fn button(&self, button: MouseButton) -> &ButtonInput {
+        &self.buttons[button as usize]
+    }
+
+    /// Is any mouse button currently down?
+    pub fn any_down(&self) -> bool {
+        self.buttons.iter().any(|button| button.down)
+    }
+
+    /// Was any mouse button pressed this frame?
+    pub fn any_pressed(&self) -> bool {
+        self.buttons.iter().any(|button| button.pressed)
+    }
+
+    /// Was any mouse button released this frame?
+    pub fn any_released(&self) -> bool {
+        self.buttons.iter().any(|button| button.released)
+    }
+}
+
+/// Builders for scripting the input of tests.
+#[cfg(test)]
+impl RawInput {
+    pub(crate) fn with_mouse_pos(mut self, mouse_pos: Pos2) -> Self {
+        self.mouse_pos = Some(mouse_pos);
+        self
+    }
+
+    pub(crate) fn with_button(mut self, button: MouseButton, down: bool) -> Self {
+        self.mouse_down[button as usize] = down;
+        self
+    }
+
+    pub(crate) fn with_events(mut self, events: Vec<Event>) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Press `key` with `modifiers`.
+    pub(crate) fn with_key(mut self, key: Key, modifiers: Modifiers) -> Self {
+        self.events.push(Event::Key {
+            key,
+            pressed: true,
+            modifiers,
+        });
+        self
+    }
 }
 
 impl RawInput {
@@ -522,11 +1208,13 @@ impl RawInput {
             predicted_dt,
             modifiers,
             events,
+            hovered_files,
+            dropped_files,
         } = self;
 
         // TODO: simpler way to show values, e.g. `ui.value("Mouse Pos:", self.mouse_pos);
         // TODO: `ui.style_mut().text_style = TextStyle::Monospace`;
-        ui.label(format!("mouse_down: {}", mouse_down));
+        ui.label(format!("mouse_down: {:?}", mouse_down));
         ui.label(format!("mouse_pos: {:.1?}", mouse_pos));
         ui.label(format!("scroll_delta: {:?} points", scroll_delta));
         ui.label(format!("screen_rect: {:?} points", screen_rect));
@@ -543,6 +1231,8 @@ impl RawInput {
         ui.label(format!("modifiers: {:#?}", modifiers));
         ui.label(format!("events: {:?}", events))
             .on_hover_text("key presses etc");
+        ui.label(format!("hovered_files: {}", hovered_files.len()));
+        ui.label(format!("dropped_files: {}", dropped_files.len()));
     }
 }
 
@@ -551,6 +1241,7 @@ impl InputState {
         let Self {
             raw,
             mouse,
+            touch,
             scroll_delta,
             screen_rect,
             pixels_per_point,
@@ -559,7 +1250,10 @@ impl InputState {
             predicted_dt,
             modifiers,
             keys_down,
-            events,
+            events: _,
+            consumed: _,
+            hovered_files,
+            dropped_files,
         } = self;
 
         ui.style_mut().body_text_style = crate::paint::TextStyle::Monospace;
@@ -571,6 +1265,9 @@ impl InputState {
                 mouse.ui(ui);
             });
 
+        ui.label(format!("any_touches: {}", touch.any_touches()));
+        ui.label(format!("multi_touch: {:#?}", touch.info()));
+
         ui.label(format!("scroll_delta: {:?} points", scroll_delta));
         ui.label(format!("screen_rect: {:?} points", screen_rect));
         ui.label(format!(
@@ -585,8 +1282,16 @@ impl InputState {
         ui.label(format!("predicted_dt: {:.1} ms", 1e3 * predicted_dt));
         ui.label(format!("modifiers: {:#?}", modifiers));
         ui.label(format!("keys_down: {:?}", keys_down));
-        ui.label(format!("events: {:?}", events))
+        ui.label(format!("events: {:?}", self.events().collect::<Vec<_>>()))
             .on_hover_text("key presses etc");
+        ui.label(format!("hovered_files: {:?}", hovered_files));
+        ui.label(format!(
+            "dropped_files: {:?}",
+            dropped_files
+                .iter()
+                .map(|file| &file.name)
+                .collect::<Vec<_>>()
+        ));
     }
 }
 
@@ -605,6 +1310,7 @@ impl MouseInput {
             delta,
             velocity,
             pos_history: _,
+            buttons,
         } = self;
 
         ui.label(format!("down: {}", down));
@@ -621,5 +1327,68 @@ impl MouseInput {
             "velocity: [{:3.0} {:3.0}] points/sec",
             velocity.x, velocity.y
         ));
+        for button in &MouseButton::ALL[1..] {
+            let ButtonInput { down, click, .. } = buttons[*button as usize];
+            ui.label(format!("{:?}: down: {}, click: {}", button, down, click));
+        }
     }
 }
+
+#[test]
+fn test_file_drag_and_drop() {
+    let mut raw = RawInput::default();
+    raw.hovered_files.push(HoveredFile {
+        mime: "image/png".to_owned(),
+        ..Default::default()
+    });
+    let input = InputState::default().begin_frame(raw.take());
+    assert_eq!(input.hovered_files.len(), 1);
+    assert!(input.dropped_files.is_empty());
+
+    raw.hovered_files.clear();
+    raw.dropped_files.push(DroppedFile {
+        name: "image.png".to_owned(),
+        mime: "image/png".to_owned(),
+        ..Default::default()
+    });
+    let input = input.begin_frame(raw.take());
+    assert!(input.hovered_files.is_empty());
+    assert_eq!(input.dropped_files[0].name, "image.png");
+
+    let input = input.begin_frame(raw.take());
+    assert!(
+        input.dropped_files.is_empty(),
+        "dropped files only last one frame"
+    );
+}
+
+#[test]
+fn test_consume_shortcut() {
+    let key = |key, modifiers| Event::Key {
+        key,
+        pressed: true,
+        modifiers,
+    };
+    let save = KeyboardShortcut::new(Modifiers::COMMAND, Key::S);
+    let raw = RawInput {
+        events: vec![
+            key(Key::S, Modifiers::COMMAND),
+            Event::Text("s".to_owned()),
+            key(Key::Z, Modifiers::COMMAND),
+        ],
+        ..Default::default()
+    };
+    let input = InputState::default().begin_frame(raw);
+
+    assert!(input.key_pressed(Key::S));
+    assert!(!input.consume_shortcut(&KeyboardShortcut::new(Modifiers::NONE, Key::S)));
+    assert!(input.consume_shortcut(&save));
+    assert!(!input.consume_shortcut(&save), "already consumed");
+    assert!(!input.key_pressed(Key::S));
+    assert!(input.key_pressed(Key::Z));
+    assert_eq!(
+        input.events().cloned().collect::<Vec<_>>(),
+        vec![Event::Text("s".to_owned()), key(Key::Z, Modifiers::COMMAND)]
+    );
+    assert_eq!(input.raw.events.len(), 3);
+}
@@ -93,6 +93,7 @@ impl Widget for &epaint::stats::PaintStats {
                 jobs,
                 vertices,
                 indices,
+                font_atlas,
             } = self;
 
             ui.label("Intermediate:");
@@ -109,6 +110,21 @@ impl Widget for &epaint::stats::PaintStats {
             label(ui, indices, "indices").on_hover_text("Three 32-bit indices per triangles");
             ui.advance_cursor(10.0);
 
+            ui.label("Font atlas:");
+            ui.label(format!(
+                "{:6} glyphs       {}x{} texture, {:.0}% used",
+                font_atlas.num_glyphs,
+                font_atlas.width,
+                font_atlas.height,
+                100.0 * font_atlas.used_height as f32 / font_atlas.height.max(1) as f32,
+            ));
+            ui.label(format!(
+                "{:6} evicted      in {} evictions",
+                font_atlas.num_evicted, font_atlas.num_evictions,
+            ))
+            .on_hover_text("Glyphs that are not painted for a while are evicted when the font texture gets too big");
+            ui.advance_cursor(10.0);
+
             // ui.label("Total:");
             // ui.label(self.total().format(""));
         })
@@ -78,6 +78,7 @@
 )]
 #![allow(clippy::manual_range_contains)]
 
+pub mod accessibility;
 mod animation_manager;
 pub mod containers;
 mod context;
@@ -92,6 +93,7 @@ pub mod menu;
 mod painter;
 pub(crate) mod placer;
 pub mod style;
+mod touch_state;
 mod types;
 mod ui;
 pub mod util;
@@ -106,7 +108,7 @@ pub use emath::{
 };
 pub use epaint::{
     color, mutex,
-    text::{FontDefinitions, FontFamily, TextStyle},
+    text::{FontDefinitions, FontFamily, LayoutJob, TextFormat, TextStyle},
     Color32, PaintJobs, Rgba, Shape, Stroke, Texture, TextureId,
 };
 
@@ -121,6 +123,7 @@ pub use {
     memory::Memory,
     painter::Painter,
     style::Style,
+    touch_state::MultiTouchInfo,
     types::*,
     ui::Ui,
     widgets::*,
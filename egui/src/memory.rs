@@ -1,11 +1,15 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+    any::Any,
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use crate::{
-    area, collapsing_header, menu,
+    area, collapsing_header, command_palette, menu,
     paint::color::{Color32, Hsva},
-    resize, scroll_area,
+    resize, scroll_area, table,
     util::Cache,
-    widgets::text_edit,
+    widgets::{plot, text_edit},
     window, Id, LayerId, Pos2, Rect, Style,
 };
 
@@ -28,11 +32,15 @@ pub struct Memory {
 
     // states of various types of widgets
     pub(crate) collapsing_headers: HashMap<Id, collapsing_header::State>,
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    pub(crate) command_palettes: HashMap<Id, command_palette::State>,
     pub(crate) grid: HashMap<Id, crate::grid::State>,
     #[cfg_attr(feature = "persistence", serde(skip))]
     pub(crate) menu_bar: HashMap<Id, menu::BarState>,
+    pub(crate) plots: HashMap<Id, plot::State>,
     pub(crate) resize: HashMap<Id, resize::State>,
     pub(crate) scroll_areas: HashMap<Id, scroll_area::State>,
+    pub(crate) tables: HashMap<Id, table::State>,
     pub(crate) text_edit: HashMap<Id, text_edit::State>,
 
     #[cfg_attr(feature = "persistence", serde(skip))]
@@ -54,6 +62,14 @@ pub struct Memory {
     #[cfg_attr(feature = "persistence", serde(skip))]
     popup: Option<Id>,
 
+    /// What is being dragged with [`crate::Ui::dnd_drag_source`] (if anything).
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    dnd_payload: Option<DndPayload>,
+
+    /// Where to show the open context menu (if any).
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    pub(crate) context_menu: Option<menu::ContextMenuState>,
+
     #[cfg_attr(feature = "persistence", serde(skip))]
     everything_is_visible: bool,
 }
@@ -70,6 +86,25 @@ pub(crate) struct Options {
     pub(crate) tessellation_options: crate::paint::TessellationOptions,
     /// Font sizes etc.
     pub(crate) font_definitions: crate::paint::text::FontDefinitions,
+    /// Collect an [`crate::accessibility::AccessTree`] each frame.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    pub(crate) access_tree: bool,
+}
+
+/// What is being dragged with [`crate::Ui::dnd_drag_source`].
+#[derive(Clone)]
+struct DndPayload {
+    /// The `Id` of the drag source.
+    source: Id,
+    payload: Arc<dyn Any + Send + Sync>,
+}
+
+impl std::fmt::Debug for DndPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DndPayload")
+            .field("source", &self.source)
+            .finish()
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -163,6 +198,17 @@ impl Interaction {
                 }
             }
         }
+
+        if self.kb_focus_id.is_none() {
+            // Tab into the first (or Shift+Tab into the last) widget interested in kb focus:
+            if self.pressed_tab {
+                self.kb_focus_give_to_next = true;
+                self.pressed_tab = false;
+            } else if self.pressed_shift_tab {
+                self.kb_focus_id = self.kb_focus_last_interested;
+                self.pressed_shift_tab = false;
+            }
+        }
     }
 }
 
@@ -177,6 +223,13 @@ impl Memory {
         if !prev_input.mouse.down {
             self.window_interaction = None;
         }
+
+        if let Some(dnd_payload) = &self.dnd_payload {
+            if !self.is_being_dragged(dnd_payload.source) {
+                // Dropped (or dropped by the source) last frame
+                self.dnd_payload = None;
+            }
+        }
     }
 
     pub(crate) fn end_frame(&mut self, used_ids: &epaint::ahash::AHashMap<Id, Pos2>) {
@@ -250,6 +303,20 @@ impl Memory {
         self.interaction.drag_id == Some(id)
     }
 
+    /// The payload of what is being dragged with [`crate::Ui::dnd_drag_source`],
+    /// if it is of type `T`.
+    pub fn dnd_payload<T: Any + Send + Sync>(&self) -> Option<Arc<T>> {
+        let dnd_payload = self.dnd_payload.as_ref()?;
+        dnd_payload.payload.clone().downcast::<T>().ok()
+    }
+
+    pub(crate) fn set_dnd_payload<T: Any + Send + Sync>(&mut self, source: Id, payload: T) {
+        self.dnd_payload = Some(DndPayload {
+            source,
+            payload: Arc::new(payload),
+        });
+    }
+
     /// Forget window positions, sizes etc.
     /// Can be used to auto-layout windows.
     pub fn reset_areas(&mut self) {
@@ -408,3 +475,50 @@ impl Areas {
         wants_to_be_on_top.clear();
     }
 }
+
+#[test]
+fn test_tab_cycles_kb_focus() {
+    use crate::{CentralPanel, CtxRef, Key, Modifiers, RawInput, TextEdit};
+
+    let mut text = String::new();
+    let mut widgets = |ctx: &CtxRef| {
+        let mut ids = vec![];
+        CentralPanel::default().show(ctx, |ui| {
+            ids.push(ui.button("Button").id);
+            ui.label("Not focusable");
+            ids.push(ui.checkbox(&mut false, "Checkbox").id);
+            ids.push(ui.add(TextEdit::singleline(&mut text)).id);
+        });
+        ids
+    };
+    let tab = RawInput::default().with_key(Key::Tab, Modifiers::default());
+    let shift_tab = RawInput::default().with_key(
+        Key::Tab,
+        Modifiers {
+            shift: true,
+            ..Default::default()
+        },
+    );
+
+    let mut ctx = CtxRef::default();
+    let ids = ctx.run_test_frame(RawInput::default(), &mut widgets);
+    let focus = |ctx: &CtxRef| ctx.memory().interaction.kb_focus_id;
+    assert_eq!(focus(&ctx), None);
+
+    for &id in &ids {
+        ctx.run_test_frame(tab.clone(), &mut widgets);
+        assert_eq!(focus(&ctx), Some(id));
+    }
+
+    // Tab from the last widget moves focus to the first one, which takes it the next frame:
+    ctx.run_test_frame(tab.clone(), &mut widgets);
+    assert_eq!(focus(&ctx), None);
+    ctx.run_test_frame(RawInput::default(), &mut widgets);
+    assert_eq!(focus(&ctx), Some(ids[0]));
+
+    // Shift+Tab goes backwards, from the first widget to the last one:
+    for &id in ids.iter().rev() {
+        ctx.run_test_frame(shift_tab.clone(), &mut widgets);
+        assert_eq!(focus(&ctx), Some(id));
+    }
+}
@@ -14,6 +14,21 @@
 //!     });
 //! }
 //! ```
+//!
+//! Context menus are shown with [`Response::context_menu`]:
+//! ```
+//! # let mut ui = egui::Ui::__test();
+//! ui.button("Right-click me").context_menu(|ui| {
+//!     if ui.button("Copy").clicked {
+//!         // ...
+//!     }
+//!     egui::menu::sub_menu(ui, "More", |ui| {
+//!         if ui.button("Delete").clicked {
+//!             // ...
+//!         }
+//!     });
+//! });
+//! ```
 
 use crate::{paint::Stroke, widgets::*, *};
 
@@ -37,6 +52,15 @@ impl BarState {
     }
 }
 
+/// What is saved between frames for the open context menu.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct ContextMenuState {
+    /// The popup id of the context menu.
+    id: Id,
+    /// Where the user clicked to open it.
+    pos: Pos2,
+}
+
 /// The menu bar goes well in `TopPanel`,
 /// but can also be placed in a `Window`.
 /// In the latter case you may want to wrap it in `Frame`.
@@ -98,25 +122,11 @@ fn menu_impl<'c>(
         let area = Area::new(menu_id)
             .order(Order::Foreground)
             .fixed_pos(button_response.rect.left_bottom());
-        let frame = Frame::menu(ui.style());
-
-        area.show(ui.ctx(), |ui| {
-            frame.show(ui, |ui| {
-                let mut style = ui.style().clone();
-                style.spacing.button_padding = vec2(2.0, 0.0);
-                // style.visuals.widgets.active.bg_fill = Color32::TRANSPARENT;
-                style.visuals.widgets.active.bg_stroke = Stroke::none();
-                // style.visuals.widgets.hovered.bg_fill = Color32::TRANSPARENT;
-                style.visuals.widgets.hovered.bg_stroke = Stroke::none();
-                style.visuals.widgets.inactive.bg_fill = Color32::TRANSPARENT;
-                style.visuals.widgets.inactive.bg_stroke = Stroke::none();
-                ui.set_style(style);
-                ui.with_layout(Layout::top_down_justified(Align::left()), add_contents);
-            })
-        });
+        menu_ui(ui.ctx(), area, add_contents);
 
-        // TODO: this prevents sub-menus in menus. We should fix that.
-        if ui.input().key_pressed(Key::Escape) || ui.input().mouse.click && !button_response.clicked
+        let sub_menu_clicked = ui.ctx().frame_state().sub_menu_clicked;
+        if ui.input().key_pressed(Key::Escape)
+            || ui.input().mouse.click && !button_response.clicked && !sub_menu_clicked
         {
             bar_state.open_menu = None;
         }
@@ -124,3 +134,191 @@ fn menu_impl<'c>(
 
     bar_state.save(ui.ctx(), bar_id);
 }
+
+/// A sub-menu in a [`menu`] or a context menu (see [`Response::context_menu`]).
+///
+/// The sub-menu opens to the right when its button is hovered or clicked.
+/// Sub-menus can be nested.
+pub fn sub_menu(ui: &mut Ui, title: impl Into<String>, add_contents: impl FnOnce(&mut Ui)) {
+    sub_menu_impl(ui, title, Box::new(add_contents))
+}
+
+fn sub_menu_impl<'c>(
+    ui: &mut Ui,
+    title: impl Into<String>,
+    add_contents: Box<dyn FnOnce(&mut Ui) + 'c>,
+) {
+    let title = title.into();
+    let menu_id = ui.id().with(&title);
+
+    // The parent menu works like a menu bar with at most one open sub-menu.
+    // Its state is keyed by the id of its `Area`, like the state of this sub-menu:
+    let parent_id = ui.layer_id().id;
+    let mut parent_state = BarState::load(ui.ctx(), &parent_id);
+
+    let mut button = Button::new(format!("{} ⏵", title));
+
+    if parent_state.open_menu == Some(menu_id) {
+        button = button.fill(Some(ui.style().visuals.selection.bg_fill));
+    }
+
+    let button_response = ui.add(button);
+    if button_response.clicked {
+        // Don't close the parent menu:
+        ui.ctx().frame_state().sub_menu_clicked = true;
+        parent_state.open_menu = Some(menu_id);
+    } else if button_response.hovered {
+        parent_state.open_menu = Some(menu_id);
+    }
+
+    if parent_state.open_menu == Some(menu_id) || ui.ctx().memory().everything_is_visible() {
+        let area = Area::new(menu_id)
+            .order(Order::Foreground)
+            .fixed_pos(button_response.rect.right_top());
+        menu_ui(ui.ctx(), area, add_contents);
+    }
+
+    parent_state.save(ui.ctx(), parent_id);
+}
+
+/// Show a context menu at the mouse position when `response` is clicked with the secondary mouse button.
+///
+/// It is usually easier to use [`Response::context_menu`].
+pub fn context_menu(response: &Response, add_contents: impl FnOnce(&mut Ui)) {
+    context_menu_impl(response, Box::new(add_contents))
+}
+
+fn context_menu_impl<'c>(response: &Response, add_contents: Box<dyn FnOnce(&mut Ui) + 'c>) {
+    let ctx = &response.ctx;
+    let menu_id = response.id.with("__context_menu");
+
+    if response.secondary_clicked {
+        if let Some(pos) = ctx.input().mouse.pos {
+            let mut memory = ctx.memory();
+            memory.open_popup(menu_id);
+            memory.context_menu = Some(ContextMenuState { id: menu_id, pos });
+            // Forget which sub-menus were open last time:
+            let mut open_menu = Some(menu_id);
+            while let Some(id) = open_menu {
+                let area_id = Area::new(id).layer().id;
+                open_menu = memory
+                    .menu_bar
+                    .remove(&area_id)
+                    .and_then(|state| state.open_menu);
+            }
+        }
+    }
+
+    if !ctx.memory().is_popup_open(menu_id) {
+        return;
+    }
+
+    let pos = match ctx.memory().context_menu {
+        Some(state) if state.id == menu_id => state.pos,
+        _ => response.rect.left_bottom(), // `everything_is_visible`
+    };
+    let area = Area::new(menu_id).order(Order::Foreground).fixed_pos(pos);
+    menu_ui(ctx, area, add_contents);
+
+    let mouse = &ctx.input().mouse;
+    let clicked = MouseButton::ALL
+        .iter()
+        .any(|&button| mouse.button(button).click);
+    let sub_menu_clicked = ctx.frame_state().sub_menu_clicked;
+    if ctx.input().key_pressed(Key::Escape)
+        || clicked && !response.secondary_clicked && !sub_menu_clicked
+    {
+        let mut memory = ctx.memory();
+        memory.close_popup();
+        memory.context_menu = None;
+    }
+}
+
+/// Show the contents of a menu in the given `Area`.
+fn menu_ui<'c>(ctx: &CtxRef, area: Area, add_contents: Box<dyn FnOnce(&mut Ui) + 'c>) -> Response {
+    let frame = Frame::menu(&ctx.style());
+
+    area.show(ctx, |ui| {
+        frame.show(ui, |ui| {
+            let mut style = ui.style().clone();
+            style.spacing.button_padding = vec2(2.0, 0.0);
+            // style.visuals.widgets.active.bg_fill = Color32::TRANSPARENT;
+            style.visuals.widgets.active.bg_stroke = Stroke::none();
+            // style.visuals.widgets.hovered.bg_fill = Color32::TRANSPARENT;
+            style.visuals.widgets.hovered.bg_stroke = Stroke::none();
+            style.visuals.widgets.inactive.bg_fill = Color32::TRANSPARENT;
+            style.visuals.widgets.inactive.bg_stroke = Stroke::none();
+            ui.set_style(style);
+            ui.with_layout(Layout::top_down_justified(Align::left()), add_contents);
+        })
+    })
+}
+
+#[test]
+fn test_context_menu_forgets_only_its_own_sub_menus() {
+    let menu = |ctx: &CtxRef| {
+        let mut button_rect = Rect::nothing();
+        CentralPanel::default().show(ctx, |ui| {
+            button_rect = ui
+                .button("Right-click me")
+                .context_menu(|ui| {
+                    sub_menu(ui, "More", |ui| {
+                        sub_menu(ui, "Even more", |ui| {
+                            ui.label("Hidden");
+                        })
+                    })
+                })
+                .rect;
+        });
+        button_rect
+    };
+    let mouse = |pos, secondary_down| {
+        RawInput::default()
+            .with_mouse_pos(pos)
+            .with_button(MouseButton::Secondary, secondary_down)
+    };
+
+    let mut ctx = CtxRef::default();
+    let button = ctx
+        .run_test_frame(mouse(Pos2::new(0.0, 0.0), false), menu)
+        .center();
+    let right_click = |ctx: &mut CtxRef| {
+        for &down in &[true, false] {
+            ctx.run_test_frame(mouse(button, down), menu);
+        }
+    };
+    right_click(&mut ctx);
+    let menu_id = ctx.memory().context_menu.map(|state| state.id).unwrap();
+
+    // A menu somewhere else:
+    let other_id = Id::new("other menu bar");
+    let file_menu_id = Id::new("File");
+    BarState {
+        open_menu: Some(file_menu_id),
+    }
+    .save(&ctx, other_id);
+
+    // The context menu is on top of the button, so the first click closes it
+    // (and opens "More", which is under the mouse), and the second click opens it again:
+    right_click(&mut ctx);
+    assert_eq!(
+        ctx.memory().menu_bar.len(),
+        3,
+        "context menu, \"More\" and other"
+    );
+    right_click(&mut ctx);
+
+    let memory = ctx.memory();
+    assert_eq!(memory.context_menu.map(|state| state.id), Some(menu_id));
+    let mut menu_bar: Vec<_> = memory
+        .menu_bar
+        .iter()
+        .map(|(id, state)| (*id, state.open_menu))
+        .collect();
+    menu_bar.sort_by_key(|(id, _)| *id != other_id);
+    let context_menu_area_id = Area::new(menu_id).layer().id;
+    assert_eq!(
+        menu_bar,
+        vec![(other_id, Some(file_menu_id)), (context_menu_area_id, None)]
+    );
+}
@@ -145,6 +145,9 @@ pub struct Visuals {
 
     pub text_cursor_width: f32,
 
+    /// Painted around the widget with keyboard focus (see [`crate::Response::kb_focusable`]).
+    pub focus_stroke: Stroke,
+
     /// Allow child widgets to be just on the border and still have a stroke with some thickness
     pub clip_rect_margin: f32,
 
@@ -287,6 +290,7 @@ impl Default for Visuals {
             window_shadow: Shadow::big(),
             resize_corner_size: 12.0,
             text_cursor_width: 2.0,
+            focus_stroke: Stroke::new(1.5, Color32::from_rgb(90, 170, 255)),
             clip_rect_margin: 3.0, // should be at least half the size of the widest frame stroke + max WidgetVisuals::expansion
             debug_expand_width: false,
             debug_expand_height: false,
@@ -504,6 +508,7 @@ impl Visuals {
             window_shadow,
             resize_corner_size,
             text_cursor_width,
+            focus_stroke,
             clip_rect_margin,
             debug_expand_width,
             debug_expand_height,
@@ -518,6 +523,7 @@ impl Visuals {
         shadow_ui(ui, window_shadow, "Window shadow:");
         ui.add(Slider::f32(resize_corner_size, 0.0..=20.0).text("resize_corner_size"));
         ui.add(Slider::f32(text_cursor_width, 0.0..=2.0).text("text_cursor_width"));
+        stroke_ui(ui, focus_stroke, "focus_stroke");
         ui.add(Slider::f32(clip_rect_margin, 0.0..=20.0).text("clip_rect_margin"));
 
         ui.label("DEBUG:");
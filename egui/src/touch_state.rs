@@ -0,0 +1,226 @@
+//! Turns the fingers on a touch screen into gestures (pinch zoom, two-finger pan and rotation).
+
+use std::collections::BTreeMap;
+
+use crate::{
+    input::{Event, TouchDeviceId, TouchId, TouchPhase},
+    math::*,
+};
+
+/// Information about an ongoing multi-touch gesture (two or more fingers).
+///
+/// See [`InputState::multi_touch`](crate::InputState::multi_touch).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MultiTouchInfo {
+    /// When the gesture started (see [`InputState::time`](crate::InputState::time)).
+    pub start_time: f64,
+    /// Number of fingers touching the screen.
+    pub num_touches: usize,
+    /// How much the fingers spread apart since last frame.
+    /// `1.0` means no change, `> 1.0` means zooming in.
+    pub zoom_delta: f32,
+    /// How much the fingers rotated since last frame, in radians.
+    /// Positive means clockwise (since the y axis points down).
+    pub rotation_delta: f32,
+    /// How much the center of the fingers moved since last frame, in points.
+    pub translation_delta: Vec2,
+    /// Average pressure of the fingers in the range `0.0..=1.0`, or `0.0` if unknown.
+    pub force: f32,
+}
+
+/// Tracks the fingers on all touch devices.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct TouchState {
+    /// The fingers currently touching, by device and finger.
+    active_touches: BTreeMap<(TouchDeviceId, TouchId), ActiveTouch>,
+
+    /// Set while two or more fingers are down.
+    gesture: Option<Gesture>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct ActiveTouch {
+    pos: Pos2,
+    force: f32,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Gesture {
+    start_time: f64,
+    /// `None` the first frame of a gesture, or when the number of fingers changed.
+    previous: Option<GestureSample>,
+    current: GestureSample,
+}
+
+/// A summary of where the fingers are.
+#[derive(Clone, Copy, Debug)]
+struct GestureSample {
+    num_touches: usize,
+    /// Average finger position.
+    center: Pos2,
+    /// Average distance of the fingers from the center.
+    avg_distance: f32,
+    /// Direction from the first to the second finger, in radians.
+    heading: f32,
+    force: f32,
+}
+
+impl TouchState {
+    pub fn begin_frame(&mut self, time: f64, events: &[Event]) {
+        for event in events {
+            if let Event::Touch {
+                device_id,
+                id,
+                phase,
+                pos,
+                force,
+            } = *event
+            {
+                match phase {
+                    TouchPhase::Start | TouchPhase::Move => {
+                        self.active_touches
+                            .insert((device_id, id), ActiveTouch { pos, force });
+                    }
+                    TouchPhase::End | TouchPhase::Cancel => {
+                        self.active_touches.remove(&(device_id, id));
+                    }
+                }
+            }
+        }
+
+        self.gesture = self.sample().map(|current| match self.gesture {
+            Some(gesture) if gesture.current.num_touches == current.num_touches => Gesture {
+                start_time: gesture.start_time,
+                previous: Some(gesture.current),
+                current,
+            },
+            Some(gesture) => Gesture {
+                // A finger was added or lifted. Don't jump!
+                start_time: gesture.start_time,
+                previous: None,
+                current,
+            },
+            None => Gesture {
+                start_time: time,
+                previous: None,
+                current,
+            },
+        });
+    }
+
+    pub fn any_touches(&self) -> bool {
+        !self.active_touches.is_empty()
+    }
+
+    pub fn info(&self) -> Option<MultiTouchInfo> {
+        let gesture = self.gesture?;
+        let current = gesture.current;
+
+        let mut info = MultiTouchInfo {
+            start_time: gesture.start_time,
+            num_touches: current.num_touches,
+            zoom_delta: 1.0,
+            rotation_delta: 0.0,
+            translation_delta: Vec2::zero(),
+            force: current.force,
+        };
+
+        if let Some(previous) = gesture.previous {
+            if previous.avg_distance > 0.0 {
+                info.zoom_delta = current.avg_distance / previous.avg_distance;
+            }
+            info.rotation_delta = normalized_angle(current.heading - previous.heading);
+            info.translation_delta = current.center - previous.center;
+        }
+
+        Some(info)
+    }
+
+    fn sample(&self) -> Option<GestureSample> {
+        let num_touches = self.active_touches.len();
+        if num_touches < 2 {
+            return None;
+        }
+        let n = num_touches as f32;
+
+        let mut sum_pos = Vec2::zero();
+        let mut sum_force = 0.0;
+        for touch in self.active_touches.values() {
+            sum_pos += touch.pos.to_vec2();
+            sum_force += touch.force;
+        }
+        let center = Pos2::default() + sum_pos / n;
+
+        let avg_distance = self
+            .active_touches
+            .values()
+            .map(|touch| touch.pos.distance(center))
+            .sum::<f32>()
+            / n;
+
+        let mut touches = self.active_touches.values();
+        let first = touches.next()?.pos;
+        let second = touches.next()?.pos;
+        let direction = second - first;
+
+        Some(GestureSample {
+            num_touches,
+            center,
+            avg_distance,
+            heading: direction.y.atan2(direction.x),
+            force: sum_force / n,
+        })
+    }
+}
+
+/// Wrap the angle into `-PI..=PI`.
+fn normalized_angle(mut angle: f32) -> f32 {
+    use std::f32::consts::{PI, TAU};
+    while angle > PI {
+        angle -= TAU;
+    }
+    while angle < -PI {
+        angle += TAU;
+    }
+    angle
+}
+
+#[test]
+fn test_pinch_zoom_and_rotation() {
+    let touch = |id, phase, pos| Event::Touch {
+        device_id: TouchDeviceId(0),
+        id: TouchId(id),
+        phase,
+        pos,
+        force: 0.0,
+    };
+
+    let mut state = TouchState::default();
+    state.begin_frame(0.0, &[touch(0, TouchPhase::Start, pos2(100.0, 100.0))]);
+    assert!(state.any_touches());
+    assert!(state.info().is_none(), "one finger is not a gesture");
+
+    state.begin_frame(0.1, &[touch(1, TouchPhase::Start, pos2(200.0, 100.0))]);
+    let info = state.info().unwrap();
+    assert_eq!(info.num_touches, 2);
+    assert_eq!(info.zoom_delta, 1.0, "no delta on the first frame");
+
+    // Spread the fingers to twice the distance, and rotate them a quarter turn clockwise:
+    state.begin_frame(
+        0.2,
+        &[
+            touch(0, TouchPhase::Move, pos2(150.0, 0.0)),
+            touch(1, TouchPhase::Move, pos2(150.0, 200.0)),
+        ],
+    );
+    let info = state.info().unwrap();
+    assert!((info.zoom_delta - 2.0).abs() < 1e-5);
+    assert!((info.rotation_delta - std::f32::consts::TAU / 4.0).abs() < 1e-5);
+    assert_eq!(info.translation_delta, Vec2::zero());
+    assert_eq!(info.start_time, 0.1);
+
+    state.begin_frame(0.3, &[touch(1, TouchPhase::End, pos2(150.0, 200.0))]);
+    assert!(state.info().is_none());
+    state.begin_frame(0.4, &[touch(0, TouchPhase::Cancel, pos2(150.0, 0.0))]);
+    assert!(!state.any_touches());
+}
@@ -16,12 +16,41 @@ pub struct Output {
     /// Response to Event::Copy or Event::Cut. Ignore if empty.
     pub copied_text: String,
 
+    /// Put this image on the clipboard, e.g. for a "Copy image" button.
+    ///
+    /// Not all integrations support this yet: `egui_glium` only does on Linux and other X11 platforms
+    /// (not on Windows, Mac or on Wayland without X11 support), and `egui_web` not at all.
+    pub copied_image: Option<ClipboardImage>,
+
+    /// Ask the integration to read the text on the clipboard and send it as an
+    /// [`crate::Event::Paste`] next frame, e.g. for a "Paste" button.
+    /// Browsers may ask the user for permission first.
+    pub request_paste: bool,
+
+    /// Screen-space rectangle of the text cursor of the [`crate::TextEdit`] with keyboard focus (if any).
+    /// Integrations can use this to position the IME candidate window, and to show an on-screen keyboard.
+    pub text_cursor_rect: Option<Rect>,
+
+    /// Description of the widgets of this frame for screen readers.
+    /// Only set after [`crate::Context::set_access_tree_enabled`].
+    pub access_tree: Option<crate::accessibility::AccessTree>,
+
     /// If `true`, Egui or a user is indicating that the UI needs immediate repaint (e.g. on the next frame).
     /// This happens for instance when there is an animation, or if a user has called `Context::request_repaint()`.
     /// Don't set this manually, but call `Context::request_repaint()` instead.
     pub needs_repaint: bool,
 }
 
+/// An image to put on the clipboard. See [`Output::copied_image`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ClipboardImage {
+    /// Width and height in pixels.
+    pub size: [usize; 2],
+    /// `4 * width * height` bytes of sRGBA, row by row from the top.
+    /// The colors are *not* premultiplied by alpha.
+    pub rgba: Vec<u8>,
+}
+
 /// A mouse cursor icon.
 ///
 /// Egui emits a `CursorIcond` in [`Output`] each frame as a request to the integration.
@@ -83,6 +112,12 @@ pub struct Response {
     /// The thing was double-clicked.
     pub double_clicked: bool,
 
+    /// The thing was clicked with the secondary mouse button (usually the right one).
+    pub secondary_clicked: bool,
+
+    /// The thing was clicked with the middle mouse button.
+    pub middle_clicked: bool,
+
     /// The mouse is interacting with this thing (e.g. dragging it).
     pub active: bool,
 
@@ -116,6 +151,8 @@ impl std::fmt::Debug for Response {
             hovered,
             clicked,
             double_clicked,
+            secondary_clicked,
+            middle_clicked,
             active,
             has_kb_focus,
             lost_kb_focus,
@@ -128,6 +165,8 @@ impl std::fmt::Debug for Response {
             .field("hovered", hovered)
             .field("clicked", clicked)
             .field("double_clicked", double_clicked)
+            .field("secondary_clicked", secondary_clicked)
+            .field("middle_clicked", middle_clicked)
             .field("active", active)
             .field("has_kb_focus", has_kb_focus)
             .field("lost_kb_focus", lost_kb_focus)
@@ -172,6 +211,87 @@ impl Response {
             .interact_with_hovered(self.layer_id, self.id, self.rect, sense, self.hovered)
     }
 
+    /// Show a context menu when this is clicked with the secondary mouse button (usually right-click).
+    ///
+    /// The menu closes when the user clicks anywhere (except on a [`crate::menu::sub_menu`]) or presses Escape.
+    /// The widget must sense clicks, so for e.g. a label you need to call [`Self::interact`] first.
+    ///
+    /// ```
+    /// # let mut ui = egui::Ui::__test();
+    /// ui.button("Right-click me").context_menu(|ui| {
+    ///     if ui.button("Rename").clicked {
+    ///         // …
+    ///     }
+    ///     egui::menu::sub_menu(ui, "Sort by", |ui| {
+    ///         if ui.button("Name").clicked {
+    ///             // …
+    ///         }
+    ///     });
+    /// });
+    /// ```
+    pub fn context_menu(self, add_contents: impl FnOnce(&mut Ui)) -> Self {
+        crate::menu::context_menu(&self, add_contents);
+        self
+    }
+
+    /// Let the user move keyboard focus to this widget with Tab and Shift+Tab,
+    /// and click it with Space or Enter while it has focus.
+    ///
+    /// The built-in buttons, checkboxes etc already do this.
+    /// Call it from your own clickable widgets, before checking [`Self::clicked`].
+    ///
+    /// ```
+    /// # let mut ctx = egui::CtxRef::default();
+    /// let key = |key| egui::Event::Key { key, pressed: true, modifiers: Default::default() };
+    /// let mut clicked = false;
+    /// for events in vec![vec![key(egui::Key::Tab)], vec![key(egui::Key::Space)]] {
+    ///     ctx.begin_frame(egui::RawInput { events, ..Default::default() });
+    ///     egui::CentralPanel::default().show(&ctx, |ui| {
+    ///         clicked |= ui.button("Ok").clicked;
+    ///     });
+    ///     let _ = ctx.end_frame();
+    /// }
+    /// assert!(clicked);
+    /// ```
+    pub fn kb_focusable(mut self) -> Self {
+        let mut memory = self.ctx.memory();
+        if self.sense.click {
+            memory.interested_in_kb_focus(self.id);
+            if self.ctx.input().mouse.any_pressed() && !self.hovered {
+                // User clicked somewhere else
+                memory.surrender_kb_focus(self.id);
+            }
+        } else {
+            memory.surrender_kb_focus(self.id);
+        }
+        self.has_kb_focus = memory.has_kb_focus(self.id);
+        drop(memory);
+
+        if self.has_kb_focus {
+            self.clicked |= self.ctx.input().key_pressed(crate::Key::Space)
+                || self.ctx.input().key_pressed(crate::Key::Enter);
+        }
+        self
+    }
+
+    /// The payload of type `T` being dragged over this widget (see [`crate::Ui::dnd_drag_source`]), if any.
+    pub fn dnd_hover_payload<T: std::any::Any + Send + Sync>(&self) -> Option<std::sync::Arc<T>> {
+        if self.hovered {
+            self.ctx.memory().dnd_payload()
+        } else {
+            None
+        }
+    }
+
+    /// The payload of type `T` dropped on this widget this frame (see [`crate::Ui::dnd_drag_source`]), if any.
+    pub fn dnd_release_payload<T: std::any::Any + Send + Sync>(&self) -> Option<std::sync::Arc<T>> {
+        if self.ctx.input().mouse.released {
+            self.dnd_hover_payload()
+        } else {
+            None
+        }
+    }
+
     /// Move the scroll to this UI with the specified alignment.
     ///
     /// ```
@@ -210,6 +330,8 @@ impl Response {
             hovered: self.hovered || other.hovered,
             clicked: self.clicked || other.clicked,
             double_clicked: self.double_clicked || other.double_clicked,
+            secondary_clicked: self.secondary_clicked || other.secondary_clicked,
+            middle_clicked: self.middle_clicked || other.middle_clicked,
             active: self.active || other.active,
             has_kb_focus: self.has_kb_focus || other.has_kb_focus,
             lost_kb_focus: self.lost_kb_focus || other.lost_kb_focus,
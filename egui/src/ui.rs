@@ -1,6 +1,6 @@
 // #![warn(missing_docs)]
 
-use std::{hash::Hash, sync::Arc};
+use std::{any::Any, hash::Hash, sync::Arc};
 
 use crate::{
     color::*, containers::*, layout::*, mutex::MutexGuard, paint::text::Fonts, placer::Placer,
@@ -46,6 +46,10 @@ pub struct Ui {
 
     /// Handles the `Ui` size and the placement of new widgets.
     placer: Placer,
+
+    /// The widget (e.g. a `Window`) that widgets in this `Ui` are children of
+    /// in the [`crate::accessibility::AccessTree`].
+    access_parent: Option<Id>,
 }
 
 impl Ui {
@@ -60,6 +64,7 @@ impl Ui {
             painter: Painter::new(ctx, layer_id, clip_rect),
             style,
             placer: Placer::new(max_rect, Layout::default()),
+            access_parent: None,
         }
     }
 
@@ -72,6 +77,27 @@ impl Ui {
             painter: self.painter.clone(),
             style: self.style.clone(),
             placer: Placer::new(max_rect, layout),
+            access_parent: self.access_parent,
+        }
+    }
+
+    /// Like [`Self::child_ui`], but the `Id`:s of the child (and its widgets) only depend on `id_source`,
+    /// and not on how many children were added before it.
+    /// Useful when only some of the children are shown, like the visible rows of a [`crate::Table`].
+    pub fn child_ui_with_id_source(
+        &mut self,
+        max_rect: Rect,
+        layout: Layout,
+        id_source: impl Hash,
+    ) -> Self {
+        let id = self.id.with(id_source);
+        Ui {
+            id,
+            next_auto_id: id.with("auto").value(),
+            painter: self.painter.clone(),
+            style: self.style.clone(),
+            placer: Placer::new(max_rect, layout),
+            access_parent: self.access_parent,
         }
     }
 
@@ -170,6 +196,18 @@ impl Ui {
     pub fn set_clip_rect(&mut self, clip_rect: Rect) {
         self.painter.set_clip_rect(clip_rect);
     }
+
+    /// Widgets described with [`Self::register_access_node`] after this
+    /// become children of the widget with the given id (e.g. a `Window`).
+    pub fn set_access_parent(&mut self, parent: Id) {
+        self.access_parent = Some(parent);
+    }
+
+    /// Describe a widget in this `Ui` for screen readers.
+    /// See [`crate::accessibility`].
+    pub fn register_access_node(&self, node: impl FnOnce() -> crate::accessibility::AccessNode) {
+        self.ctx().register_access_node(self.access_parent, node);
+    }
 }
 
 // ------------------------------------------------------------------------
@@ -1179,6 +1217,112 @@ impl Ui {
 
 // ----------------------------------------------------------------------------
 
+/// ## Drag and drop
+impl Ui {
+    /// Let the user drag `add_contents` around, carrying `payload` to a [`Self::dnd_drop_zone`].
+    ///
+    /// While being dragged, the contents are painted under the mouse pointer.
+    /// The `id` must be unique and stable (e.g. not depend on where the contents are).
+    ///
+    /// ```
+    /// # let ui = &mut egui::Ui::__test();
+    /// #[derive(Clone, Copy)]
+    /// struct Fruit(usize);
+    ///
+    /// ui.dnd_drag_source(egui::Id::new("apple"), Fruit(0), |ui| ui.label("🍎"));
+    /// let response = ui.dnd_drop_zone::<Fruit, _>(|ui| ui.label("Fruit basket")).1;
+    /// if let Some(fruit) = response.dnd_release_payload::<Fruit>() {
+    ///     // Fruit number `fruit.0` was dropped in the basket
+    /// }
+    /// ```
+    pub fn dnd_drag_source<T: Any + Send + Sync, R>(
+        &mut self,
+        id: Id,
+        payload: T,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> (R, Response) {
+        if self.memory().is_being_dragged(id) {
+            self.memory().set_dnd_payload(id, payload);
+            self.output().cursor_icon = CursorIcon::Grabbing;
+
+            // Paint the contents on a layer above everything else…
+            let layer_id = LayerId::new(Order::Tooltip, id);
+            let (ret, response) = self.with_layer_id(layer_id, add_contents);
+
+            // …and move that layer to where the mouse is.
+            // The contents can't be interacted with anyway while being dragged.
+            if let Some(mouse_pos) = self.input().mouse.pos {
+                let delta = mouse_pos - response.rect.center();
+                self.ctx().translate_layer(layer_id, delta);
+            }
+
+            (ret, self.interact(response.rect, id, Sense::drag()))
+        } else {
+            let (ret, response) = self.wrap(add_contents);
+            let response = self.interact(response.rect, id, Sense::drag());
+            if response.hovered {
+                self.output().cursor_icon = CursorIcon::Grab;
+            }
+            if response.active {
+                // The drag started this frame
+                self.memory().set_dnd_payload(id, payload);
+            }
+            (ret, response)
+        }
+    }
+
+    /// A frame around `add_contents` which a [`Self::dnd_drag_source`] payload of type `T` can be dropped on.
+    ///
+    /// It is highlighted while a `T` is being dragged.
+    /// Use [`Response::dnd_release_payload`] on the returned [`Response`] to see what was dropped.
+    pub fn dnd_drop_zone<T: Any + Send + Sync, R>(
+        &mut self,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> (R, Response) {
+        let (is_anything_being_dragged, can_accept) = {
+            let memory = self.memory();
+            (
+                memory.is_anything_being_dragged(),
+                memory.dnd_payload::<T>().is_some(),
+            )
+        };
+
+        let margin = Vec2::splat(4.0);
+        let outer_rect_bounds = self.available_rect_before_wrap();
+        let inner_rect = outer_rect_bounds.shrink2(margin);
+        let where_to_put_background = self.painter().add(Shape::Noop);
+        let mut content_ui = self.child_ui(inner_rect, *self.layout());
+        let ret = add_contents(&mut content_ui);
+        let outer_rect =
+            Rect::from_min_max(outer_rect_bounds.min, content_ui.min_rect().max + margin);
+        let (rect, response) = self.allocate_at_least(outer_rect.size(), Sense::hover());
+
+        let widgets = &self.style().visuals.widgets;
+        let visuals = if can_accept && response.hovered {
+            widgets.active
+        } else if can_accept {
+            widgets.hovered
+        } else if is_anything_being_dragged {
+            widgets.disabled
+        } else {
+            widgets.inactive
+        };
+        self.painter().set(
+            where_to_put_background,
+            Shape::Rect {
+                rect,
+                corner_radius: visuals.corner_radius,
+                fill: visuals.bg_fill,
+                stroke: visuals.bg_stroke,
+            },
+        );
+
+        (ret, response)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 /// ## Debug stuff
 impl Ui {
     /// Shows where the next widget is going to be placed
@@ -1186,3 +1330,52 @@ impl Ui {
         self.placer.debug_paint_cursor(&self.painter);
     }
 }
+
+#[test]
+fn test_drag_and_drop() {
+    struct Frame {
+        source_rect: Rect,
+        zone_rect: Rect,
+        payload: Option<Arc<u32>>,
+        dropped: Option<Arc<u32>>,
+    }
+
+    let drag_and_drop = |ctx: &CtxRef| {
+        let mut frame = Frame {
+            source_rect: Rect::nothing(),
+            zone_rect: Rect::nothing(),
+            payload: None,
+            dropped: None,
+        };
+        crate::CentralPanel::default().show(ctx, |ui| {
+            let source = ui.dnd_drag_source(Id::new("source"), 42_u32, |ui| ui.label("Drag me"));
+            frame.source_rect = source.1.rect;
+            ui.advance_cursor(100.0);
+            let zone = ui.dnd_drop_zone::<u32, _>(|ui| ui.label("Drop here")).1;
+            frame.zone_rect = zone.rect;
+            frame.dropped = zone.dnd_release_payload::<u32>();
+        });
+        frame.payload = ctx.memory().dnd_payload::<u32>();
+        frame
+    };
+    let mouse = |pos, down| {
+        crate::RawInput::default()
+            .with_mouse_pos(pos)
+            .with_button(crate::MouseButton::Primary, down)
+    };
+
+    let mut ctx = CtxRef::default();
+    let first = ctx.run_test_frame(mouse(Pos2::new(0.0, 0.0), false), drag_and_drop);
+    let (source, zone) = (first.source_rect.center(), first.zone_rect.center());
+    let frame = ctx.run_test_frame(mouse(source, false), drag_and_drop);
+    assert_eq!(frame.payload, None);
+    let frame = ctx.run_test_frame(mouse(source, true), drag_and_drop);
+    assert_eq!(frame.payload.as_deref(), Some(&42));
+    let over_zone = ctx.run_test_frame(mouse(zone, true), drag_and_drop);
+    assert_eq!(over_zone.payload.as_deref(), Some(&42));
+    assert_eq!(over_zone.dropped, None, "not released yet");
+    let frame = ctx.run_test_frame(mouse(zone, false), drag_and_drop);
+    assert_eq!(frame.dropped.as_deref(), Some(&42));
+    let after = ctx.run_test_frame(mouse(zone, false), drag_and_drop);
+    assert_eq!((after.payload, after.dropped), (None, None));
+}
@@ -2,7 +2,9 @@
 
 pub(crate) mod cache;
 mod history;
+pub mod recording;
 pub mod undoer;
 
 pub(crate) use cache::Cache;
 pub use history::History;
+pub use recording::{InputPlayer, InputRecording};
@@ -0,0 +1,140 @@
+//! Recording and playback of [`RawInput`], e.g. for including reproductions in bug reports.
+//!
+//! An integration records each [`RawInput`] right before passing it to
+//! [`CtxRef::begin_frame`](crate::CtxRef::begin_frame), and can later play it back
+//! instead of the real input. Since [`RawInput::time`] is recorded too, playback is deterministic.
+//!
+//! With the `persistence` feature, an [`InputRecording`] can be serialized with `serde`.
+//!
+//! ```
+//! # let mut ctx = egui::CtxRef::default();
+//! # let raw_input_from_integration = || egui::RawInput::default();
+//! use egui::util::{InputPlayer, InputRecording};
+//!
+//! let mut recording = InputRecording::default();
+//! for _ in 0..3 {
+//!     let raw_input = raw_input_from_integration();
+//!     recording.record(&raw_input);
+//!     ctx.begin_frame(raw_input);
+//!     // …
+//!     let _ = ctx.end_frame();
+//! }
+//!
+//! // Later:
+//! let mut player = InputPlayer::new(recording);
+//! while let Some(raw_input) = player.next_frame() {
+//!     ctx.begin_frame(raw_input);
+//!     // …
+//!     let _ = ctx.end_frame();
+//! }
+//! ```
+
+use crate::RawInput;
+
+/// All the [`RawInput`] that was fed to Egui, one per frame.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "persistence", serde(default))]
+pub struct InputRecording {
+    pub frames: Vec<RawInput>,
+}
+
+impl InputRecording {
+    /// Call this with the input of each frame, before handing it to Egui.
+    pub fn record(&mut self, raw_input: &RawInput) {
+        self.frames.push(raw_input.clone());
+    }
+
+    /// Number of recorded frames.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
+
+/// Plays back an [`InputRecording`], one frame at a time.
+#[derive(Clone, Debug, Default)]
+pub struct InputPlayer {
+    recording: InputRecording,
+    next_frame: usize,
+}
+
+impl InputPlayer {
+    pub fn new(recording: InputRecording) -> Self {
+        Self {
+            recording,
+            next_frame: 0,
+        }
+    }
+
+    /// The input to use for the next frame instead of the real input,
+    /// or `None` when the whole recording has been played.
+    pub fn next_frame(&mut self) -> Option<RawInput> {
+        let raw_input = self.recording.frames.get(self.next_frame)?.clone();
+        self.next_frame += 1;
+        Some(raw_input)
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.next_frame >= self.recording.len()
+    }
+
+    /// `(frames played, total frames)`
+    pub fn progress(&self) -> (usize, usize) {
+        (self.next_frame, self.recording.len())
+    }
+
+    /// Start over from the first frame.
+    pub fn rewind(&mut self) {
+        self.next_frame = 0;
+    }
+}
+
+#[cfg(feature = "persistence")]
+#[test]
+fn test_recording_roundtrip() {
+    use crate::{Event, Key, Modifiers};
+
+    let frames = vec![
+        RawInput {
+            time: Some(0.0),
+            ..RawInput::default().with_mouse_pos(crate::pos2(10.0, 20.0))
+        },
+        RawInput {
+            time: Some(0.1),
+            ..RawInput::default().with_button(crate::MouseButton::Secondary, true)
+        },
+        RawInput {
+            time: Some(0.2),
+            ..RawInput::default()
+                .with_events(vec![Event::Text("hello".to_owned())])
+                .with_key(Key::Enter, Modifiers::default())
+        },
+    ];
+    let mut recording = InputRecording::default();
+    for raw_input in &frames {
+        recording.record(raw_input);
+    }
+
+    let path = std::env::temp_dir().join(format!("egui_recording_{}.json", std::process::id()));
+    serde_json::to_writer(std::fs::File::create(&path).unwrap(), &recording).unwrap();
+    let recording: InputRecording =
+        serde_json::from_reader(std::fs::File::open(&path).unwrap()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let mut player = InputPlayer::new(recording);
+    assert_eq!(player.progress(), (0, 3));
+    for raw_input in &frames {
+        assert!(!player.is_done());
+        let played = player.next_frame().unwrap();
+        assert_eq!(format!("{:?}", played), format!("{:?}", raw_input)); // No `PartialEq`
+    }
+    assert!(player.is_done());
+    assert!(player.next_frame().is_none());
+
+    player.rewind();
+    assert_eq!(player.next_frame().unwrap().time, Some(0.0));
+}
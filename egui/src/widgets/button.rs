@@ -1,9 +1,16 @@
-use crate::*;
+use crate::{
+    accessibility::{AccessNode, AccessRole},
+    paint::text::LayoutSection,
+    widgets::paint_focus_ring,
+    *,
+};
 
 /// Clickable button with text.
 #[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
 pub struct Button {
     text: String,
+    /// If non-empty, the text is rich text, and `text_style` is ignored.
+    sections: Vec<LayoutSection>,
     text_color: Option<Color32>,
     text_style: TextStyle,
     /// None means default for interact
@@ -17,6 +24,7 @@ impl Button {
     pub fn new(text: impl Into<String>) -> Self {
         Self {
             text: text.into(),
+            sections: vec![],
             text_color: None,
             text_style: TextStyle::Button,
             fill: Default::default(),
@@ -26,6 +34,15 @@ impl Button {
         }
     }
 
+    /// A button with several sections of differently formatted text.
+    /// Sections without a color get the color of the button text.
+    pub fn rich(job: LayoutJob) -> Self {
+        Self {
+            sections: job.sections,
+            ..Self::new(job.text)
+        }
+    }
+
     pub fn text_color(mut self, text_color: Color32) -> Self {
         self.text_color = Some(text_color);
         self
@@ -80,6 +97,7 @@ impl Widget for Button {
     fn ui(self, ui: &mut Ui) -> Response {
         let Button {
             text,
+            sections,
             text_color,
             text_style,
             fill,
@@ -90,7 +108,15 @@ impl Widget for Button {
         let font = &ui.fonts()[text_style];
 
         let single_line = ui.layout().is_horizontal();
-        let galley = if single_line {
+        let galley = if !sections.is_empty() {
+            let max_width = if single_line {
+                f32::INFINITY
+            } else {
+                ui.available_width()
+            };
+            ui.fonts()
+                .layout_job(LayoutJob { text, sections }, max_width)
+        } else if single_line {
             font.layout_single_line(text)
         } else {
             font.layout_multiline(text, ui.available_width())
@@ -107,6 +133,8 @@ impl Widget for Button {
         }
 
         let (rect, response) = ui.allocate_at_least(desired_size, sense);
+        let response = response.kb_focusable();
+        ui.register_access_node(|| AccessNode::new(AccessRole::Button, &response, &galley.text));
 
         if ui.clip_rect().intersects(rect) {
             let visuals = ui.style().interact(&response);
@@ -124,6 +152,7 @@ impl Widget for Button {
                     visuals.bg_stroke,
                 );
             }
+            paint_focus_ring(ui, &response);
 
             let text_color = text_color
                 .or(ui.style().visuals.override_text_color)
@@ -191,11 +220,16 @@ impl<'a> Widget for Checkbox<'a> {
         desired_size = desired_size.at_least(spacing.interact_size);
         desired_size.y = desired_size.y.max(icon_width);
         let (rect, response) = ui.allocate_exact_size(desired_size, Sense::click());
+        let response = response.kb_focusable();
         if response.clicked {
             *checked = !*checked;
         }
+        ui.register_access_node(|| {
+            AccessNode::new(AccessRole::Checkbox, &response, &galley.text).checked(*checked)
+        });
 
         let visuals = ui.style().interact(&response);
+        paint_focus_ring(ui, &response);
         let text_cursor = pos2(
             rect.min.x + button_padding.x + icon_width + icon_spacing,
             rect.center().y - 0.5 * galley.size.y,
@@ -283,6 +317,10 @@ impl Widget for RadioButton {
         desired_size = desired_size.at_least(ui.style().spacing.interact_size);
         desired_size.y = desired_size.y.max(icon_width);
         let (rect, response) = ui.allocate_exact_size(desired_size, Sense::click());
+        let response = response.kb_focusable();
+        ui.register_access_node(|| {
+            AccessNode::new(AccessRole::RadioButton, &response, &galley.text).checked(checked)
+        });
 
         let text_cursor = pos2(
             rect.min.x + button_padding.x + icon_width + icon_spacing,
@@ -290,6 +328,7 @@ impl Widget for RadioButton {
         );
 
         let visuals = ui.style().interact(&response);
+        paint_focus_ring(ui, &response);
 
         let (small_icon_rect, big_icon_rect) = ui.style().spacing.icon_rectangles(rect);
 
@@ -1,4 +1,4 @@
-use crate::*;
+use crate::{paint::text::LayoutSection, *};
 
 /// A clickable hyperlink, e.g. to `"https://github.com/emilk/egui"`.
 #[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
@@ -6,6 +6,8 @@ pub struct Hyperlink {
     // TODO: wrap Label
     url: String,
     text: String,
+    /// If non-empty, the text is rich text, and `text_style` is ignored.
+    sections: Vec<LayoutSection>,
     pub(crate) text_style: Option<TextStyle>,
 }
 
@@ -15,6 +17,7 @@ impl Hyperlink {
         Self {
             text: url.clone(),
             url,
+            sections: vec![],
             text_style: None,
         }
     }
@@ -22,6 +25,15 @@ impl Hyperlink {
     /// Show some other text than the url
     pub fn text(mut self, text: impl Into<String>) -> Self {
         self.text = text.into();
+        self.sections.clear();
+        self
+    }
+
+    /// Show some rich text instead of the url.
+    /// Sections without a color get the hyperlink color.
+    pub fn rich_text(mut self, job: LayoutJob) -> Self {
+        self.text = job.text;
+        self.sections = job.sections;
         self
     }
 
@@ -41,11 +53,17 @@ impl Widget for Hyperlink {
         let Hyperlink {
             url,
             text,
+            sections,
             text_style,
         } = self;
         let text_style = text_style.unwrap_or_else(|| ui.style().body_text_style);
-        let font = &ui.fonts()[text_style];
-        let galley = font.layout_multiline(text, ui.available_width());
+        let galley = if sections.is_empty() {
+            let font = &ui.fonts()[text_style];
+            font.layout_multiline(text, ui.available_width())
+        } else {
+            ui.fonts()
+                .layout_job(LayoutJob { text, sections }, ui.available_width())
+        };
         let (rect, response) = ui.allocate_exact_size(galley.size, Sense::click());
 
         if response.hovered {
@@ -1,10 +1,28 @@
-use crate::{paint::Galley, *};
+use crate::{
+    paint::{text::LayoutSection, Galley},
+    *,
+};
 
 /// Static text.
+///
+/// The text can have several sections of different formatting with [`Self::rich`]:
+///
+/// ```
+/// # let ui = &mut egui::Ui::__test();
+/// use egui::{LayoutJob, TextFormat, TextStyle};
+/// let mut job = LayoutJob::default();
+/// job.append("This is ", TextFormat::default());
+/// job.append("bold", TextFormat { bold: true, ..Default::default() });
+/// job.append(" and this is ", TextFormat::default());
+/// job.append("code", TextFormat::style(TextStyle::Monospace));
+/// ui.label(job);
+/// ```
 #[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
 pub struct Label {
     // TODO: not pub
     pub(crate) text: String,
+    /// If non-empty, the text is rich text, and `text_style` is ignored.
+    pub(crate) sections: Vec<LayoutSection>,
     pub(crate) multiline: Option<bool>,
     pub(crate) text_style: Option<TextStyle>,
     pub(crate) text_color: Option<Color32>,
@@ -14,12 +32,33 @@ impl Label {
     pub fn new(text: impl Into<String>) -> Self {
         Self {
             text: text.into(),
+            sections: vec![],
             multiline: None,
             text_style: None,
             text_color: None,
         }
     }
 
+    /// Text with several sections of different formatting.
+    /// Sections without a color get the color of the label.
+    pub fn rich(job: LayoutJob) -> Self {
+        Self {
+            sections: job.sections,
+            ..Self::new(job.text)
+        }
+    }
+
+    fn layout_job(&self) -> Option<LayoutJob> {
+        if self.sections.is_empty() {
+            None
+        } else {
+            Some(LayoutJob {
+                text: self.text.clone(),
+                sections: self.sections.clone(),
+            })
+        }
+    }
+
     pub fn text(&self) -> &str {
         &self.text
     }
@@ -36,6 +75,9 @@ impl Label {
     }
 
     /// If you do not set a `TextStyle`, the default `style.text_style`.
+    /// Rich text (see [`Self::rich`]) uses the [`TextStyle`] of each section instead.
+    ///
+    /// This can also be a [`TextStyle::Custom`] added to [`crate::FontDefinitions::family_and_size`].
     pub fn text_style(mut self, text_style: TextStyle) -> Self {
         self.text_style = Some(text_style);
         self
@@ -64,6 +106,15 @@ impl Label {
     }
 
     pub fn layout_width(&self, ui: &Ui, max_width: f32) -> Galley {
+        if let Some(job) = self.layout_job() {
+            let max_width = if self.is_multiline(ui) {
+                max_width
+            } else {
+                f32::INFINITY
+            };
+            return ui.fonts().layout_job(job, max_width);
+        }
+
         let text_style = self.text_style_or_default(ui.style());
         let font = &ui.fonts()[text_style];
         if self.is_multiline(ui) {
@@ -74,8 +125,15 @@ impl Label {
     }
 
     pub fn font_height(&self, fonts: &paint::text::Fonts, style: &Style) -> f32 {
-        let text_style = self.text_style_or_default(style);
-        fonts[text_style].row_height()
+        if self.sections.is_empty() {
+            let text_style = self.text_style_or_default(style);
+            fonts[text_style].row_height()
+        } else {
+            self.sections
+                .iter()
+                .map(|section| fonts[section.format.style].row_height())
+                .fold(0.0, f32::max)
+        }
     }
 
     // TODO: this should return a LabelLayout which has a paint method.
@@ -121,13 +179,21 @@ impl Widget for Label {
             let max_width = ui.available_width();
             let first_row_indentation = max_width - ui.available_size_before_wrap().x;
 
-            let text_style = self.text_style_or_default(ui.style());
-            let font = &ui.fonts()[text_style];
-            let mut galley = font.layout_multiline_with_indentation_and_max_width(
-                self.text.clone(),
-                first_row_indentation,
-                max_width,
-            );
+            let mut galley = if let Some(job) = self.layout_job() {
+                ui.fonts().layout_job_with_indentation_and_max_width(
+                    job,
+                    first_row_indentation,
+                    max_width,
+                )
+            } else {
+                let text_style = self.text_style_or_default(ui.style());
+                let font = &ui.fonts()[text_style];
+                font.layout_multiline_with_indentation_and_max_width(
+                    self.text.clone(),
+                    first_row_indentation,
+                    max_width,
+                )
+            };
 
             let pos = pos2(ui.min_rect().left(), ui.cursor().y);
 
@@ -181,3 +247,9 @@ impl Into<Label> for String {
         Label::new(self)
     }
 }
+
+impl From<LayoutJob> for Label {
+    fn from(job: LayoutJob) -> Label {
+        Label::rich(job)
+    }
+}
@@ -0,0 +1,559 @@
+//! Show simple markdown, e.g. release notes or help pages.
+//!
+//! Supports headings, emphasis (`*italics*`, `**bold**`), `inline code`, code blocks,
+//! bullet and numbered lists, block quotes, horizontal rules and links.
+//!
+//! ```
+//! # let ui = &mut egui::Ui::__test();
+//! ui.add(egui::widgets::markdown::Markdown::new(
+//!     "# Release notes\n\
+//!      * **New:** a `Markdown` widget\n\
+//!      * See [the repository](https://github.com/emilk/egui) for more.",
+//! ));
+//! ```
+
+use crate::{widgets::syntax_highlighting, *};
+
+/// Shows some markdown text.
+///
+/// Code blocks with a language (e.g. ```` ```rust ````) are highlighted
+/// with [`syntax_highlighting::highlight`].
+/// Clicking a link sets [`Output::open_url`].
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct Markdown<'a> {
+    text: &'a str,
+}
+
+impl<'a> Markdown<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self { text }
+    }
+}
+
+impl<'a> Widget for Markdown<'a> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let blocks = parse_blocks(&self.text.lines().collect::<Vec<_>>());
+        ui.vertical(|ui| blocks_ui(ui, &blocks)).1
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[derive(Clone, Debug, PartialEq)]
+enum Block {
+    Heading {
+        level: usize,
+        text: Vec<Inline>,
+    },
+    Paragraph(Vec<Inline>),
+    Code {
+        language: String,
+        code: String,
+    },
+    /// `start` is the number of the first item of a numbered list, or `None` for a bullet list.
+    List {
+        start: Option<usize>,
+        items: Vec<Vec<Block>>,
+    },
+    Quote(Vec<Block>),
+    Rule,
+}
+
+/// A piece of text in a paragraph or heading with the same formatting.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Inline {
+    text: String,
+    strong: bool,
+    emphasis: bool,
+    code: bool,
+    link: Option<String>,
+}
+
+fn parse_blocks(lines: &[&str]) -> Vec<Block> {
+    let mut blocks = vec![];
+    let mut i = 0;
+    while i < lines.len() {
+        let trimmed = lines[i].trim_start();
+        if trimmed.is_empty() {
+            i += 1;
+        } else if let Some(language) = trimmed.strip_prefix("```") {
+            let indentation = indentation(lines[i]);
+            let mut code = vec![];
+            i += 1;
+            while i < lines.len() && !lines[i].trim_start().starts_with("```") {
+                code.push(strip_indentation(lines[i], indentation));
+                i += 1;
+            }
+            i += 1; // Skip the closing fence
+            blocks.push(Block::Code {
+                language: language.trim().to_owned(),
+                code: code.join("\n"),
+            });
+        } else if let Some((level, text)) = heading(trimmed) {
+            blocks.push(Block::Heading {
+                level,
+                text: parse_inline(text),
+            });
+            i += 1;
+        } else if is_rule(trimmed) {
+            blocks.push(Block::Rule);
+            i += 1;
+        } else if trimmed.starts_with('>') {
+            let mut quoted = vec![];
+            while let Some(line) = lines.get(i).and_then(|l| l.trim_start().strip_prefix('>')) {
+                quoted.push(line.strip_prefix(' ').unwrap_or(line));
+                i += 1;
+            }
+            blocks.push(Block::Quote(parse_blocks(&quoted)));
+        } else if list_marker(trimmed).is_some() {
+            blocks.push(parse_list(lines, &mut i));
+        } else {
+            let mut text = String::new();
+            while i < lines.len() && !lines[i].trim().is_empty() {
+                let trimmed = lines[i].trim_start();
+                if !text.is_empty() {
+                    if starts_block(trimmed) {
+                        break;
+                    }
+                    let hard_break = text.ends_with("  ") || text.ends_with('\\');
+                    text.truncate(text.trim_end_matches(&[' ', '\\'][..]).len());
+                    text.push(if hard_break { '\n' } else { ' ' });
+                }
+                text += trimmed;
+                i += 1;
+            }
+            blocks.push(Block::Paragraph(parse_inline(text.trim_end())));
+        }
+    }
+    blocks
+}
+
+/// Parse the list starting at `lines[*i]`, and advance `i` past it.
+fn parse_list(lines: &[&str], i: &mut usize) -> Block {
+    let list_indentation = indentation(lines[*i]);
+    let (start, _) = list_marker(lines[*i].trim_start()).unwrap();
+    let mut items = vec![];
+
+    while let Some(line) = lines.get(*i) {
+        let trimmed = line.trim_start();
+        let (number, marker_len) = match list_marker(trimmed) {
+            Some(marker) if indentation(line) == list_indentation => marker,
+            _ => break,
+        };
+        if number.is_some() != start.is_some() {
+            break;
+        }
+        let content_indentation = list_indentation + marker_len;
+
+        let mut item_lines = vec![&trimmed[marker_len..]];
+        *i += 1;
+        while let Some(line) = lines.get(*i) {
+            let trimmed = line.trim_start();
+            if trimmed.is_empty() {
+                // Blank lines only continue the item if it is followed by indented lines:
+                let next = lines[*i..].iter().find(|l| !l.trim().is_empty());
+                if matches!(next, Some(next) if indentation(next) >= content_indentation) {
+                    item_lines.push("");
+                    *i += 1;
+                    continue;
+                }
+                break;
+            }
+            if indentation(line) > list_indentation {
+                item_lines.push(strip_indentation(line, content_indentation));
+            } else if starts_block(trimmed) {
+                break;
+            } else {
+                item_lines.push(trimmed); // Lazy continuation of a paragraph
+            }
+            *i += 1;
+        }
+        items.push(parse_blocks(&item_lines));
+
+        // Blank lines between items:
+        while matches!(lines.get(*i), Some(l) if l.trim().is_empty()) {
+            *i += 1;
+        }
+    }
+
+    Block::List { start, items }
+}
+
+fn indentation(line: &str) -> usize {
+    // Only ASCII spaces and tabs count, so this is also a number of bytes:
+    line.len() - line.trim_start_matches(&[' ', '\t'][..]).len()
+}
+
+/// Remove at most `indentation` leading spaces and tabs.
+fn strip_indentation(line: &str, indentation: usize) -> &str {
+    &line[self::indentation(line).min(indentation)..]
+}
+
+/// Does this (trimmed) line start something that interrupts a paragraph?
+fn starts_block(trimmed: &str) -> bool {
+    trimmed.starts_with("```")
+        || trimmed.starts_with('>')
+        || heading(trimmed).is_some()
+        || is_rule(trimmed)
+        || list_marker(trimmed).is_some()
+}
+
+/// `# Heading` -> `(1, "Heading")`
+fn heading(trimmed: &str) -> Option<(usize, &str)> {
+    let level = trimmed.chars().take_while(|&c| c == '#').count();
+    let rest = &trimmed[level..];
+    if (1..=6).contains(&level) && (rest.is_empty() || rest.starts_with(' ')) {
+        Some((level, rest.trim().trim_end_matches('#').trim_end()))
+    } else {
+        None
+    }
+}
+
+/// `---`, `***`, `___`, `* * *` etc.
+fn is_rule(trimmed: &str) -> bool {
+    let mut chars = trimmed.chars().filter(|c| !c.is_whitespace());
+    match chars.next() {
+        Some(first) if "-*_".contains(first) => {
+            let rest: Vec<char> = chars.collect();
+            rest.len() >= 2 && rest.iter().all(|&c| c == first)
+        }
+        _ => false,
+    }
+}
+
+/// `"- item"` -> `(None, 2)`, `"12. item"` -> `(Some(12), 4)`.
+fn list_marker(trimmed: &str) -> Option<(Option<usize>, usize)> {
+    if trimmed.starts_with("- ") || trimmed.starts_with("* ") || trimmed.starts_with("+ ") {
+        return Some((None, 2));
+    }
+    let num_digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+    let rest = &trimmed[num_digits..];
+    if (1..10).contains(&num_digits) && (rest.starts_with(". ") || rest.starts_with(") ")) {
+        Some((trimmed[..num_digits].parse().ok(), num_digits + 2))
+    } else {
+        None
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+fn parse_inline(text: &str) -> Vec<Inline> {
+    let mut out = vec![];
+    parse_inline_into(text, &Inline::default(), &mut out);
+    out
+}
+
+/// Parse `text`, with `style` as the formatting outside of any emphasis in it.
+fn parse_inline_into(text: &str, style: &Inline, out: &mut Vec<Inline>) {
+    let mut current = style.clone();
+    // The delimiters (`*` or `_`) of open emphasis:
+    let mut strong: Option<char> = None;
+    let mut emphasis: Option<char> = None;
+
+    let flush = |current: &mut Inline, out: &mut Vec<Inline>| {
+        if !current.text.is_empty() {
+            out.push(current.clone());
+            current.text.clear();
+        }
+    };
+
+    let mut i = 0;
+    while let Some(c) = text[i..].chars().next() {
+        let rest = &text[i..];
+        let prev = text[..i].chars().next_back();
+
+        if c == '\\' && rest[1..].starts_with(|c: char| c.is_ascii_punctuation()) {
+            current.text.push(rest[1..].chars().next().unwrap());
+            i += 2;
+        } else if c == '`' {
+            let fence_len = rest.chars().take_while(|&c| c == '`').count();
+            let fence = &rest[..fence_len];
+            if let Some(end) = rest[fence_len..].find(fence) {
+                flush(&mut current, out);
+                let code = &rest[fence_len..fence_len + end];
+                out.push(Inline {
+                    text: code.trim().to_owned(),
+                    code: true,
+                    ..current.clone()
+                });
+                i += fence_len + end + fence_len;
+            } else {
+                current.text += fence;
+                i += fence_len;
+            }
+        } else if let Some((link_text, url, len)) = link(rest) {
+            flush(&mut current, out);
+            let link_style = Inline {
+                link: Some(url.to_owned()),
+                ..current.clone()
+            };
+            parse_inline_into(link_text, &link_style, out);
+            i += len;
+        } else if c == '*' || c == '_' {
+            let run_len = rest.chars().take_while(|&d| d == c).count();
+            let next = rest[run_len..].chars().next();
+            let can_open = matches!(next, Some(next) if !next.is_whitespace())
+                && (c == '*' || !matches!(prev, Some(prev) if prev.is_alphanumeric()));
+            let can_close = matches!(prev, Some(prev) if !prev.is_whitespace())
+                && (c == '*' || !matches!(next, Some(next) if next.is_alphanumeric()));
+
+            let mut consumed = 0;
+            if run_len >= 2 {
+                let delimiter = &rest[..2];
+                if strong == Some(c) && can_close {
+                    flush(&mut current, out);
+                    strong = None;
+                    current.strong = style.strong;
+                    consumed = 2;
+                } else if strong.is_none() && can_open && rest[2..].contains(delimiter) {
+                    flush(&mut current, out);
+                    strong = Some(c);
+                    current.strong = true;
+                    consumed = 2;
+                }
+            }
+            if run_len > consumed {
+                if emphasis == Some(c) && can_close {
+                    flush(&mut current, out);
+                    emphasis = None;
+                    current.emphasis = style.emphasis;
+                    consumed += 1;
+                } else if emphasis.is_none() && can_open && rest[consumed + 1..].contains(c) {
+                    flush(&mut current, out);
+                    emphasis = Some(c);
+                    current.emphasis = true;
+                    consumed += 1;
+                }
+            }
+            if consumed == 0 {
+                current.text += &rest[..run_len];
+                consumed = run_len;
+            }
+            i += consumed;
+        } else {
+            current.text.push(c);
+            i += c.len_utf8();
+        }
+    }
+    flush(&mut current, out);
+}
+
+/// `[text](url)` or `<url>` at the start of `text` -> `(text, url, length in bytes)`
+fn link(text: &str) -> Option<(&str, &str, usize)> {
+    if let Some(rest) = text.strip_prefix('<') {
+        let end = rest.find('>')?;
+        let url = &rest[..end];
+        if url.contains("://") && !url.contains(char::is_whitespace) {
+            return Some((url, url, end + 2));
+        }
+        return None;
+    }
+
+    let rest = text.strip_prefix('[')?;
+    let mut depth = 0;
+    let text_end = rest.find(|c| {
+        match c {
+            '[' => depth += 1,
+            ']' if depth == 0 => return true,
+            ']' => depth -= 1,
+            _ => {}
+        }
+        false
+    })?;
+    let url_part = rest[text_end + 1..].strip_prefix('(')?;
+    let url_end = url_part.find(')')?;
+    let url = url_part[..url_end].trim();
+    Some((&rest[..text_end], url, 1 + text_end + 2 + url_end + 1))
+}
+
+// ----------------------------------------------------------------------------
+
+fn blocks_ui(ui: &mut Ui, blocks: &[Block]) {
+    for (block_nr, block) in blocks.iter().enumerate() {
+        match block {
+            Block::Heading { level, text } => {
+                let format = if *level <= 2 {
+                    TextFormat::style(TextStyle::Heading)
+                } else {
+                    TextFormat {
+                        style: ui.style().body_text_style,
+                        bold: true,
+                        ..Default::default()
+                    }
+                };
+                inline_ui(ui, text, format);
+            }
+            Block::Paragraph(text) => {
+                inline_ui(ui, text, TextFormat::style(ui.style().body_text_style));
+            }
+            Block::Code { language, code } => {
+                let frame = Frame {
+                    margin: vec2(6.0, 4.0),
+                    corner_radius: 4.0,
+                    fill: ui.style().visuals.dark_bg_color,
+                    ..Frame::none()
+                };
+                frame.show(ui, |ui| {
+                    let job = if language.is_empty() {
+                        LayoutJob::single_section(
+                            code.clone(),
+                            TextFormat::style(TextStyle::Monospace),
+                        )
+                    } else {
+                        let theme = syntax_highlighting::CodeTheme::default();
+                        syntax_highlighting::highlight(&theme, code)
+                    };
+                    ui.add(Label::rich(job).multiline(true));
+                });
+            }
+            Block::List { start, items } => {
+                for (item_nr, item) in items.iter().enumerate() {
+                    let marker = match start {
+                        Some(start) => format!("{}.", start + item_nr),
+                        None => "•".to_owned(),
+                    };
+                    ui.with_layout(Layout::left_to_right().with_cross_align(Align::Min), |ui| {
+                        ui.add(Label::new(marker).multiline(false));
+                        ui.vertical(|ui| blocks_ui(ui, item));
+                    });
+                }
+            }
+            Block::Quote(blocks) => {
+                ui.indent(block_nr, |ui| blocks_ui(ui, blocks));
+            }
+            Block::Rule => {
+                ui.separator();
+            }
+        }
+    }
+}
+
+/// Show a paragraph or heading.
+fn inline_ui(ui: &mut Ui, text: &[Inline], format: TextFormat) {
+    ui.horizontal_wrapped_for_text(format.style, |ui| {
+        ui.style_mut().spacing.item_spacing.x = 0.0; // The spaces are part of the text
+
+        // Each link is its own widget, with labels for the text between them:
+        let mut pieces = text.iter().peekable();
+        while let Some(first) = pieces.next() {
+            let mut job = LayoutJob::default();
+            job.append(&first.text, first.format(format, ui.style()));
+            while let Some(piece) = pieces.next_if(|piece| piece.link == first.link) {
+                job.append(&piece.text, piece.format(format, ui.style()));
+            }
+            match &first.link {
+                Some(url) => ui.add(Hyperlink::new(url).rich_text(job)),
+                None => ui.label(job),
+            };
+        }
+    });
+}
+
+impl Inline {
+    fn format(&self, mut format: TextFormat, style: &Style) -> TextFormat {
+        format.bold |= self.strong;
+        format.italics |= self.emphasis;
+        if self.code {
+            format.style = TextStyle::Monospace;
+            format.background = style.visuals.dark_bg_color;
+        }
+        format
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[test]
+fn test_parse_markdown() {
+    let text = "# Title #\n\
+        Some *emphasis*, **strong** and `code`,\n\
+        a [link](https://example.com) and snake_case_words.\n\
+        \n\
+        - one\n  continued\n\
+        - two\n    1. nested\n    2. list\n\
+        \n\
+        > quoted\n\
+        ---\n\
+        ```rust\n\
+        fn main() {}\n\
+        ```";
+    let blocks = parse_blocks(&text.lines().collect::<Vec<_>>());
+
+    let plain = |text: &str| Inline {
+        text: text.to_owned(),
+        ..Default::default()
+    };
+    let paragraph = |text: &str| Block::Paragraph(vec![plain(text)]);
+
+    assert_eq!(
+        blocks,
+        vec![
+            Block::Heading {
+                level: 1,
+                text: vec![plain("Title")]
+            },
+            Block::Paragraph(vec![
+                plain("Some "),
+                Inline {
+                    text: "emphasis".to_owned(),
+                    emphasis: true,
+                    ..Default::default()
+                },
+                plain(", "),
+                Inline {
+                    text: "strong".to_owned(),
+                    strong: true,
+                    ..Default::default()
+                },
+                plain(" and "),
+                Inline {
+                    text: "code".to_owned(),
+                    code: true,
+                    ..Default::default()
+                },
+                plain(", a "),
+                Inline {
+                    text: "link".to_owned(),
+                    link: Some("https://example.com".to_owned()),
+                    ..Default::default()
+                },
+                plain(" and snake_case_words."),
+            ]),
+            Block::List {
+                start: None,
+                items: vec![
+                    vec![paragraph("one continued")],
+                    vec![
+                        paragraph("two"),
+                        Block::List {
+                            start: Some(1),
+                            items: vec![vec![paragraph("nested")], vec![paragraph("list")]],
+                        }
+                    ],
+                ],
+            },
+            Block::Quote(vec![paragraph("quoted")]),
+            Block::Rule,
+            Block::Code {
+                language: "rust".to_owned(),
+                code: "fn main() {}".to_owned(),
+            },
+        ]
+    );
+
+    // Indentation with non-ASCII whitespace:
+    assert_eq!(
+        parse_blocks(&["- a", "\u{3000}b"]),
+        vec![Block::List {
+            start: None,
+            items: vec![vec![paragraph("a b")]],
+        }]
+    );
+    assert_eq!(
+        parse_blocks(&[" ```", "\u{3000}x", "```"]),
+        vec![Block::Code {
+            language: String::new(),
+            code: "\u{3000}x".to_owned(),
+        }]
+    );
+}
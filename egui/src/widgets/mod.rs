@@ -14,9 +14,12 @@ mod drag_value;
 mod hyperlink;
 mod image;
 mod label;
+pub mod markdown;
+pub mod plot;
 mod selected_label;
 mod separator;
 mod slider;
+pub mod syntax_highlighting;
 pub(crate) mod text_edit;
 
 pub use hyperlink::*;
@@ -36,6 +39,20 @@ pub trait Widget {
 
 // ----------------------------------------------------------------------------
 
+/// Paint [`crate::style::Visuals::focus_stroke`] around a widget if it has keyboard focus.
+pub(crate) fn paint_focus_ring(ui: &Ui, response: &Response) {
+    if response.has_kb_focus {
+        let visuals = ui.style().interact(response);
+        ui.painter().rect_stroke(
+            response.rect.expand(visuals.expansion + 1.0),
+            visuals.corner_radius,
+            ui.style().visuals.focus_stroke,
+        );
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 /// Show a button to reset a value to its default.
 /// The button is only enabled if the value does not already have its original value.
 pub fn reset_button<T: Default + PartialEq>(ui: &mut Ui, value: &mut T) {
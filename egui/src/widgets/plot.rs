@@ -0,0 +1,697 @@
+//! Simple plotting: line, scatter and bar series, with pan, zoom, hover and a legend.
+//!
+//! ```
+//! # let ui = &mut egui::Ui::__test();
+//! use egui::plot::{Plot, Series, Value};
+//! let sin = (0..100).map(|i| {
+//!     let x = i as f64 * 0.1;
+//!     Value::new(x, x.sin())
+//! });
+//! ui.add(Plot::new("sine").series(Series::line("sin(x)", sin)).height(120.0));
+//! ```
+
+#![allow(clippy::float_cmp)]
+
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use crate::*;
+
+// ----------------------------------------------------------------------------
+
+/// A point in plot space.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Value {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Value {
+    pub fn new(x: impl Into<f64>, y: impl Into<f64>) -> Self {
+        Self {
+            x: x.into(),
+            y: y.into(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SeriesKind {
+    Line,
+    Scatter,
+    Bars,
+}
+
+/// A named set of values, shown as a line, as points or as bars.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Series {
+    name: String,
+    values: Vec<Value>,
+    kind: SeriesKind,
+    color: Option<Color32>,
+    width: Option<f32>,
+    bar_width: Option<f64>,
+}
+
+impl Series {
+    fn new(
+        kind: SeriesKind,
+        name: impl Into<String>,
+        values: impl IntoIterator<Item = Value>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            values: values.into_iter().collect(),
+            kind,
+            color: None,
+            width: None,
+            bar_width: None,
+        }
+    }
+
+    /// The values joined by straight lines, in the given order.
+    pub fn line(name: impl Into<String>, values: impl IntoIterator<Item = Value>) -> Self {
+        Self::new(SeriesKind::Line, name, values)
+    }
+
+    /// Each value as a point.
+    pub fn scatter(name: impl Into<String>, values: impl IntoIterator<Item = Value>) -> Self {
+        Self::new(SeriesKind::Scatter, name, values)
+    }
+
+    /// Each value as a bar from zero, centered on `x`.
+    pub fn bars(name: impl Into<String>, values: impl IntoIterator<Item = Value>) -> Self {
+        Self::new(SeriesKind::Bars, name, values)
+    }
+
+    /// Default: picked from a palette based on the position in the [`Plot`].
+    pub fn color(mut self, color: impl Into<Color32>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Line width, or point radius for scatter series.
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Width of each bar in plot units.
+    /// Default: 80% of the smallest distance between two bars.
+    pub fn bar_width(mut self, bar_width: f64) -> Self {
+        self.bar_width = Some(bar_width);
+        self
+    }
+
+    fn auto_bar_width(&self) -> f64 {
+        if let Some(bar_width) = self.bar_width {
+            return bar_width;
+        }
+        let mut xs: Vec<f64> = self.values.iter().map(|value| value.x).collect();
+        xs.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let min_distance = xs
+            .windows(2)
+            .map(|pair| pair[1] - pair[0])
+            .filter(|distance| *distance > 0.0)
+            .fold(f64::INFINITY, f64::min);
+        if min_distance.is_finite() {
+            0.8 * min_distance
+        } else {
+            0.8
+        }
+    }
+}
+
+fn auto_color(index: usize) -> Color32 {
+    const COLORS: [Color32; 6] = [
+        Color32::from_rgb(90, 170, 255),
+        Color32::from_rgb(255, 160, 60),
+        Color32::from_rgb(110, 200, 110),
+        Color32::from_rgb(255, 100, 100),
+        Color32::from_rgb(180, 130, 255),
+        Color32::from_rgb(230, 220, 90),
+    ];
+    COLORS[index % COLORS.len()]
+}
+
+// ----------------------------------------------------------------------------
+
+/// The visible region of the plot, in plot space.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+struct Bounds {
+    min: [f64; 2],
+    max: [f64; 2],
+}
+
+impl Bounds {
+    const NOTHING: Self = Self {
+        min: [f64::INFINITY; 2],
+        max: [f64::NEG_INFINITY; 2],
+    };
+
+    fn extend_with_x(&mut self, x: f64) {
+        self.min[0] = self.min[0].min(x);
+        self.max[0] = self.max[0].max(x);
+    }
+
+    fn extend_with_y(&mut self, y: f64) {
+        self.min[1] = self.min[1].min(y);
+        self.max[1] = self.max[1].max(y);
+    }
+
+    fn size(&self, axis: usize) -> f64 {
+        self.max[axis] - self.min[axis]
+    }
+
+    /// Make sure each axis covers a finite, non-empty range.
+    fn make_valid(&mut self) {
+        for axis in 0..2 {
+            if !(self.min[axis].is_finite() && self.max[axis].is_finite()) {
+                self.min[axis] = -1.0;
+                self.max[axis] = 1.0;
+            } else if self.size(axis) <= 0.0 {
+                self.min[axis] -= 0.5;
+                self.max[axis] += 0.5;
+            }
+        }
+    }
+
+    fn add_relative_margin(&mut self, fraction: f64) {
+        for axis in 0..2 {
+            let margin = fraction * self.size(axis);
+            self.min[axis] -= margin;
+            self.max[axis] += margin;
+        }
+    }
+
+    fn translate(&mut self, delta: [f64; 2]) {
+        for (axis, delta) in delta.iter().enumerate() {
+            self.min[axis] += delta;
+            self.max[axis] += delta;
+        }
+    }
+
+    /// `factor > 1` zooms in, keeping `center` at the same place.
+    ///
+    /// Stops zooming in when an axis gets so small (relative to its values)
+    /// that there is no longer enough precision to tell its ends apart.
+    fn zoom(&mut self, factor: f64, center: Value) {
+        /// The smallest size of an axis, relative to the largest value on it.
+        const MIN_RELATIVE_SIZE: f64 = 1e-9;
+        /// For axes around zero.
+        const MIN_SIZE: f64 = 1e-200;
+
+        if !(factor.is_finite() && factor > 0.0) {
+            return;
+        }
+        let center = [center.x, center.y];
+        for (axis, center) in center.iter().enumerate() {
+            let magnitude = self.min[axis].abs().max(self.max[axis].abs());
+            let min_size = (MIN_RELATIVE_SIZE * magnitude).max(MIN_SIZE);
+            let factor = factor.min(self.size(axis) / min_size).max(factor.min(1.0));
+            self.min[axis] = center + (self.min[axis] - center) / factor;
+            self.max[axis] = center + (self.max[axis] - center) / factor;
+        }
+        self.make_valid();
+    }
+}
+
+/// Maps between plot space and screen space.
+struct ScreenTransform {
+    frame: Rect,
+    bounds: Bounds,
+}
+
+impl ScreenTransform {
+    fn position_from_value(&self, value: &Value) -> Pos2 {
+        let Self { frame, bounds } = self;
+        let x =
+            frame.left() as f64 + (value.x - bounds.min[0]) / bounds.size(0) * frame.width() as f64;
+        let y = frame.bottom() as f64
+            - (value.y - bounds.min[1]) / bounds.size(1) * frame.height() as f64;
+        pos2(x as f32, y as f32)
+    }
+
+    fn value_from_position(&self, pos: Pos2) -> Value {
+        let Self { frame, bounds } = self;
+        let x = bounds.min[0] + ((pos.x - frame.left()) / frame.width()) as f64 * bounds.size(0);
+        let y = bounds.min[1] + ((frame.bottom() - pos.y) / frame.height()) as f64 * bounds.size(1);
+        Value::new(x, y)
+    }
+
+    /// Plot units per point, for each axis.
+    fn dvalue_dpos(&self) -> [f64; 2] {
+        [
+            self.bounds.size(0) / self.frame.width() as f64,
+            -self.bounds.size(1) / self.frame.height() as f64,
+        ]
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Information about a plot that is kept from one frame to the next.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "persistence", serde(default))]
+pub(crate) struct State {
+    /// `None` while the view follows the data.
+    /// Set once the user pans or zooms, and reset by double-clicking.
+    bounds: Option<Bounds>,
+
+    /// Names of the series the user has hidden by clicking the legend.
+    hidden_series: HashSet<String>,
+}
+
+// ----------------------------------------------------------------------------
+
+/// A plot of one or more [`Series`].
+///
+/// Drag to pan, scroll (or pinch) to zoom, and double-click to fit the data again.
+/// Hovering shows the nearest value, and clicking a name in the legend hides or shows that series.
+///
+/// The view and the hidden series are stored in [`Memory`], so give each plot a unique id source.
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+#[derive(Clone, Debug)]
+pub struct Plot {
+    id_source: Id,
+    series: Vec<Series>,
+    width: Option<f32>,
+    height: Option<f32>,
+    include: Bounds,
+    show_legend: bool,
+    allow_drag: bool,
+    allow_zoom: bool,
+}
+
+impl Plot {
+    pub fn new(id_source: impl Hash) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            series: vec![],
+            width: None,
+            height: None,
+            include: Bounds::NOTHING,
+            show_legend: true,
+            allow_drag: true,
+            allow_zoom: true,
+        }
+    }
+
+    /// Add a series to the plot.
+    pub fn series(mut self, series: Series) -> Self {
+        self.series.push(series);
+        self
+    }
+
+    /// Default: all the available width.
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Default: half the width.
+    pub fn height(mut self, height: f32) -> Self {
+        self.height = Some(height);
+        self
+    }
+
+    /// Always include this x value when fitting the view to the data.
+    pub fn include_x(mut self, x: impl Into<f64>) -> Self {
+        self.include.extend_with_x(x.into());
+        self
+    }
+
+    /// Always include this y value when fitting the view to the data.
+    pub fn include_y(mut self, y: impl Into<f64>) -> Self {
+        self.include.extend_with_y(y.into());
+        self
+    }
+
+    /// Show the names of the series in the top left corner. Default: `true`.
+    pub fn show_legend(mut self, show_legend: bool) -> Self {
+        self.show_legend = show_legend;
+        self
+    }
+
+    /// Pan by dragging. Default: `true`.
+    pub fn allow_drag(mut self, allow_drag: bool) -> Self {
+        self.allow_drag = allow_drag;
+        self
+    }
+
+    /// Zoom by scrolling or pinching. Default: `true`.
+    pub fn allow_zoom(mut self, allow_zoom: bool) -> Self {
+        self.allow_zoom = allow_zoom;
+        self
+    }
+
+    /// The bounds that fit all the visible series.
+    fn data_bounds(&self, state: &State) -> Bounds {
+        let mut bounds = self.include;
+        for series in &self.series {
+            if state.hidden_series.contains(&series.name) {
+                continue;
+            }
+            let half_bar_width = if series.kind == SeriesKind::Bars {
+                bounds.extend_with_y(0.0);
+                0.5 * series.auto_bar_width()
+            } else {
+                0.0
+            };
+            for value in &series.values {
+                bounds.extend_with_x(value.x - half_bar_width);
+                bounds.extend_with_x(value.x + half_bar_width);
+                bounds.extend_with_y(value.y);
+            }
+        }
+        bounds.make_valid();
+        bounds.add_relative_margin(0.05);
+        bounds
+    }
+}
+
+const LEGEND_SWATCH_RADIUS: f32 = 4.0;
+
+/// How close (in points) the mouse needs to be to a value to show it.
+const HOVER_RADIUS: f32 = 16.0;
+
+impl Widget for Plot {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let plot_id = ui.make_persistent_id(self.id_source);
+        let mut state = ui.memory().plots.get(&plot_id).cloned().unwrap_or_default();
+
+        let width = self
+            .width
+            .unwrap_or_else(|| ui.available_size_before_wrap_finite().x)
+            .at_least(2.0 * HOVER_RADIUS);
+        let height = self
+            .height
+            .unwrap_or(0.5 * width)
+            .at_least(2.0 * HOVER_RADIUS);
+        let (_id, rect) = ui.allocate_space(vec2(width, height));
+
+        // The legend goes first, so that its clicks aren't taken by the plot:
+        let mut legend = vec![];
+        if self.show_legend {
+            let text_style = TextStyle::Body;
+            let font = &ui.fonts()[text_style];
+            let padding = ui.style().spacing.button_padding;
+            let mut pos = rect.min + vec2(4.0, 4.0);
+            for (i, series) in self.series.iter().enumerate() {
+                let galley = font.layout_single_line(series.name.clone());
+                let entry_size =
+                    vec2(2.0 * LEGEND_SWATCH_RADIUS, 0.0) + galley.size + 3.0 * padding;
+                let entry_rect = Rect::from_min_size(pos, entry_size);
+                pos.y += entry_size.y;
+
+                let response =
+                    ui.interact(entry_rect, plot_id.with("legend").with(i), Sense::click());
+                if response.clicked && !state.hidden_series.remove(&series.name) {
+                    state.hidden_series.insert(series.name.clone());
+                }
+                legend.push((response, galley));
+            }
+        }
+
+        let response = ui.interact(rect, plot_id, Sense::click_and_drag());
+
+        if response.double_clicked {
+            state.bounds = None;
+        }
+        let mut bounds = state.bounds.unwrap_or_else(|| self.data_bounds(&state));
+
+        if self.allow_drag && response.active && ui.input().mouse.delta != Vec2::zero() {
+            let delta = ui.input().mouse.delta;
+            let dvalue_dpos = ScreenTransform {
+                frame: rect,
+                bounds,
+            }
+            .dvalue_dpos();
+            bounds.translate([
+                -delta.x as f64 * dvalue_dpos[0],
+                -delta.y as f64 * dvalue_dpos[1],
+            ]);
+            state.bounds = Some(bounds);
+        }
+
+        if self.allow_zoom && response.hovered {
+            if let Some(mouse_pos) = ui.input().mouse.pos {
+                let scroll_delta = ui.ctx().frame_state().scroll_delta;
+                // Shift+wheel ends up in `x` (see `FrameState::begin_frame`):
+                let scroll = scroll_delta.x + scroll_delta.y;
+                let zoom_factor = f64::from((scroll / 200.0).exp() * ui.input().zoom_delta());
+                if zoom_factor != 1.0 {
+                    ui.ctx().frame_state().scroll_delta = Vec2::zero(); // we used it
+                    let center = ScreenTransform {
+                        frame: rect,
+                        bounds,
+                    }
+                    .value_from_position(mouse_pos);
+                    bounds.zoom(zoom_factor, center);
+                    state.bounds = Some(bounds);
+                }
+            }
+        }
+
+        let transform = ScreenTransform {
+            frame: rect,
+            bounds,
+        };
+        let painter = ui.painter().sub_region(rect);
+        let visuals = &ui.style().visuals;
+        painter.rect(
+            rect,
+            2.0,
+            visuals.dark_bg_color,
+            visuals.widgets.noninteractive.bg_stroke,
+        );
+        paint_grid(ui, &painter, &transform);
+
+        let mut closest: Option<(f32, &Series, Value, Color32)> = None;
+        let mouse_pos = ui
+            .input()
+            .mouse
+            .pos
+            .filter(|_| response.hovered && !response.active);
+
+        for (i, series) in self.series.iter().enumerate() {
+            if state.hidden_series.contains(&series.name) {
+                continue;
+            }
+            let color = series.color.unwrap_or_else(|| auto_color(i));
+            let positions: Vec<Pos2> = series
+                .values
+                .iter()
+                .map(|value| transform.position_from_value(value))
+                .collect();
+
+            match series.kind {
+                SeriesKind::Line => {
+                    let width = series.width.unwrap_or(1.5);
+                    painter.add(Shape::line(positions.clone(), Stroke::new(width, color)));
+                }
+                SeriesKind::Scatter => {
+                    let radius = series.width.unwrap_or(2.5);
+                    for &pos in &positions {
+                        painter.circle_filled(pos, radius, color);
+                    }
+                }
+                SeriesKind::Bars => {
+                    let half_bar_width = 0.5 * series.auto_bar_width();
+                    let fill = Color32::from(Rgba::from(color) * 0.5);
+                    for value in &series.values {
+                        let bar = bar_rect(&transform, value, half_bar_width);
+                        painter.rect(bar, 0.0, fill, Stroke::new(1.0, color));
+                    }
+                }
+            }
+
+            if let Some(mouse_pos) = mouse_pos {
+                for (value, pos) in series.values.iter().zip(positions) {
+                    let mut distance = mouse_pos.distance(pos);
+                    if series.kind == SeriesKind::Bars {
+                        let half_bar_width = 0.5 * series.auto_bar_width();
+                        if bar_rect(&transform, value, half_bar_width).contains(mouse_pos) {
+                            distance = 0.0;
+                        }
+                    }
+                    let is_closer = match closest {
+                        Some((closest_distance, ..)) => distance < closest_distance,
+                        None => true,
+                    };
+                    if distance <= HOVER_RADIUS && is_closer {
+                        closest = Some((distance, series, *value, color));
+                    }
+                }
+            }
+        }
+
+        for ((response, galley), (i, series)) in
+            legend.into_iter().zip(self.series.iter().enumerate())
+        {
+            let visible = !state.hidden_series.contains(&series.name);
+            let color = series.color.unwrap_or_else(|| auto_color(i));
+            let visuals = ui.style().interact(&response);
+            let swatch_center = pos2(
+                response.rect.left() + ui.style().spacing.button_padding.x + LEGEND_SWATCH_RADIUS,
+                response.rect.center().y,
+            );
+            if visible {
+                painter.circle_filled(swatch_center, LEGEND_SWATCH_RADIUS, color);
+            } else {
+                painter.circle_stroke(swatch_center, LEGEND_SWATCH_RADIUS, (1.0, color));
+            }
+            let text_pos = pos2(
+                swatch_center.x + LEGEND_SWATCH_RADIUS + ui.style().spacing.button_padding.x,
+                response.rect.center().y - 0.5 * galley.size.y,
+            );
+            let text_color = if visible {
+                visuals.text_color()
+            } else {
+                ui.style().visuals.widgets.disabled.text_color()
+            };
+            painter.galley(text_pos, galley, TextStyle::Body, text_color);
+        }
+
+        ui.memory().plots.insert(plot_id, state);
+
+        if let Some((_, series, value, color)) = closest {
+            painter.circle_stroke(
+                transform.position_from_value(&value),
+                LEGEND_SWATCH_RADIUS,
+                (1.5, color),
+            );
+            let text = format!(
+                "{}\nx = {}\ny = {}",
+                series.name,
+                math::format_with_decimals_in_range(value.x, 0..=6),
+                math::format_with_decimals_in_range(value.y, 0..=6),
+            );
+            response.on_hover_text(text)
+        } else {
+            response
+        }
+    }
+}
+
+fn bar_rect(transform: &ScreenTransform, value: &Value, half_bar_width: f64) -> Rect {
+    let a = transform.position_from_value(&Value::new(value.x - half_bar_width, 0.0));
+    let b = transform.position_from_value(&Value::new(value.x + half_bar_width, value.y));
+    Rect::from_min_max(a.min(b), a.max(b))
+}
+
+/// A round step (1, 2 or 5 times a power of ten) so that `size` is covered by at most `max_steps`.
+///
+/// `None` if there is no such step, e.g. if `size` is zero or infinite.
+fn grid_step(size: f64, max_steps: f64) -> Option<f64> {
+    let min_step = size / max_steps.max(1.0);
+    let magnitude = 10_f64.powf(min_step.log10().floor());
+    let step = [1.0, 2.0, 5.0]
+        .iter()
+        .map(|multiple| multiple * magnitude)
+        .find(|&step| step >= min_step)
+        .unwrap_or(10.0 * magnitude);
+    if step.is_finite() && step > 0.0 {
+        Some(step)
+    } else {
+        None
+    }
+}
+
+/// Paint grid lines with tick labels along the bottom and left edges.
+fn paint_grid(ui: &Ui, painter: &Painter, transform: &ScreenTransform) {
+    const MIN_LINE_SPACING_IN_POINTS: f64 = 60.0;
+
+    let frame = transform.frame;
+    let bounds = transform.bounds;
+    let widgets = &ui.style().visuals.widgets;
+    let grid_stroke = Stroke::new(1.0, widgets.noninteractive.bg_stroke.color);
+    let axis_stroke = Stroke::new(1.0, widgets.inactive.bg_fill);
+    let text_color = widgets.noninteractive.text_color();
+
+    for axis in 0..2 {
+        let size_in_points = if axis == 0 {
+            frame.width()
+        } else {
+            frame.height()
+        } as f64;
+        let step = match grid_step(
+            bounds.size(axis),
+            size_in_points / MIN_LINE_SPACING_IN_POINTS,
+        ) {
+            Some(step) => step,
+            None => continue, // No room for grid lines
+        };
+        let decimals = (-step.log10().floor()).max(0.0) as usize;
+
+        let first = (bounds.min[axis] / step).ceil() as i64;
+        let last = (bounds.max[axis] / step).floor() as i64;
+        for i in first..=last {
+            let value = i as f64 * step;
+            let stroke = if i == 0 { axis_stroke } else { grid_stroke };
+            let text = math::format_with_decimals_in_range(value, decimals..=decimals);
+            if axis == 0 {
+                let x = transform.position_from_value(&Value::new(value, 0.0)).x;
+                painter.line_segment([pos2(x, frame.top()), pos2(x, frame.bottom())], stroke);
+                painter.text(
+                    pos2(x + 2.0, frame.bottom() - 2.0),
+                    Align2::LEFT_BOTTOM,
+                    text,
+                    TextStyle::Small,
+                    text_color,
+                );
+            } else {
+                let y = transform.position_from_value(&Value::new(0.0, value)).y;
+                painter.line_segment([pos2(frame.left(), y), pos2(frame.right(), y)], stroke);
+                painter.text(
+                    pos2(frame.left() + 2.0, y - 2.0),
+                    Align2::LEFT_BOTTOM,
+                    text,
+                    TextStyle::Small,
+                    text_color,
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_grid_step() {
+    let almost_equal = |a: Option<f64>, b: f64| (a.unwrap() - b).abs() < 1e-9 * b;
+    assert!(almost_equal(grid_step(10.0, 10.0), 1.0));
+    assert!(almost_equal(grid_step(10.0, 4.0), 5.0));
+    assert!(almost_equal(grid_step(1.0, 8.0), 0.2));
+    assert!(almost_equal(grid_step(300.0, 2.0), 200.0));
+    assert!(almost_equal(grid_step(0.03, 3.0), 0.01));
+}
+
+#[test]
+fn test_degenerate_bounds() {
+    assert_eq!(grid_step(0.0, 4.0), None);
+    assert_eq!(grid_step(f64::INFINITY, 4.0), None);
+    assert_eq!(grid_step(f64::NAN, 4.0), None);
+
+    // Zooming in stops before the ends of an axis become the same number:
+    let mut bounds = Bounds {
+        min: [1000.0, -1.0],
+        max: [1001.0, 1.0],
+    };
+    for _ in 0..100 {
+        bounds.zoom(10.0, Value::new(1000.5, 0.0));
+        for axis in 0..2 {
+            assert!(bounds.min[axis] < bounds.max[axis], "{:?}", bounds);
+            assert!(grid_step(bounds.size(axis), 4.0).is_some(), "{:?}", bounds);
+        }
+    }
+    assert!(bounds.size(0) >= 1e-6 * 0.999, "{:?}", bounds);
+
+    // Zooming out further than `f64` goes:
+    for _ in 0..100 {
+        bounds.zoom(1e-100, Value::new(0.0, 0.0));
+        for axis in 0..2 {
+            assert!(bounds.size(axis).is_finite() && bounds.size(axis) > 0.0);
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::*;
+use crate::{widgets::paint_focus_ring, *};
 
 /// One out of several alternatives, either selected or not.
 /// Will mark selected items with a different background color
@@ -34,6 +34,7 @@ impl Widget for SelectableLabel {
         let mut desired_size = total_extra + galley.size;
         desired_size.y = desired_size.y.at_least(ui.style().spacing.interact_size.y);
         let (rect, response) = ui.allocate_at_least(desired_size, Sense::click());
+        let response = response.kb_focusable();
 
         let text_cursor = ui
             .layout()
@@ -57,6 +58,7 @@ impl Widget for SelectableLabel {
             let corner_radius = 2.0;
             ui.painter().rect(rect, corner_radius, fill, stroke);
         }
+        paint_focus_ring(ui, &response);
 
         let text_color = ui
             .style()
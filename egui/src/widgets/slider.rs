@@ -2,7 +2,11 @@
 
 use std::ops::RangeInclusive;
 
-use crate::{widgets::Label, *};
+use crate::{
+    accessibility::{AccessNode, AccessRole},
+    widgets::Label,
+    *,
+};
 
 // ----------------------------------------------------------------------------
 
@@ -267,6 +271,12 @@ impl<'a> Slider<'a> {
             }
         }
 
+        ui.register_access_node(|| {
+            let label = self.text.clone().unwrap_or_default();
+            let value = self.format_value(ui.input().aim_radius(), x_range.clone());
+            AccessNode::new(AccessRole::Slider, response, label).value(value)
+        });
+
         // Paint it:
         {
             let value = self.get_value();
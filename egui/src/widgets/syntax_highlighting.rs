@@ -0,0 +1,207 @@
+//! A simple syntax highlighter for Rust-like code, for use with [`crate::TextEdit::highlighter`].
+//!
+//! ```
+//! # let ui = &mut egui::Ui::__test();
+//! use egui::widgets::syntax_highlighting::{highlight, CodeTheme};
+//! let mut code = "fn main() {\n    println!(\"Hello world!\"); // Greet\n}".to_owned();
+//! let theme = CodeTheme::default();
+//! let highlighter = |code: &str| highlight(&theme, code);
+//! ui.add(egui::TextEdit::multiline(&mut code).highlighter(&highlighter));
+//! ```
+
+use crate::*;
+
+/// What kind of code a piece of text is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenType {
+    Comment,
+    Keyword,
+    /// Numbers, `true`, `false`, and char literals.
+    Literal,
+    StringLiteral,
+    Punctuation,
+    /// Identifiers and whitespace.
+    Plain,
+}
+
+/// The colors used by [`highlight`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+pub struct CodeTheme {
+    pub text_style: TextStyle,
+    pub comment: Color32,
+    pub keyword: Color32,
+    pub literal: Color32,
+    pub string: Color32,
+    pub punctuation: Color32,
+    pub plain: Color32,
+}
+
+impl Default for CodeTheme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl CodeTheme {
+    /// For dark backgrounds.
+    pub fn dark() -> Self {
+        Self {
+            text_style: TextStyle::Monospace,
+            comment: Color32::from_gray(120),
+            keyword: Color32::from_rgb(255, 100, 100),
+            literal: Color32::from_rgb(178, 108, 210),
+            string: Color32::from_rgb(109, 200, 109),
+            punctuation: Color32::LIGHT_GRAY,
+            plain: Color32::from_rgb(200, 200, 200),
+        }
+    }
+
+    /// For light backgrounds.
+    pub fn light() -> Self {
+        Self {
+            text_style: TextStyle::Monospace,
+            comment: Color32::GRAY,
+            keyword: Color32::from_rgb(235, 0, 0),
+            literal: Color32::from_rgb(153, 0, 153),
+            string: Color32::from_rgb(37, 130, 37),
+            punctuation: Color32::from_gray(60),
+            plain: Color32::from_gray(40),
+        }
+    }
+
+    pub fn color(&self, token_type: TokenType) -> Color32 {
+        match token_type {
+            TokenType::Comment => self.comment,
+            TokenType::Keyword => self.keyword,
+            TokenType::Literal => self.literal,
+            TokenType::StringLiteral => self.string,
+            TokenType::Punctuation => self.punctuation,
+            TokenType::Plain => self.plain,
+        }
+    }
+
+    pub fn format(&self, token_type: TokenType) -> TextFormat {
+        TextFormat {
+            style: self.text_style,
+            color: Some(self.color(token_type)),
+            italics: token_type == TokenType::Comment,
+            ..Default::default()
+        }
+    }
+}
+
+/// Highlight Rust-like code (Rust, C, C++, JavaScript, …).
+///
+/// The returned [`LayoutJob`] has the same text as `code`.
+pub fn highlight(theme: &CodeTheme, code: &str) -> LayoutJob {
+    let mut job = LayoutJob::default();
+    for (token_type, token) in tokenize(code) {
+        job.append(token, theme.format(token_type));
+    }
+    job
+}
+
+/// Split code into tokens. Concatenating the tokens gives back the original code.
+pub fn tokenize(mut code: &str) -> Vec<(TokenType, &str)> {
+    let mut tokens = vec![];
+    while !code.is_empty() {
+        let (token_type, len) = next_token(code);
+        tokens.push((token_type, &code[..len]));
+        code = &code[len..];
+    }
+    tokens
+}
+
+/// The type and length in bytes of the first token in the (non-empty) `code`.
+fn next_token(code: &str) -> (TokenType, usize) {
+    let mut chars = code.char_indices();
+    let (_, first) = chars.next().unwrap();
+    let end_of = |pred: &dyn Fn(char) -> bool| {
+        code.char_indices()
+            .skip(1)
+            .find(|&(_, c)| !pred(c))
+            .map_or(code.len(), |(i, _)| i)
+    };
+
+    if code.starts_with("//") {
+        (TokenType::Comment, code.find('\n').unwrap_or(code.len()))
+    } else if let Some(rest) = code.strip_prefix("/*") {
+        let len = rest.find("*/").map_or(code.len(), |end| 2 + end + 2);
+        (TokenType::Comment, len)
+    } else if first == '"' {
+        let mut escaped = false;
+        for (i, c) in chars {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                return (TokenType::StringLiteral, i + 1);
+            }
+        }
+        (TokenType::StringLiteral, code.len())
+    } else if first == '\'' {
+        char_literal_len(code).map_or((TokenType::Punctuation, 1), |len| (TokenType::Literal, len))
+    } else if first.is_ascii_digit() {
+        (
+            TokenType::Literal,
+            end_of(&|c| c.is_alphanumeric() || c == '_' || c == '.'),
+        )
+    } else if first.is_alphabetic() || first == '_' {
+        let len = end_of(&|c| c.is_alphanumeric() || c == '_');
+        let word = &code[..len];
+        if word == "true" || word == "false" {
+            (TokenType::Literal, len)
+        } else if is_keyword(word) {
+            (TokenType::Keyword, len)
+        } else {
+            (TokenType::Plain, len)
+        }
+    } else if first.is_whitespace() {
+        (TokenType::Plain, end_of(&char::is_whitespace))
+    } else {
+        (TokenType::Punctuation, first.len_utf8())
+    }
+}
+
+/// Length of a char literal like `'a'` or `'\n'` at the start of `code`,
+/// or `None` if it is something else, like a lifetime (`'a`).
+fn char_literal_len(code: &str) -> Option<usize> {
+    let mut chars = code.char_indices().skip(1);
+    let (_, c) = chars.next()?;
+    if c == '\\' {
+        chars.next()?;
+        chars.find(|&(_, c)| c == '\'' || c == '\n')
+    } else {
+        chars.next()
+    }
+    .filter(|&(_, c)| c == '\'')
+    .map(|(i, _)| i + 1)
+}
+
+fn is_keyword(word: &str) -> bool {
+    const KEYWORDS: &[&str] = &[
+        "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+        "extern", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut",
+        "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "type",
+        "unsafe", "use", "where", "while",
+    ];
+    KEYWORDS.contains(&word)
+}
+
+#[test]
+fn test_tokenize() {
+    let code = "fn foo<'a>(x: &'a str) -> char {\n    let s = \"a \\\" b\"; // comment\n    /* block */ '\\n'\n}";
+    let tokens = tokenize(code);
+    assert_eq!(tokens.iter().map(|(_, t)| *t).collect::<String>(), code);
+
+    let find = |text: &str| tokens.iter().find(|(_, t)| *t == text).unwrap().0;
+    assert_eq!(find("fn"), TokenType::Keyword);
+    assert_eq!(find("foo"), TokenType::Plain);
+    assert_eq!(find("'"), TokenType::Punctuation); // lifetime
+    assert_eq!(find("\"a \\\" b\""), TokenType::StringLiteral);
+    assert_eq!(find("// comment"), TokenType::Comment);
+    assert_eq!(find("/* block */"), TokenType::Comment);
+    assert_eq!(find("'\\n'"), TokenType::Literal);
+}
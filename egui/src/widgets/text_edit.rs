@@ -1,4 +1,5 @@
 use crate::{
+    accessibility::{AccessNode, AccessRole},
     paint::{text::cursor::*, *},
     util::undoer::Undoer,
     *,
@@ -12,6 +13,10 @@ pub(crate) struct State {
 
     #[cfg_attr(feature = "persistence", serde(skip))]
     undoer: Undoer<(CCursorPair, String)>,
+
+    /// Text that is being composed with an IME. It is shown at the cursor, but is not yet part of the text.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    preedit: String,
 }
 
 #[derive(Clone, Copy, Debug, Default)]
@@ -120,13 +125,13 @@ impl CCursorPair {
 /// }
 /// ```
 #[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
-#[derive(Debug)]
 pub struct TextEdit<'t> {
     text: &'t mut String,
     id: Option<Id>,
     id_source: Option<Id>,
     text_style: Option<TextStyle>,
     text_color: Option<Color32>,
+    highlighter: Option<&'t dyn Fn(&str) -> LayoutJob>,
     frame: bool,
     multiline: bool,
     enabled: bool,
@@ -148,6 +153,7 @@ impl<'t> TextEdit<'t> {
             id_source: None,
             text_style: None,
             text_color: None,
+            highlighter: None,
             frame: true,
             multiline: false,
             enabled: true,
@@ -165,6 +171,7 @@ impl<'t> TextEdit<'t> {
             text_style: None,
             frame: true,
             text_color: None,
+            highlighter: None,
             multiline: true,
             enabled: true,
             desired_width: None,
@@ -198,6 +205,36 @@ impl<'t> TextEdit<'t> {
         self
     }
 
+    /// Format the text with several styles, e.g. for syntax highlighting.
+    ///
+    /// The highlighter is called with the text each time it needs to be laid out,
+    /// and must return a [`LayoutJob`] with that same text.
+    /// If it returns some other text, the highlighting is ignored.
+    /// Sections without a color get the color of the `TextEdit`.
+    /// See [`crate::widgets::syntax_highlighting`] for a highlighter for Rust-like code.
+    ///
+    /// ```
+    /// # let ui = &mut egui::Ui::__test();
+    /// # let mut my_code = String::new();
+    /// let highlighter = |text: &str| {
+    ///     // Show all digits in red:
+    ///     let mut job = egui::LayoutJob::default();
+    ///     for c in text.chars() {
+    ///         let mut format = egui::TextFormat::style(egui::TextStyle::Monospace);
+    ///         if c.is_ascii_digit() {
+    ///             format.color = Some(egui::Color32::RED);
+    ///         }
+    ///         job.append(c.encode_utf8(&mut [0; 4]), format);
+    ///     }
+    ///     job
+    /// };
+    /// ui.add(egui::TextEdit::multiline(&mut my_code).highlighter(&highlighter));
+    /// ```
+    pub fn highlighter(mut self, highlighter: &'t dyn Fn(&str) -> LayoutJob) -> Self {
+        self.highlighter = Some(highlighter);
+        self
+    }
+
     /// Default is `true`. If set to `false` then you cannot edit the text.
     pub fn enabled(mut self, enabled: bool) -> Self {
         self.enabled = enabled;
@@ -225,6 +262,24 @@ impl<'t> TextEdit<'t> {
     }
 }
 
+impl<'t> std::fmt::Debug for TextEdit<'t> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TextEdit")
+            .field("text", &self.text)
+            .field("id", &self.id)
+            .field("id_source", &self.id_source)
+            .field("text_style", &self.text_style)
+            .field("text_color", &self.text_color)
+            .field("highlighter", &self.highlighter.is_some())
+            .field("frame", &self.frame)
+            .field("multiline", &self.multiline)
+            .field("enabled", &self.enabled)
+            .field("desired_width", &self.desired_width)
+            .field("desired_height_rows", &self.desired_height_rows)
+            .finish()
+    }
+}
+
 impl<'t> Widget for TextEdit<'t> {
     fn ui(self, ui: &mut Ui) -> Response {
         let frame = self.frame;
@@ -272,6 +327,7 @@ impl<'t> TextEdit<'t> {
             id_source,
             text_style,
             text_color,
+            highlighter,
             frame: _,
             multiline,
             enabled,
@@ -280,14 +336,28 @@ impl<'t> TextEdit<'t> {
         } = self;
 
         let text_style = text_style.unwrap_or_else(|| ui.style().body_text_style);
-        let font = &ui.fonts()[text_style];
-        let line_spacing = font.row_height();
+        let line_spacing = ui.fonts()[text_style].row_height();
         let available_width = ui.available_width();
-        let mut galley = if multiline {
-            font.layout_multiline(text.clone(), available_width)
-        } else {
-            font.layout_single_line(text.clone())
+        let layout = |ui: &Ui, text: String| {
+            let max_width = if multiline {
+                available_width
+            } else {
+                f32::INFINITY
+            };
+            if let Some(highlighter) = highlighter {
+                let mut job = highlighter(&text);
+                if job.text != text {
+                    // The sections don't fit the text, so ignore them:
+                    job = LayoutJob::single_section(text, TextFormat::style(text_style));
+                }
+                ui.fonts().layout_job(job, max_width)
+            } else if multiline {
+                ui.fonts()[text_style].layout_multiline(text, max_width)
+            } else {
+                ui.fonts()[text_style].layout_single_line(text)
+            }
         };
+        let mut galley = layout(ui, text.clone());
 
         let desired_width = desired_width.unwrap_or_else(|| ui.style().spacing.text_edit_width);
         let desired_height = (desired_height_rows.at_least(1) as f32) * line_spacing;
@@ -356,7 +426,7 @@ impl<'t> TextEdit<'t> {
             }
         }
 
-        if ui.input().mouse.pressed && !response.hovered {
+        if ui.input().mouse.any_pressed() && !response.hovered {
             // User clicked somewhere else
             ui.memory().surrender_kb_focus(id);
         }
@@ -393,7 +463,7 @@ impl<'t> TextEdit<'t> {
                 .undoer
                 .feed_state(ui.input().time, &(cursorp.as_ccursorp(), text.clone()));
 
-            for event in &ui.input().events {
+            for event in ui.input().events() {
                 let did_mutate_text = match event {
                     Event::Copy => {
                         if cursorp.is_empty() {
@@ -425,6 +495,22 @@ impl<'t> TextEdit<'t> {
                             None
                         }
                     }
+                    Event::Paste(text_to_insert) => {
+                        if !text_to_insert.is_empty() {
+                            let mut ccursor = delete_selected(text, &cursorp);
+                            if multiline {
+                                insert_text(&mut ccursor, text, text_to_insert);
+                            } else {
+                                // No newlines in a single line:
+                                let single_line =
+                                    text_to_insert.lines().collect::<Vec<_>>().join(" ");
+                                insert_text(&mut ccursor, text, &single_line);
+                            }
+                            Some(CCursorPair::one(ccursor))
+                        } else {
+                            None
+                        }
+                    }
                     Event::Key {
                         key: Key::Enter,
                         pressed: true,
@@ -472,16 +558,37 @@ impl<'t> TextEdit<'t> {
                     } => on_key_press(&mut cursorp, text, &galley, *key, modifiers),
 
                     Event::Key { .. } => None,
+
+                    Event::CompositionStart => {
+                        state.preedit.clear();
+                        // What the user composes will replace the selection:
+                        if cursorp.is_empty() {
+                            None
+                        } else {
+                            Some(CCursorPair::one(delete_selected(text, &cursorp)))
+                        }
+                    }
+                    Event::CompositionUpdate(preedit) => {
+                        state.preedit = preedit.clone();
+                        None
+                    }
+                    Event::CompositionEnd(text_to_insert) => {
+                        state.preedit.clear();
+                        if text_to_insert.is_empty() {
+                            None
+                        } else {
+                            let mut ccursor = delete_selected(text, &cursorp);
+                            insert_text(&mut ccursor, text, text_to_insert);
+                            Some(CCursorPair::one(ccursor))
+                        }
+                    }
+
+                    Event::Touch { .. } => None,
                 };
 
                 if let Some(new_ccursorp) = did_mutate_text {
                     // Layout again to avoid frame delay, and to keep `text` and `galley` in sync.
-                    let font = &ui.fonts()[text_style];
-                    galley = if multiline {
-                        font.layout_multiline(text.clone(), available_width)
-                    } else {
-                        font.layout_single_line(text.clone())
-                    };
+                    galley = layout(ui, text.clone());
 
                     // Set cursorp using new galley:
                     cursorp = CursorPair {
@@ -499,9 +606,27 @@ impl<'t> TextEdit<'t> {
 
         if ui.memory().has_kb_focus(id) {
             if let Some(cursorp) = state.cursorp {
-                paint_cursor_selection(ui, response.rect.min, &galley, &cursorp);
-                paint_cursor_end(ui, response.rect.min, &galley, &cursorp.primary);
+                let cursor_rect = if state.preedit.is_empty() {
+                    paint_cursor_selection(ui, response.rect.min, &galley, &cursorp);
+                    paint_cursor_end(ui, response.rect.min, &galley, &cursorp.primary)
+                } else {
+                    // Show the text being composed underlined at the cursor:
+                    let mut text_with_preedit = text.clone();
+                    let preedit_start = cursorp.primary.ccursor;
+                    let mut preedit_end = preedit_start;
+                    insert_text(&mut preedit_end, &mut text_with_preedit, &state.preedit);
+
+                    galley = layout(ui, text_with_preedit);
+
+                    let preedit_start = galley.from_ccursor(preedit_start);
+                    let preedit_end = galley.from_ccursor(preedit_end);
+                    paint_underline(ui, response.rect.min, &galley, [preedit_start, preedit_end]);
+                    paint_cursor_end(ui, response.rect.min, &galley, &preedit_end)
+                };
+                ui.output().text_cursor_rect = Some(cursor_rect);
             }
+        } else {
+            state.preedit.clear();
         }
 
         let text_color = text_color
@@ -513,10 +638,14 @@ impl<'t> TextEdit<'t> {
 
         ui.memory().text_edit.insert(id, state);
 
-        Response {
+        let response = Response {
             lost_kb_focus: ui.memory().lost_kb_focus(id), // we may have lost it during the course of this function
             ..response
-        }
+        };
+        ui.register_access_node(|| {
+            AccessNode::new(AccessRole::TextEdit, &response, "").value(text.as_str())
+        });
+        response
     }
 }
 
@@ -553,7 +682,32 @@ fn paint_cursor_selection(ui: &mut Ui, pos: Pos2, galley: &Galley, cursorp: &Cur
     }
 }
 
-fn paint_cursor_end(ui: &mut Ui, pos: Pos2, galley: &Galley, cursor: &Cursor) {
+/// Underline the text between `[min, max]`.
+fn paint_underline(ui: &mut Ui, pos: Pos2, galley: &Galley, [min, max]: [Cursor; 2]) {
+    let stroke = Stroke::new(1.0, ui.style().visuals.widgets.inactive.text_color());
+    let min = min.rcursor;
+    let max = max.rcursor;
+
+    for ri in min.row..=max.row {
+        let row = &galley.rows[ri];
+        let left = if ri == min.row {
+            row.x_offset(min.column)
+        } else {
+            row.min_x()
+        };
+        let right = if ri == max.row {
+            row.x_offset(max.column)
+        } else {
+            row.max_x()
+        };
+        let y = row.y_max - 1.0;
+        ui.painter()
+            .line_segment([pos + vec2(left, y), pos + vec2(right, y)], stroke);
+    }
+}
+
+/// Returns the screen-space rectangle of the cursor.
+fn paint_cursor_end(ui: &mut Ui, pos: Pos2, galley: &Galley, cursor: &Cursor) -> Rect {
     let stroke = ui.style().visuals.selection.stroke;
 
     let cursor_pos = galley.pos_from_cursor(cursor).translate(pos.to_vec2());
@@ -580,6 +734,8 @@ fn paint_cursor_end(ui: &mut Ui, pos: Pos2, galley: &Galley, cursor: &Cursor) {
             (width, stroke.color),
         );
     }
+
+    cursor_pos
 }
 
 // ----------------------------------------------------------------------------
@@ -915,3 +1071,69 @@ fn next_word_boundary_char_index(it: impl Iterator<Item = char>, mut index: usiz
 fn is_word_char(c: char) -> bool {
     c.is_ascii_alphanumeric() || c == '_'
 }
+
+#[test]
+fn test_paste() {
+    use crate::{CtxRef, Event, RawInput};
+
+    let paste = || RawInput::default().with_events(vec![Event::Paste("two\nlines".to_owned())]);
+    for &(multiline, expected) in &[(false, "two lines"), (true, "two\nlines")] {
+        let mut text = String::new();
+        let mut text_edit = |ctx: &CtxRef| {
+            let id = Id::new("text_edit");
+            crate::CentralPanel::default().show(ctx, |ui| {
+                ui.memory().request_kb_focus(id);
+                let text_edit = if multiline {
+                    TextEdit::multiline(&mut text)
+                } else {
+                    TextEdit::singleline(&mut text)
+                };
+                ui.add(text_edit.id(id));
+            });
+        };
+        let mut ctx = CtxRef::default();
+        ctx.run_test_frame(RawInput::default(), &mut text_edit);
+        ctx.run_test_frame(paste(), &mut text_edit);
+        assert_eq!(text, expected);
+    }
+}
+
+#[test]
+fn test_composition() {
+    use crate::{CtxRef, Event, RawInput};
+
+    let id = Id::new("text_edit");
+    let mut text = String::new();
+    // Returns the text after the frame:
+    let mut text_edit = |ctx: &CtxRef| {
+        crate::CentralPanel::default().show(ctx, |ui| {
+            ui.memory().request_kb_focus(id);
+            ui.add(TextEdit::singleline(&mut text).id(id));
+        });
+        text.clone()
+    };
+    let event = |event| RawInput::default().with_events(vec![event]);
+    let preedit = |ctx: &CtxRef| ctx.memory().text_edit.get(&id).unwrap().preedit.clone();
+
+    let mut ctx = CtxRef::default();
+    ctx.run_test_frame(RawInput::default(), &mut text_edit);
+    let mut committed = String::new();
+    for word in &["日本", "語"] {
+        ctx.run_test_frame(event(Event::CompositionStart), &mut text_edit);
+        for i in 1..=word.chars().count() {
+            let composed: String = word.chars().take(i).collect();
+            let update = event(Event::CompositionUpdate(composed.clone()));
+            let text = ctx.run_test_frame(update, &mut text_edit);
+            assert_eq!(text, committed, "The text being composed is only shown");
+            assert_eq!(preedit(&ctx), composed);
+        }
+        let end = event(Event::CompositionEnd(word.to_string()));
+        committed += word;
+        assert_eq!(ctx.run_test_frame(end, &mut text_edit), committed);
+        assert_eq!(preedit(&ctx), "");
+    }
+    assert_eq!(
+        ctx.run_test_frame(RawInput::default(), &mut text_edit),
+        "日本語"
+    );
+}
@@ -0,0 +1,65 @@
+use egui::{widgets::syntax_highlighting, *};
+
+/// Shows off [`TextEdit::highlighter`].
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "persistence", serde(default))]
+pub struct CodeEditor {
+    code: String,
+    dark_theme: bool,
+}
+
+impl Default for CodeEditor {
+    fn default() -> Self {
+        Self {
+            code: "// A very simple example\n\
+fn main() {\n\
+\tprintln!(\"Hello world!\");\n\
+}\n\
+"
+            .into(),
+            dark_theme: true,
+        }
+    }
+}
+
+impl super::Demo for CodeEditor {
+    fn name(&self) -> &str {
+        "🖮 Code Editor"
+    }
+
+    fn show(&mut self, ctx: &CtxRef, open: &mut bool) {
+        use super::View;
+        Window::new(self.name())
+            .open(open)
+            .default_height(500.0)
+            .show(ctx, |ui| self.ui(ui));
+    }
+}
+
+impl super::View for CodeEditor {
+    fn ui(&mut self, ui: &mut Ui) {
+        let Self { code, dark_theme } = self;
+
+        ui.horizontal(|ui| {
+            ui.radio_value(dark_theme, true, "Dark theme");
+            ui.radio_value(dark_theme, false, "Light theme");
+        });
+
+        let theme = if *dark_theme {
+            syntax_highlighting::CodeTheme::dark()
+        } else {
+            syntax_highlighting::CodeTheme::light()
+        };
+        let highlighter = |code: &str| syntax_highlighting::highlight(&theme, code);
+
+        ScrollArea::auto_sized().show(ui, |ui| {
+            ui.add(
+                TextEdit::multiline(code)
+                    .text_style(TextStyle::Monospace)
+                    .desired_rows(10)
+                    .desired_width(f32::INFINITY)
+                    .highlighter(&highlighter),
+            );
+        });
+    }
+}
@@ -17,7 +17,12 @@ impl Default for Demos {
             Box::new(super::FontBook::default()),
             Box::new(super::Painting::default()),
             Box::new(super::DancingStrings::default()),
+            Box::new(super::PlotDemo::default()),
+            Box::new(super::TableDemo::default()),
+            Box::new(super::CodeEditor::default()),
+            Box::new(super::MarkdownDemo::default()),
             Box::new(super::DragAndDropDemo::default()),
+            Box::new(super::MultiTouch::default()),
             Box::new(super::Tests::default()),
             Box::new(super::WindowOptions::default()),
         ];
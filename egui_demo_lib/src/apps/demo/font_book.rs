@@ -28,7 +28,13 @@ impl FontBook {
 
                 let tooltip_ui = |ui: &mut egui::Ui| {
                     ui.add(Label::new(chr).text_style(self.text_style));
-                    ui.label(format!("{}\nU+{:X}\n\nClick to copy", name, chr as u32));
+                    let font_name = ui.fonts()[self.text_style]
+                        .font_name_for_char(chr)
+                        .unwrap_or_else(|| "(missing)".to_owned());
+                    ui.label(format!(
+                        "{}\nU+{:X}\nFont: {}\n\nClick to copy",
+                        name, chr as u32, font_name
+                    ));
                 };
 
                 if ui.add(button).on_hover_ui(tooltip_ui).clicked {
@@ -66,7 +72,14 @@ impl super::View for FontBook {
         ui.separator();
 
         egui::combo_box_with_label(ui, "Text style", format!("{:?}", self.text_style), |ui| {
-            for style in egui::TextStyle::all() {
+            let styles: Vec<egui::TextStyle> = ui
+                .fonts()
+                .definitions()
+                .family_and_size
+                .keys()
+                .copied()
+                .collect();
+            for style in styles {
                 ui.selectable_value(&mut self.text_style, style, format!("{:?}", style));
             }
         });
@@ -0,0 +1,67 @@
+use egui::{widgets::markdown::Markdown, *};
+
+/// Shows off [`Markdown`].
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "persistence", serde(default))]
+pub struct MarkdownDemo {
+    markdown: String,
+}
+
+impl Default for MarkdownDemo {
+    fn default() -> Self {
+        Self {
+            markdown: r#"# Markdown
+Egui can show *simple* **markdown**, e.g. for `release notes` or help pages.
+
+## Lists
+* Bullet lists
+* can be
+  1. numbered
+  2. and nested
+
+> Block quotes
+> span several lines.
+
+---
+
+```rust
+fn main() {
+    println!("Code blocks are highlighted"); // if they have a language
+}
+```
+
+See [the egui repository](https://github.com/emilk/egui) for more.
+"#
+            .to_owned(),
+        }
+    }
+}
+
+impl super::Demo for MarkdownDemo {
+    fn name(&self) -> &str {
+        "🗒 Markdown"
+    }
+
+    fn show(&mut self, ctx: &CtxRef, open: &mut bool) {
+        use super::View;
+        Window::new(self.name())
+            .open(open)
+            .default_size(vec2(600.0, 500.0))
+            .scroll(true)
+            .show(ctx, |ui| self.ui(ui));
+    }
+}
+
+impl super::View for MarkdownDemo {
+    fn ui(&mut self, ui: &mut Ui) {
+        ui.columns(2, |columns| {
+            columns[0].add(
+                TextEdit::multiline(&mut self.markdown)
+                    .text_style(TextStyle::Monospace)
+                    .desired_rows(20)
+                    .desired_width(f32::INFINITY),
+            );
+            columns[1].add(Markdown::new(&self.markdown));
+        });
+    }
+}
@@ -5,6 +5,7 @@
 // ----------------------------------------------------------------------------
 
 mod app;
+mod code_editor;
 mod dancing_strings;
 pub mod demo_window;
 mod demo_windows;
@@ -12,9 +13,13 @@ mod drag_and_drop;
 mod font_book;
 pub mod font_contents_emoji;
 pub mod font_contents_ubuntu;
+mod markdown_demo;
+mod multi_touch;
 mod painting;
+mod plot_demo;
 mod scrolls;
 mod sliders;
+mod table_demo;
 mod tests;
 pub mod toggle_switch;
 mod widget_gallery;
@@ -22,9 +27,11 @@ mod widgets;
 mod window_options;
 
 pub use {
-    app::*, dancing_strings::DancingStrings, demo_window::DemoWindow, demo_windows::*,
-    drag_and_drop::*, font_book::FontBook, painting::Painting, scrolls::Scrolls, sliders::Sliders,
-    tests::Tests, widget_gallery::*, widgets::Widgets, window_options::WindowOptions,
+    app::*, code_editor::CodeEditor, dancing_strings::DancingStrings, demo_window::DemoWindow,
+    demo_windows::*, drag_and_drop::*, font_book::FontBook, markdown_demo::MarkdownDemo,
+    multi_touch::MultiTouch, painting::Painting, plot_demo::PlotDemo, scrolls::Scrolls,
+    sliders::Sliders, table_demo::TableDemo, tests::Tests, widget_gallery::*, widgets::Widgets,
+    window_options::WindowOptions,
 };
 
 // ----------------------------------------------------------------------------
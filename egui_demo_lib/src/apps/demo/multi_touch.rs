@@ -0,0 +1,86 @@
+use egui::{containers::*, emath::Rot2, *};
+
+/// Pinch, rotate and pan a shape with two or more fingers.
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "persistence", serde(default))]
+pub struct MultiTouch {
+    zoom: f32,
+    rotation: f32,
+    translation: Vec2,
+}
+
+impl Default for MultiTouch {
+    fn default() -> Self {
+        Self {
+            zoom: 1.0,
+            rotation: 0.0,
+            translation: Vec2::zero(),
+        }
+    }
+}
+
+impl super::Demo for MultiTouch {
+    fn name(&self) -> &str {
+        "👌 Multi Touch"
+    }
+
+    fn show(&mut self, ctx: &CtxRef, open: &mut bool) {
+        use super::View;
+        Window::new(self.name())
+            .open(open)
+            .default_size(vec2(512.0, 512.0))
+            .scroll(false)
+            .show(ctx, |ui| self.ui(ui));
+    }
+}
+
+impl super::View for MultiTouch {
+    fn ui(&mut self, ui: &mut Ui) {
+        ui.label("Use two or more fingers on a touch screen to zoom, rotate and move the square.");
+        if ui.button("Reset").clicked {
+            *self = Default::default();
+        }
+
+        Frame::dark_canvas(ui.style()).show(ui, |ui| {
+            let desired_size = ui.available_width() * vec2(1.0, 0.75);
+            let (rect, response) = ui.allocate_exact_size(desired_size, Sense::hover());
+
+            if let Some(multi_touch) = ui.input().multi_touch() {
+                if response.hovered {
+                    self.zoom *= multi_touch.zoom_delta;
+                    self.rotation += multi_touch.rotation_delta;
+                    self.translation += multi_touch.translation_delta;
+                }
+            }
+
+            let rotation = Rot2::from_angle(self.rotation);
+            let half_size = 0.25 * rect.height() * self.zoom;
+            let center = rect.center() + self.translation;
+            let corners = [
+                vec2(-1.0, -1.0),
+                vec2(1.0, -1.0),
+                vec2(1.0, 1.0),
+                vec2(-1.0, 1.0),
+            ];
+            let points = corners
+                .iter()
+                .map(|&corner| center + rotation * (half_size * corner))
+                .collect();
+
+            let painter = ui.painter().sub_region(rect);
+            painter.add(paint::Shape::closed_line(
+                points,
+                Stroke::new(2.0, ui.style().visuals.text_color()),
+            ));
+        });
+
+        ui.label(format!(
+            "zoom: {:.2}, rotation: {:.0}°",
+            self.zoom,
+            self.rotation.to_degrees()
+        ));
+        ui.vertical_centered(|ui| {
+            ui.add(crate::__egui_github_link_file!());
+        });
+    }
+}
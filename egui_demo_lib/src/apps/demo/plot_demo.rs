@@ -0,0 +1,82 @@
+use egui::plot::{Plot, Series, Value};
+use egui::*;
+
+/// Shows off the [`Plot`] widget.
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "persistence", serde(default))]
+pub struct PlotDemo {
+    animate: bool,
+    frequency: f64,
+    num_points: usize,
+}
+
+impl Default for PlotDemo {
+    fn default() -> Self {
+        Self {
+            animate: true,
+            frequency: 1.0,
+            num_points: 100,
+        }
+    }
+}
+
+impl super::Demo for PlotDemo {
+    fn name(&self) -> &str {
+        "📈 Plot"
+    }
+
+    fn show(&mut self, ctx: &CtxRef, open: &mut bool) {
+        use super::View;
+        Window::new(self.name())
+            .open(open)
+            .default_size(vec2(512.0, 512.0))
+            .scroll(false)
+            .show(ctx, |ui| self.ui(ui));
+    }
+}
+
+impl super::View for PlotDemo {
+    fn ui(&mut self, ui: &mut Ui) {
+        let Self {
+            animate,
+            frequency,
+            num_points,
+        } = self;
+
+        ui.label("Drag to pan, scroll to zoom and double-click to reset. Click the legend to hide a series.");
+        ui.checkbox(animate, "Animate");
+        ui.add(Slider::f64(frequency, 0.1..=4.0).text("Frequency"));
+        ui.add(Slider::usize(num_points, 2..=1000).text("Points"));
+
+        let time = if *animate {
+            ui.ctx().request_repaint();
+            ui.input().time
+        } else {
+            0.0
+        };
+        let (frequency, num_points) = (*frequency, *num_points);
+        let x = move |i: usize| i as f64 * std::f64::consts::TAU / (num_points - 1) as f64;
+
+        let sin = (0..num_points).map(|i| Value::new(x(i), (frequency * x(i) + time).sin()));
+        let cos = (0..num_points).map(|i| Value::new(x(i), (frequency * x(i) + time).cos()));
+        ui.add(
+            Plot::new("waves")
+                .series(Series::line("sin", sin))
+                .series(Series::scatter("cos", cos))
+                .include_y(-1.0)
+                .include_y(1.0)
+                .height(200.0),
+        );
+
+        let bars = (1..=6).map(|i| Value::new(i, (i * i) as f64 / 6.0));
+        ui.add(
+            Plot::new("bars")
+                .series(Series::bars("x²/6", bars))
+                .height(150.0),
+        );
+
+        ui.vertical_centered(|ui| {
+            ui.add(crate::__egui_github_link_file!());
+        });
+    }
+}
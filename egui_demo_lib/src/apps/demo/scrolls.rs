@@ -87,5 +87,36 @@ impl Scrolls {
                 );
             }
         });
+
+        ui.separator();
+
+        ui.label("Scroll horizontally with shift + mouse wheel:");
+        ScrollArea::both()
+            .max_height(200.0)
+            .id_source("both")
+            .show(ui, |ui| {
+                Grid::new("wide_grid").show(ui, |ui| {
+                    for row in 0..30 {
+                        for col in 0..30 {
+                            ui.label(format!("Cell {},{}", row, col));
+                        }
+                        ui.end_row();
+                    }
+                });
+            });
+
+        ui.separator();
+
+        ui.label("Only the visible rows of these 100 000 are laid out:");
+        let text_style = TextStyle::Body;
+        let row_height = ui.fonts()[text_style].row_height();
+        let num_rows = 100_000;
+        ScrollArea::from_max_height(200.0)
+            .id_source("many_rows")
+            .show_rows(ui, row_height, num_rows, |ui, row_range| {
+                for row in row_range {
+                    ui.label(format!("Row {}/{}", row + 1, num_rows));
+                }
+            });
     }
 }
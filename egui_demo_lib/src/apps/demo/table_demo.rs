@@ -0,0 +1,70 @@
+use egui::*;
+
+/// Shows off the [`Table`] widget.
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "persistence", serde(default))]
+pub struct TableDemo {
+    num_rows: usize,
+    striped: bool,
+}
+
+impl Default for TableDemo {
+    fn default() -> Self {
+        Self {
+            num_rows: 10_000,
+            striped: true,
+        }
+    }
+}
+
+impl super::Demo for TableDemo {
+    fn name(&self) -> &str {
+        "☰ Table"
+    }
+
+    fn show(&mut self, ctx: &CtxRef, open: &mut bool) {
+        use super::View;
+        Window::new(self.name())
+            .open(open)
+            .default_size(vec2(400.0, 400.0))
+            .scroll(false)
+            .show(ctx, |ui| self.ui(ui));
+    }
+}
+
+impl super::View for TableDemo {
+    fn ui(&mut self, ui: &mut Ui) {
+        let Self { num_rows, striped } = self;
+
+        ui.label("Drag the lines between the column headers to resize. Click a header to sort.");
+        ui.add(Slider::usize(num_rows, 0..=1_000_000).text("Rows"));
+        ui.checkbox(striped, "Striped");
+
+        let num_rows = *num_rows;
+        Table::new("demo_table")
+            .column("Row", 80.0)
+            .column("Square", 120.0)
+            .column("Parity", 80.0)
+            .sortable(true)
+            .striped(*striped)
+            .max_height(300.0)
+            .show(ui, |body| {
+                // The rows are generated in order, so sorting by any column just flips them:
+                let descending = matches!(body.sort(), Some((_, SortOrder::Descending)));
+                body.rows(num_rows, |row_index, row| {
+                    let n = if descending {
+                        num_rows - 1 - row_index
+                    } else {
+                        row_index
+                    };
+                    row.col(|ui| ui.label(n.to_string()));
+                    row.col(|ui| ui.label((n as u64 * n as u64).to_string()));
+                    row.col(|ui| ui.label(if n % 2 == 0 { "even" } else { "odd" }));
+                });
+            });
+
+        ui.vertical_centered(|ui| {
+            ui.add(crate::__egui_github_link_file!());
+        });
+    }
+}
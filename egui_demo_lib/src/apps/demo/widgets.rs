@@ -62,12 +62,14 @@ impl Widgets {
             ui.label(".");
 
             ui.label("The default font supports all latin and cyrillic characters (ИÅđ…), common math symbols (∫√∞²⅓…), and many emojis (💓🌟🖩…).")
-                .on_hover_text("There is currently no support for right-to-left languages.");
+                .on_hover_text("Right-to-left languages need the `shaping` feature, and a font that supports them.");
             ui.label("See the 🔤 Font Book for more!");
 
             ui.monospace("There is also a monospace font.");
         });
 
+        ui.label(rich_text_example());
+
         let tooltip_ui = |ui: &mut Ui| {
             ui.heading("The name of the tooltip");
             ui.horizontal(|ui| {
@@ -94,13 +96,25 @@ impl Widgets {
         ui.checkbox(&mut self.button_enabled, "Button enabled");
 
         ui.horizontal(|ui| {
-            if ui
+            let response = ui
                 .add(Button::new("Click me").enabled(self.button_enabled))
-                .on_hover_text("This will just increase a counter.")
-                .clicked
-            {
+                .on_hover_text("This will just increase a counter. Right-click for more options.");
+            if response.clicked {
                 self.count += 1;
             }
+            let count = &mut self.count;
+            response.context_menu(|ui| {
+                if ui.button("Reset").clicked {
+                    *count = 0;
+                }
+                egui::menu::sub_menu(ui, "Add", |ui| {
+                    for amount in &[10, 100, 1000] {
+                        if ui.button(format!("+{}", amount)).clicked {
+                            *count += amount;
+                        }
+                    }
+                });
+            });
             ui.label(format!("The button has been clicked {} times.", self.count));
         });
 
@@ -140,6 +154,17 @@ impl Widgets {
         ui.horizontal(|ui| {
             ui.colored_label(self.color, "Click to select a different text color: ");
             ui.color_edit_button_srgba(&mut self.color);
+            if ui
+                .button("📋 Copy swatch")
+                .on_hover_text("Copy a 32x32 image of this color")
+                .clicked
+            {
+                let srgba = Hsva::from(self.color).to_srgba_unmultiplied();
+                ui.output().copied_image = Some(ClipboardImage {
+                    size: [32, 32],
+                    rgba: srgba.repeat(32 * 32),
+                });
+            }
         });
 
         ui.separator();
@@ -159,3 +184,59 @@ impl Widgets {
         super::toggle_switch::demo(ui, &mut self.toggle_switch);
     }
 }
+
+fn rich_text_example() -> LayoutJob {
+    let mut job = LayoutJob::default();
+    job.append("A single label can mix ", TextFormat::default());
+    job.append(
+        "bold",
+        TextFormat {
+            bold: true,
+            ..Default::default()
+        },
+    );
+    job.append(", ", TextFormat::default());
+    job.append(
+        "italics",
+        TextFormat {
+            italics: true,
+            ..Default::default()
+        },
+    );
+    job.append(", ", TextFormat::default());
+    job.append(
+        "colors",
+        TextFormat {
+            color: Some(Color32::from_rgb(255, 140, 110)),
+            ..Default::default()
+        },
+    );
+    job.append(", ", TextFormat::default());
+    job.append(
+        "underline",
+        TextFormat {
+            underline: Stroke::new(1.0, Color32::LIGHT_BLUE),
+            ..Default::default()
+        },
+    );
+    job.append(", ", TextFormat::default());
+    job.append(
+        "strikethrough",
+        TextFormat {
+            strikethrough: Stroke::new(1.0, Color32::GRAY),
+            ..Default::default()
+        },
+    );
+    job.append(", ", TextFormat::default());
+    job.append(
+        "backgrounds",
+        TextFormat {
+            background: Color32::from_rgb(40, 60, 90),
+            ..Default::default()
+        },
+    );
+    job.append(" and ", TextFormat::default());
+    job.append("text styles", TextFormat::style(TextStyle::Monospace));
+    job.append(".", TextFormat::default());
+    job
+}
@@ -31,6 +31,8 @@ pub struct WrapApp {
     selected_anchor: String,
     apps: Apps,
     backend_panel: BackendPanel,
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    dropped_files: Vec<egui::DroppedFile>,
 }
 
 impl epi::App for WrapApp {
@@ -63,11 +65,20 @@ impl epi::App for WrapApp {
             self.selected_anchor = self.apps.iter_mut().next().unwrap().0.to_owned();
         }
 
+        self.command_palette(ctx, frame);
+
         egui::TopPanel::top("wrap_app_top_bar").show(ctx, |ui| {
             // A menu-bar is a horizontal layout with some special styles applied.
             // egui::menu::bar(ui, |ui| {
             ui.horizontal_wrapped(|ui| {
                 ui.checkbox(&mut self.backend_panel.open, "💻 Backend");
+                if ui
+                    .button("⌨ Commands")
+                    .on_hover_text("Command palette (Ctrl+Shift+P or ⇧⌘P)")
+                    .clicked
+                {
+                    egui::CommandPalette::new(COMMAND_PALETTE_ID).open(ctx);
+                }
                 ui.separator();
 
                 for (anchor, app) in self.apps.iter_mut() {
@@ -112,6 +123,105 @@ impl epi::App for WrapApp {
                 app.update(ctx, frame);
             }
         }
+
+        self.ui_file_drag_and_drop(ctx);
+    }
+}
+
+const COMMAND_PALETTE_ID: &str = "wrap_app_command_palette";
+
+impl WrapApp {
+    fn command_palette(&mut self, ctx: &egui::CtxRef, frame: &mut epi::Frame<'_>) {
+        use egui::{Key, KeyboardShortcut, Modifiers};
+        const APP_KEYS: [Key; 4] = [Key::Num1, Key::Num2, Key::Num3, Key::Num4];
+
+        let mut palette = egui::CommandPalette::new(COMMAND_PALETTE_ID).command(
+            "Toggle backend panel",
+            Some(KeyboardShortcut::new(Modifiers::COMMAND, Key::B)),
+        );
+        let mut anchors = vec![];
+        for (i, (anchor, app)) in self.apps.iter_mut().enumerate() {
+            let shortcut = APP_KEYS
+                .get(i)
+                .map(|&key| KeyboardShortcut::new(Modifiers::COMMAND, key));
+            palette = palette.command(format!("Show {}", app.name()), shortcut);
+            anchors.push(anchor.to_owned());
+        }
+
+        match palette.show(ctx) {
+            Some(0) => self.backend_panel.open = !self.backend_panel.open,
+            Some(i) => {
+                self.selected_anchor = anchors[i - 1].clone();
+                if frame.is_web() {
+                    ctx.output().open_url = Some(format!("#{}", self.selected_anchor));
+                }
+            }
+            None => {}
+        }
+    }
+
+    fn ui_file_drag_and_drop(&mut self, ctx: &egui::CtxRef) {
+        use egui::*;
+
+        // Preview hovering files:
+        if !ctx.input().hovered_files.is_empty() {
+            let mut text = "Dropping files:\n".to_owned();
+            for file in &ctx.input().hovered_files {
+                if let Some(path) = &file.path {
+                    text += &format!("\n{}", path.display());
+                } else if !file.mime.is_empty() {
+                    text += &format!("\n{}", file.mime);
+                } else {
+                    text += "\n???";
+                }
+            }
+
+            let painter = Painter::new(
+                ctx.clone(),
+                LayerId::new(Order::Foreground, Id::new("file_drop_target")),
+                ctx.input().screen_rect(),
+            );
+            let screen_rect = ctx.input().screen_rect();
+            painter.rect_filled(screen_rect, 0.0, Color32::from_black_alpha(192));
+            painter.text(
+                screen_rect.center(),
+                Align2::CENTER_CENTER,
+                text,
+                TextStyle::Heading,
+                Color32::WHITE,
+            );
+        }
+
+        // Collect dropped files:
+        if !ctx.input().dropped_files.is_empty() {
+            self.dropped_files = ctx.input().dropped_files.clone();
+        }
+
+        // Show dropped files (if any):
+        if !self.dropped_files.is_empty() {
+            let mut open = true;
+            Window::new("Dropped files")
+                .open(&mut open)
+                .show(ctx, |ui| {
+                    for file in &self.dropped_files {
+                        let mut info = if let Some(path) = &file.path {
+                            path.display().to_string()
+                        } else {
+                            file.name.clone()
+                        };
+                        if !file.mime.is_empty() {
+                            info += &format!(" ({})", file.mime);
+                        }
+                        if let Some(bytes) = &file.bytes {
+                            info += &format!(" {} bytes", bytes.len());
+                        }
+                        ui.label(info);
+                    }
+                });
+            if !open {
+                self.dropped_files.clear();
+            }
+        }
     }
 }
 
@@ -7,6 +7,13 @@ const EGUI_MEMORY_KEY: &str = "egui";
 #[cfg(feature = "persistence")]
 const WINDOW_KEY: &str = "window";
 
+/// Set this environment variable to a file path to record all input to it.
+#[cfg(feature = "persistence")]
+const RECORD_INPUT_ENV_VAR: &str = "EGUI_RECORD_INPUT";
+/// Set this environment variable to a file written by `EGUI_RECORD_INPUT` to play it back.
+#[cfg(feature = "persistence")]
+const REPLAY_INPUT_ENV_VAR: &str = "EGUI_REPLAY_INPUT";
+
 #[cfg(feature = "persistence")]
 fn deserialize_window_settings(storage: &Option<Box<dyn epi::Storage>>) -> Option<WindowSettings> {
     epi::get_value(&**storage.as_ref()?, WINDOW_KEY)
@@ -27,6 +34,44 @@ fn deserialize_memory(_: &Option<Box<dyn epi::Storage>>) -> Option<egui::Memory>
     None
 }
 
+#[cfg(feature = "persistence")]
+fn input_recording_path() -> Option<std::path::PathBuf> {
+    std::env::var_os(RECORD_INPUT_ENV_VAR).map(Into::into)
+}
+
+#[cfg(not(feature = "persistence"))]
+fn input_recording_path() -> Option<std::path::PathBuf> {
+    None
+}
+
+#[cfg(feature = "persistence")]
+fn save_input_recording(path: &std::path::Path, recording: &egui::util::InputRecording) {
+    match crate::persistence::write_input_recording(recording, path) {
+        Ok(()) => eprintln!("Recorded {} frames of input to {:?}", recording.len(), path),
+        Err(err) => eprintln!("Failed to save input recording to {:?}: {}", path, err),
+    }
+}
+
+#[cfg(not(feature = "persistence"))]
+fn save_input_recording(_: &std::path::Path, _: &egui::util::InputRecording) {}
+
+#[cfg(feature = "persistence")]
+fn load_input_player() -> Option<egui::util::InputPlayer> {
+    let path = std::env::var_os(REPLAY_INPUT_ENV_VAR)?;
+    match crate::persistence::read_input_recording(&path) {
+        Some(recording) => Some(egui::util::InputPlayer::new(recording)),
+        None => {
+            eprintln!("Failed to load input recording from {:?}", path);
+            None
+        }
+    }
+}
+
+#[cfg(not(feature = "persistence"))]
+fn load_input_player() -> Option<egui::util::InputPlayer> {
+    None
+}
+
 impl epi::TextureAllocator for Painter {
     fn alloc_srgba_premultiplied(
         &mut self,
@@ -148,6 +193,10 @@ pub fn run(mut app: Box<dyn epi::App>) -> ! {
 
     let mut input_state = GliumInputState::from_pixels_per_point(native_pixels_per_point(&display));
 
+    let mut input_recording =
+        input_recording_path().map(|path| (path, egui::util::InputRecording::default()));
+    let mut input_player = load_input_player();
+
     let start_time = Instant::now();
     let mut previous_frame_time = None;
     let mut painter = Painter::new(&display);
@@ -185,7 +234,7 @@ pub fn run(mut app: Box<dyn epi::App>) -> ! {
         ctx.clear_animations();
 
         let (egui_output, _shapes) = ctx.end_frame();
-        handle_output(egui_output, &display, clipboard.as_mut());
+        handle_output(egui_output, &display, clipboard.as_mut(), &mut input_state);
         // TODO: handle app_output
         // eprintln!("Warmed up in {} ms", warm_up_start.elapsed().as_millis())
     }
@@ -199,7 +248,20 @@ pub fn run(mut app: Box<dyn epi::App>) -> ! {
                 screen_size_in_pixels(&display) / input_state.raw.pixels_per_point.unwrap(),
             ));
 
-            ctx.begin_frame(input_state.raw.take());
+            let mut raw_input = input_state.raw.take();
+            if let Some(player) = &mut input_player {
+                if let Some(replayed_input) = player.next_frame() {
+                    raw_input = replayed_input;
+                } else {
+                    eprintln!("Finished replaying input");
+                    input_player = None;
+                }
+            }
+            if let Some((_, recording)) = &mut input_recording {
+                recording.record(&raw_input);
+            }
+
+            ctx.begin_frame(raw_input);
             let mut app_output = epi::backend::AppOutput::default();
             let mut frame = epi::backend::FrameBuilder {
                 info: integration_info(&display, previous_frame_time),
@@ -248,7 +310,7 @@ pub fn run(mut app: Box<dyn epi::App>) -> ! {
 
                 *control_flow = if quit {
                     glutin::event_loop::ControlFlow::Exit
-                } else if egui_output.needs_repaint {
+                } else if egui_output.needs_repaint || input_player.is_some() {
                     display.gl_window().window().request_redraw();
                     glutin::event_loop::ControlFlow::Poll
                 } else {
@@ -256,7 +318,7 @@ pub fn run(mut app: Box<dyn epi::App>) -> ! {
                 };
             }
 
-            handle_output(egui_output, &display, clipboard.as_mut());
+            handle_output(egui_output, &display, clipboard.as_mut(), &mut input_state);
 
             #[cfg(feature = "persistence")]
             if let Some(storage) = &mut storage {
@@ -288,6 +350,9 @@ pub fn run(mut app: Box<dyn epi::App>) -> ! {
             }
             glutin::event::Event::LoopDestroyed => {
                 app.on_exit();
+                if let Some((path, recording)) = &input_recording {
+                    save_input_recording(path, recording);
+                }
                 #[cfg(feature = "persistence")]
                 if let Some(storage) = &mut storage {
                     epi::set_value(
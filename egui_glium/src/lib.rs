@@ -30,6 +30,8 @@ pub use clipboard::ClipboardContext; // TODO: remove
 
 pub struct GliumInputState {
     pub raw: egui::RawInput,
+    /// The finger that is emulating the mouse, if any.
+    mouse_touch_id: Option<u64>,
 }
 
 impl GliumInputState {
@@ -39,6 +41,7 @@ impl GliumInputState {
                 pixels_per_point: Some(pixels_per_point),
                 ..Default::default()
             },
+            mouse_touch_id: None,
         }
     }
 }
@@ -52,8 +55,11 @@ pub fn input_to_egui(
     use glutin::event::WindowEvent::*;
     match event {
         CloseRequested | Destroyed => *control_flow = ControlFlow::Exit,
-        MouseInput { state, .. } => {
-            input_state.raw.mouse_down = state == glutin::event::ElementState::Pressed;
+        MouseInput { state, button, .. } => {
+            if let Some(button) = translate_mouse_button(button) {
+                input_state.raw.mouse_down[button as usize] =
+                    state == glutin::event::ElementState::Pressed;
+            }
         }
         CursorMoved {
             position: pos_in_pixels,
@@ -113,16 +119,7 @@ pub fn input_to_egui(
                     } else if input_state.raw.modifiers.command && keycode == VirtualKeyCode::C {
                         input_state.raw.events.push(Event::Copy);
                     } else if input_state.raw.modifiers.command && keycode == VirtualKeyCode::V {
-                        if let Some(clipboard) = clipboard {
-                            match clipboard.get_contents() {
-                                Ok(contents) => {
-                                    input_state.raw.events.push(Event::Text(contents));
-                                }
-                                Err(err) => {
-                                    eprintln!("Paste error: {}", err);
-                                }
-                            }
-                        }
+                        paste(clipboard, input_state);
                     }
                 }
 
@@ -135,6 +132,39 @@ pub fn input_to_egui(
                 }
             }
         }
+        Touch(touch) => {
+            let pixels_per_point = input_state.raw.pixels_per_point.unwrap();
+            let phase = match touch.phase {
+                glutin::event::TouchPhase::Started => TouchPhase::Start,
+                glutin::event::TouchPhase::Moved => TouchPhase::Move,
+                glutin::event::TouchPhase::Ended => TouchPhase::End,
+                glutin::event::TouchPhase::Cancelled => TouchPhase::Cancel,
+            };
+            let pos = pos2(
+                touch.location.x as f32 / pixels_per_point,
+                touch.location.y as f32 / pixels_per_point,
+            );
+            input_state.raw.events.push(Event::Touch {
+                device_id: TouchDeviceId(hash(touch.device_id)),
+                id: TouchId(touch.id),
+                phase,
+                pos,
+                force: touch.force.map_or(0.0, |force| force.normalized() as f32),
+            });
+
+            // Emulate the mouse with the first finger, so that taps click and one finger drags:
+            if phase == TouchPhase::Start && input_state.mouse_touch_id.is_none() {
+                input_state.mouse_touch_id = Some(touch.id);
+            }
+            if input_state.mouse_touch_id == Some(touch.id) {
+                input_state.raw.mouse_pos = Some(pos);
+                input_state.raw.mouse_down[MouseButton::Primary as usize] =
+                    phase == TouchPhase::Start || phase == TouchPhase::Move;
+                if phase == TouchPhase::End || phase == TouchPhase::Cancel {
+                    input_state.mouse_touch_id = None;
+                }
+            }
+        }
         MouseWheel { delta, .. } => {
             match delta {
                 glutin::event::MouseScrollDelta::LineDelta(x, y) => {
@@ -147,12 +177,40 @@ pub fn input_to_egui(
                 }
             }
         }
+        HoveredFile(path) => {
+            input_state.raw.hovered_files.push(egui::HoveredFile {
+                mime: guess_mime_type(&path).to_owned(),
+                path: Some(path),
+            });
+        }
+        HoveredFileCancelled => {
+            input_state.raw.hovered_files.clear();
+        }
+        DroppedFile(path) => {
+            input_state.raw.hovered_files.clear();
+            input_state.raw.dropped_files.push(egui::DroppedFile {
+                name: path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default(),
+                mime: guess_mime_type(&path).to_owned(),
+                path: Some(path),
+                bytes: None,
+            });
+        }
         _ => {
             // dbg!(event);
         }
     }
 }
 
+fn hash(value: impl std::hash::Hash) -> u64 {
+    use std::hash::Hasher;
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
 /// Glium sends special keys (backspace, delete, F1, ...) as characters.
 /// Ignore those.
 /// We also ignore '\r', '\n', '\t'.
@@ -165,6 +223,50 @@ fn printable_char(chr: char) -> bool {
     !is_in_private_use_area && !chr.is_ascii_control()
 }
 
+/// Guess the MIME type of a file from its extension, or `""` if unknown.
+fn guess_mime_type(path: &std::path::Path) -> &'static str {
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    match extension.to_ascii_lowercase().as_str() {
+        "bmp" => "image/bmp",
+        "gif" => "image/gif",
+        "jpg" | "jpeg" => "image/jpeg",
+        "png" => "image/png",
+        "svg" => "image/svg+xml",
+        "webp" => "image/webp",
+        "otf" => "font/otf",
+        "ttf" => "font/ttf",
+        "wav" => "audio/wav",
+        "mp3" => "audio/mpeg",
+        "ogg" => "audio/ogg",
+        "mp4" => "video/mp4",
+        "json" => "application/json",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        "csv" => "text/csv",
+        "html" | "htm" => "text/html",
+        "md" => "text/markdown",
+        "txt" => "text/plain",
+        _ => "",
+    }
+}
+
+pub fn translate_mouse_button(button: glutin::event::MouseButton) -> Option<egui::MouseButton> {
+    use glutin::event::MouseButton;
+    match button {
+        MouseButton::Left => Some(egui::MouseButton::Primary),
+        MouseButton::Right => Some(egui::MouseButton::Secondary),
+        MouseButton::Middle => Some(egui::MouseButton::Middle),
+        // The numbering of the extra buttons depends on the platform (Windows, Mac, X11):
+        MouseButton::Other(1) | MouseButton::Other(3) | MouseButton::Other(8) => {
+            Some(egui::MouseButton::Extra1)
+        }
+        MouseButton::Other(2) | MouseButton::Other(4) | MouseButton::Other(9) => {
+            Some(egui::MouseButton::Extra2)
+        }
+        MouseButton::Other(_) => None,
+    }
+}
+
 pub fn translate_virtual_key_code(key: VirtualKeyCode) -> Option<egui::Key> {
     use VirtualKeyCode::*;
 
@@ -177,7 +279,7 @@ pub fn translate_virtual_key_code(key: VirtualKeyCode) -> Option<egui::Key> {
         Escape => Key::Escape,
         Tab => Key::Tab,
         Back => Key::Backspace,
-        Return => Key::Enter,
+        Return | NumpadEnter => Key::Enter,
         Space => Key::Space,
 
         Insert => Key::Insert,
@@ -225,6 +327,42 @@ pub fn translate_virtual_key_code(key: VirtualKeyCode) -> Option<egui::Key> {
         Y => Key::Y,
         Z => Key::Z,
 
+        Minus | NumpadSubtract => Key::Minus,
+        Plus | NumpadAdd => Key::Plus,
+        Equals | NumpadEquals => Key::Equals,
+        Asterisk | NumpadMultiply => Key::Asterisk,
+        Slash | NumpadDivide => Key::Slash,
+        Backslash => Key::Backslash,
+        Period | NumpadDecimal => Key::Period,
+        Comma | NumpadComma => Key::Comma,
+        Semicolon => Key::Semicolon,
+        Colon => Key::Colon,
+        Apostrophe => Key::Quote,
+        Grave => Key::Backtick,
+        LBracket => Key::OpenBracket,
+        RBracket => Key::CloseBracket,
+
+        F1 => Key::F1,
+        F2 => Key::F2,
+        F3 => Key::F3,
+        F4 => Key::F4,
+        F5 => Key::F5,
+        F6 => Key::F6,
+        F7 => Key::F7,
+        F8 => Key::F8,
+        F9 => Key::F9,
+        F10 => Key::F10,
+        F11 => Key::F11,
+        F12 => Key::F12,
+        F13 => Key::F13,
+        F14 => Key::F14,
+        F15 => Key::F15,
+        F16 => Key::F16,
+        F17 => Key::F17,
+        F18 => Key::F18,
+        F19 => Key::F19,
+        F20 => Key::F20,
+
         _ => {
             return None;
         }
@@ -248,7 +386,8 @@ pub fn translate_cursor(cursor_icon: egui::CursorIcon) -> glutin::window::Cursor
 pub fn handle_output(
     output: egui::Output,
     display: &glium::backend::glutin::Display,
-    clipboard: Option<&mut ClipboardContext>,
+    mut clipboard: Option<&mut ClipboardContext>,
+    input_state: &mut GliumInputState,
 ) {
     if let Some(url) = output.open_url {
         if let Err(err) = webbrowser::open(&url) {
@@ -257,19 +396,115 @@ pub fn handle_output(
     }
 
     if !output.copied_text.is_empty() {
-        if let Some(clipboard) = clipboard {
+        if let Some(clipboard) = clipboard.as_deref_mut() {
             if let Err(err) = clipboard.set_contents(output.copied_text) {
                 eprintln!("Copy/Cut error: {}", err);
             }
         }
     }
 
+    if let Some(image) = &output.copied_image {
+        if let Err(err) = copy_image(image) {
+            eprintln!("Failed to copy image: {}", err);
+        }
+    }
+
+    if output.request_paste {
+        paste(clipboard, input_state);
+        // Make sure the `Event::Paste` is delivered even if nothing else happens:
+        display.gl_window().window().request_redraw();
+    }
+
+    if let Some(cursor_rect) = output.text_cursor_rect {
+        // Show the IME candidate window below the text cursor:
+        display
+            .gl_window()
+            .window()
+            .set_ime_position(glutin::dpi::LogicalPosition::new(
+                cursor_rect.left(),
+                cursor_rect.bottom(),
+            ));
+    }
+
     display
         .gl_window()
         .window()
         .set_cursor_icon(translate_cursor(output.cursor_icon));
 }
 
+/// Read the clipboard and send its contents to egui as an [`Event::Paste`].
+fn paste(clipboard: Option<&mut ClipboardContext>, input_state: &mut GliumInputState) {
+    if let Some(clipboard) = clipboard {
+        match clipboard.get_contents() {
+            Ok(contents) => {
+                input_state.raw.events.push(Event::Paste(contents));
+            }
+            Err(err) => {
+                eprintln!("Paste error: {}", err);
+            }
+        }
+    }
+}
+
+/// Put an image on the clipboard, as a PNG.
+///
+/// The `clipboard` crate only handles text, so we talk to X11 directly.
+#[cfg(all(unix, not(any(target_os = "macos", target_os = "android"))))]
+fn copy_image(image: &egui::ClipboardImage) -> Result<(), String> {
+    use std::cell::RefCell;
+
+    thread_local! {
+        // Kept alive, since it answers other applications asking for the image.
+        static X11_CLIPBOARD: RefCell<Option<x11_clipboard::Clipboard>> = const { RefCell::new(None) };
+    }
+
+    let png = encode_png(image)?;
+    X11_CLIPBOARD.with(|x11_clipboard| {
+        let mut x11_clipboard = x11_clipboard.borrow_mut();
+        if x11_clipboard.is_none() {
+            *x11_clipboard = Some(x11_clipboard::Clipboard::new().map_err(|err| err.to_string())?);
+        }
+        let x11_clipboard = x11_clipboard.as_ref().unwrap();
+        let target = x11_clipboard
+            .setter
+            .get_atom("image/png")
+            .map_err(|err| err.to_string())?;
+        x11_clipboard
+            .store(x11_clipboard.setter.atoms.clipboard, target, png)
+            .map_err(|err| err.to_string())
+    })
+}
+
+#[cfg(not(all(unix, not(any(target_os = "macos", target_os = "android")))))]
+fn copy_image(_image: &egui::ClipboardImage) -> Result<(), String> {
+    Err("copying images is not supported on this platform yet".to_owned())
+}
+
+#[cfg(all(unix, not(any(target_os = "macos", target_os = "android"))))]
+fn encode_png(image: &egui::ClipboardImage) -> Result<Vec<u8>, String> {
+    let [width, height] = image.size;
+    if image.rgba.len() != 4 * width * height {
+        return Err(format!(
+            "expected {} bytes for a {}x{} image, got {}",
+            4 * width * height,
+            width,
+            height,
+            image.rgba.len()
+        ));
+    }
+
+    let mut png = vec![];
+    let mut encoder = png::Encoder::new(&mut png, width as u32, height as u32);
+    encoder.set_color(png::ColorType::RGBA);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(|err| err.to_string())?;
+    writer
+        .write_image_data(&image.rgba)
+        .map_err(|err| err.to_string())?;
+    drop(writer); // Writes the end of the PNG
+    Ok(png)
+}
+
 pub fn init_clipboard() -> Option<ClipboardContext> {
     match ClipboardContext::new() {
         Ok(clipboard) => Some(clipboard),
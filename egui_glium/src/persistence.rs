@@ -85,3 +85,19 @@ pub fn write_memory(
     serde_json::to_writer_pretty(std::fs::File::create(memory_json_path)?, &*ctx.memory())?;
     Ok(())
 }
+
+// ----------------------------------------------------------------------------
+
+/// Read an input recording written by [`write_input_recording`].
+pub fn read_input_recording(path: impl AsRef<Path>) -> Option<egui::util::InputRecording> {
+    read_json(path)
+}
+
+/// Write an input recording as JSON, e.g. for attaching to a bug report.
+pub fn write_input_recording(
+    recording: &egui::util::InputRecording,
+    path: impl AsRef<Path>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    serde_json::to_writer(std::fs::File::create(path)?, recording)?;
+    Ok(())
+}
@@ -0,0 +1,486 @@
+//! Snapshot (golden image) testing of [`egui`] UIs and [`epi`] apps.
+//!
+//! A [`Harness`] drives an [`egui::CtxRef`] through `begin_frame`/`end_frame`/`tessellate`
+//! with scripted [`egui::RawInput`], paints the result on the CPU using
+//! [`egui::paint::rasterizer`] and compares it against a stored PNG.
+//!
+//! ``` no_run
+//! let mut harness = egui_snapshot::Harness::new(egui::vec2(200.0, 100.0));
+//! harness.step(Default::default(), |ctx| {
+//!     egui::CentralPanel::default().show(ctx, |ui| {
+//!         ui.label("Hello snapshot!");
+//!     });
+//! });
+//! harness.assert_snapshot("tests/snapshots/hello.png");
+//! ```
+
+#![forbid(unsafe_code)]
+#![cfg_attr(not(debug_assertions), deny(warnings))] // Forbid warnings in release builds
+#![warn(clippy::all, rust_2018_idioms)]
+
+use std::path::{Path, PathBuf};
+
+use egui::{
+    paint::rasterizer::{Image, Rasterizer},
+    Color32, CtxRef, Output, PaintJobs, RawInput, Rect, Rgba, Vec2,
+};
+
+/// Set this environment variable to overwrite all snapshots with the current output.
+pub const UPDATE_SNAPSHOTS_ENV_VAR: &str = "UPDATE_SNAPSHOTS";
+
+// ----------------------------------------------------------------------------
+
+/// Runs Egui frames headlessly and paints them into [`Image`]s.
+pub struct Harness {
+    ctx: CtxRef,
+    painter: SoftwarePainter,
+    paint_jobs: PaintJobs,
+
+    screen_size: Vec2,
+    pixels_per_point: f32,
+    clear_color: Rgba,
+    tolerance: Tolerance,
+
+    /// Time of the next frame, unless given by the input.
+    time: f64,
+
+    /// Has `App::setup` been called?
+    app_is_set_up: bool,
+}
+
+impl Harness {
+    /// `screen_size` is in points.
+    pub fn new(screen_size: Vec2) -> Self {
+        Self {
+            ctx: Default::default(),
+            painter: Default::default(),
+            paint_jobs: Default::default(),
+            screen_size,
+            pixels_per_point: 1.0,
+            clear_color: Color32::from_rgb(12, 12, 12).into(),
+            tolerance: Default::default(),
+            time: 0.0,
+            app_is_set_up: false,
+        }
+    }
+
+    /// Default: `1.0`.
+    pub fn pixels_per_point(mut self, pixels_per_point: f32) -> Self {
+        self.pixels_per_point = pixels_per_point;
+        self
+    }
+
+    /// Background color. Overwritten by [`epi::App::clear_color`] when using [`Self::step_app`].
+    pub fn clear_color(mut self, clear_color: Rgba) -> Self {
+        self.clear_color = clear_color;
+        self
+    }
+
+    /// How different the images may be in [`Self::check_snapshot`].
+    /// Default: [`Tolerance::default`].
+    pub fn tolerance(mut self, tolerance: Tolerance) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn ctx(&self) -> &CtxRef {
+        &self.ctx
+    }
+
+    /// The paint jobs of the last frame.
+    pub fn paint_jobs(&self) -> &PaintJobs {
+        &self.paint_jobs
+    }
+
+    /// Fill in what the scripted input left out, and advance time by a fixed step
+    /// so that animations are deterministic.
+    fn prepare_input(&mut self, mut input: RawInput) -> RawInput {
+        if input.screen_rect.is_none() {
+            input.screen_rect = Some(Rect::from_min_size(Default::default(), self.screen_size));
+        }
+        if input.pixels_per_point.is_none() {
+            input.pixels_per_point = Some(self.pixels_per_point);
+        }
+        let time = *input.time.get_or_insert(self.time);
+        self.time = time + input.predicted_dt as f64;
+        input
+    }
+
+    fn finish_frame(&mut self) -> Output {
+        let (output, shapes) = self.ctx.end_frame();
+        self.paint_jobs = self.ctx.tessellate(shapes);
+        output
+    }
+
+    /// Run one frame, calling `run_ui` to add panels, windows and widgets.
+    pub fn step(&mut self, input: RawInput, run_ui: impl FnOnce(&CtxRef)) -> Output {
+        let input = self.prepare_input(input);
+        self.ctx.begin_frame(input);
+        run_ui(&self.ctx);
+        self.finish_frame()
+    }
+
+    /// Run one frame of an [`epi::App`].
+    ///
+    /// [`epi::App::setup`] is called before the first frame.
+    pub fn step_app(&mut self, app: &mut dyn epi::App, input: RawInput) -> Output {
+        if !self.app_is_set_up {
+            app.setup(&self.ctx);
+            self.app_is_set_up = true;
+        }
+
+        let input = self.prepare_input(input);
+        self.ctx.begin_frame(input);
+
+        let mut app_output = epi::backend::AppOutput::default();
+        let mut frame = epi::backend::FrameBuilder {
+            info: epi::IntegrationInfo {
+                web_info: None,
+                cpu_usage: None,
+                seconds_since_midnight: None,
+                native_pixels_per_point: Some(self.pixels_per_point),
+            },
+            tex_allocator: Some(&mut self.painter),
+            #[cfg(feature = "http")]
+            http: std::sync::Arc::new(NoHttp {}),
+            output: &mut app_output,
+            repaint_signal: std::sync::Arc::new(NoRepaintSignal {}),
+        }
+        .build();
+        app.update(&self.ctx, &mut frame);
+        self.clear_color = app.clear_color();
+
+        if let Some(pixels_per_point) = app_output.pixels_per_point {
+            self.pixels_per_point = pixels_per_point;
+        }
+        if let Some(window_size) = app_output.window_size {
+            self.screen_size = window_size;
+        }
+
+        self.finish_frame()
+    }
+
+    /// Size of the painted image, in physical pixels.
+    pub fn size_in_pixels(&self) -> (usize, usize) {
+        let size = self.screen_size * self.ctx.pixels_per_point();
+        (size.x.round() as usize, size.y.round() as usize)
+    }
+
+    /// Paint the last frame.
+    pub fn image(&mut self) -> Image {
+        let size_in_pixels = self.size_in_pixels();
+        let pixels_per_point = self.ctx.pixels_per_point();
+        self.painter.0.paint_jobs(
+            size_in_pixels,
+            pixels_per_point,
+            self.clear_color,
+            &self.paint_jobs,
+            &self.ctx.texture(),
+        )
+    }
+
+    /// Paint the last frame and compare it to the PNG at `path`, allowing the differences
+    /// set with [`Self::tolerance`].
+    ///
+    /// # Errors
+    /// See [`check_snapshot`].
+    pub fn check_snapshot(&mut self, path: impl AsRef<Path>) -> Result<(), SnapshotError> {
+        check_snapshot(&self.image(), path.as_ref(), self.tolerance)
+    }
+
+    /// Like [`Self::check_snapshot`], but panics on failure.
+    pub fn assert_snapshot(&mut self, path: impl AsRef<Path>) {
+        if let Err(err) = self.check_snapshot(path) {
+            panic!("{}", err);
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Lets apps allocate user textures when running in the [`Harness`].
+#[derive(Default)]
+struct SoftwarePainter(Rasterizer);
+
+impl epi::TextureAllocator for SoftwarePainter {
+    fn alloc_srgba_premultiplied(
+        &mut self,
+        size: (usize, usize),
+        srgba_pixels: &[Color32],
+    ) -> egui::TextureId {
+        let id = self.0.alloc_user_texture();
+        self.0.set_user_texture(id, size, srgba_pixels);
+        id
+    }
+
+    fn free(&mut self, id: egui::TextureId) {
+        self.0.free_user_texture(id)
+    }
+}
+
+/// The harness repaints when told to, so this does nothing.
+struct NoRepaintSignal {}
+
+impl epi::RepaintSignal for NoRepaintSignal {
+    fn request_repaint(&self) {}
+}
+
+/// Tests should not do network requests, so all requests fail.
+#[cfg(feature = "http")]
+struct NoHttp {}
+
+#[cfg(feature = "http")]
+impl epi::backend::Http for NoHttp {
+    fn fetch_dyn(
+        &self,
+        _request: epi::http::Request,
+        on_done: Box<dyn FnOnce(Result<epi::http::Response, epi::http::Error>) + Send>,
+    ) {
+        on_done(Err("http is not available in egui_snapshot".to_owned()))
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// How different two images may be and still be considered equal.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Tolerance {
+    /// How much each channel of a pixel may differ (0-255) before the pixel is counted as different.
+    pub channel_threshold: u8,
+
+    /// How many pixels may differ before the images are considered different.
+    pub max_different_pixels: usize,
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self {
+            channel_threshold: 2,
+            max_different_pixels: 0,
+        }
+    }
+}
+
+/// Why a snapshot did not match.
+#[derive(Debug)]
+pub enum SnapshotError {
+    /// Failed to read or write a PNG.
+    Image {
+        path: PathBuf,
+        err: image::ImageError,
+    },
+
+    /// The image had a different size than the snapshot.
+    SizeMismatch {
+        path: PathBuf,
+        expected: [usize; 2],
+        actual: [usize; 2],
+    },
+
+    /// Too many pixels differed.
+    Mismatch {
+        path: PathBuf,
+        different_pixels: usize,
+        diff_path: PathBuf,
+    },
+}
+
+impl std::fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let update_hint = format!(
+            "Run with {}=1 to accept the new output.",
+            UPDATE_SNAPSHOTS_ENV_VAR
+        );
+        match self {
+            Self::Image { path, err } => write!(f, "Snapshot {:?}: {}", path, err),
+            Self::SizeMismatch {
+                path,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Snapshot {:?} has size {:?}, but the image is {:?}. {}",
+                path, expected, actual, update_hint
+            ),
+            Self::Mismatch {
+                path,
+                different_pixels,
+                diff_path,
+            } => write!(
+                f,
+                "Snapshot {:?} differs in {} pixels. See {:?}. {}",
+                path, different_pixels, diff_path, update_hint
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+/// Compare `image` to the PNG stored at `path`.
+///
+/// If there is no PNG at `path`, or if the environment variable
+/// [`UPDATE_SNAPSHOTS_ENV_VAR`] is set, `image` is written to `path` instead.
+///
+/// On a mismatch the new image is written to `<name>.new.png`
+/// and an image highlighting the different pixels in red to `<name>.diff.png`.
+///
+/// # Errors
+/// If the images differ more than `tolerance` allows, or if reading/writing the PNG:s failed.
+pub fn check_snapshot(
+    image: &Image,
+    path: &Path,
+    tolerance: Tolerance,
+) -> Result<(), SnapshotError> {
+    if std::env::var_os(UPDATE_SNAPSHOTS_ENV_VAR).is_some() || !path.exists() {
+        return save_png(image, path);
+    }
+
+    let expected = load_png(path)?;
+    let new_path = path.with_extension("new.png");
+    let diff_path = path.with_extension("diff.png");
+
+    if expected.size() != image.size() {
+        save_png(image, &new_path)?;
+        return Err(SnapshotError::SizeMismatch {
+            path: path.to_owned(),
+            expected: expected.size(),
+            actual: image.size(),
+        });
+    }
+
+    let (different_pixels, diff) = diff_images(&expected, image, tolerance.channel_threshold);
+    if different_pixels > tolerance.max_different_pixels {
+        save_png(image, &new_path)?;
+        save_png(&diff, &diff_path)?;
+        Err(SnapshotError::Mismatch {
+            path: path.to_owned(),
+            different_pixels,
+            diff_path,
+        })
+    } else {
+        // Clean up after a previous failure:
+        std::fs::remove_file(&new_path).ok();
+        std::fs::remove_file(&diff_path).ok();
+        Ok(())
+    }
+}
+
+/// Returns the number of different pixels,
+/// and an image with those pixels in red on top of a faded version of `expected`.
+pub fn diff_images(expected: &Image, actual: &Image, channel_threshold: u8) -> (usize, Image) {
+    assert_eq!(expected.size(), actual.size());
+
+    let mut different_pixels = 0;
+    let pixels = expected
+        .pixels
+        .iter()
+        .zip(&actual.pixels)
+        .map(|(&e, &a)| {
+            let is_different =
+                (0..4).any(|i| (e[i] as i32 - a[i] as i32).abs() > channel_threshold as i32);
+            if is_different {
+                different_pixels += 1;
+                Color32::RED
+            } else {
+                let gray = Rgba::from(e).intensity();
+                Rgba::from_gray(0.25 * gray).into()
+            }
+        })
+        .collect();
+
+    let diff = Image {
+        width: expected.width,
+        height: expected.height,
+        pixels,
+    };
+    (different_pixels, diff)
+}
+
+/// Load a PNG into an [`Image`] (with premultiplied alpha).
+///
+/// # Errors
+/// If the file could not be read or decoded.
+pub fn load_png(path: &Path) -> Result<Image, SnapshotError> {
+    let png = image::open(path)
+        .map_err(|err| SnapshotError::Image {
+            path: path.to_owned(),
+            err,
+        })?
+        .to_rgba8();
+
+    let pixels = png
+        .pixels()
+        .map(|p| {
+            let [r, g, b, a] = p.0;
+            Color32::from_rgba_unmultiplied(r, g, b, a)
+        })
+        .collect();
+
+    Ok(Image {
+        width: png.width() as usize,
+        height: png.height() as usize,
+        pixels,
+    })
+}
+
+/// Save an [`Image`] as a PNG, creating parent directories as needed.
+///
+/// # Errors
+/// If the directory could not be created, or the file could not be encoded or written.
+pub fn save_png(image: &Image, path: &Path) -> Result<(), SnapshotError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|err| SnapshotError::Image {
+            path: path.to_owned(),
+            err: image::ImageError::IoError(err),
+        })?;
+    }
+
+    image::save_buffer(
+        path,
+        &image.to_srgba_unmultiplied_bytes(),
+        image.width as u32,
+        image.height as u32,
+        image::ColorType::Rgba8,
+    )
+    .map_err(|err| SnapshotError::Image {
+        path: path.to_owned(),
+        err,
+    })
+}
+
+// ----------------------------------------------------------------------------
+
+#[test]
+fn test_snapshot_roundtrip() {
+    let dir = std::env::temp_dir().join("egui_snapshot_test");
+    let path = dir.join("demo_app.png");
+    std::fs::remove_file(&path).ok();
+
+    let mut app = egui_demo_lib::WrapApp::default();
+    let mut harness = Harness::new(egui::vec2(640.0, 480.0));
+    for _ in 0..3 {
+        harness.step_app(&mut app, Default::default());
+    }
+    let image = harness.image();
+    assert_eq!(image.size(), [640, 480]);
+
+    check_snapshot(&image, &path, Tolerance::default()).unwrap(); // writes it
+    check_snapshot(&image, &path, Tolerance::default()).unwrap(); // compares it
+
+    let mut changed = image;
+    changed[(10, 10)] = Color32::GOLD;
+    assert!(matches!(
+        check_snapshot(&changed, &path, Tolerance::default()),
+        Err(SnapshotError::Mismatch {
+            different_pixels: 1,
+            ..
+        })
+    ));
+    assert!(path.with_extension("diff.png").exists());
+
+    assert!(
+        save_png(&changed, &path.join("inside_a_file.png")).is_err(),
+        "can't create a directory where there is a file"
+    );
+}
@@ -142,6 +142,8 @@ pub struct AppRunner {
     last_save_time: f64,
     #[cfg(feature = "http")]
     http: Arc<http::WebHttp>,
+    input_recording: Option<egui::util::InputRecording>,
+    input_player: Option<egui::util::InputPlayer>,
 }
 
 impl AppRunner {
@@ -159,6 +161,8 @@ impl AppRunner {
             last_save_time: now_sec(),
             #[cfg(feature = "http")]
             http: Arc::new(http::WebHttp {}),
+            input_recording: None,
+            input_player: None,
         })
     }
 
@@ -177,6 +181,22 @@ impl AppRunner {
         self.web_backend.canvas_id()
     }
 
+    /// Record all input from now on, until [`Self::stop_recording_input`].
+    pub fn start_recording_input(&mut self) {
+        self.input_recording = Some(Default::default());
+    }
+
+    /// Returns everything recorded since [`Self::start_recording_input`].
+    pub fn stop_recording_input(&mut self) -> Option<egui::util::InputRecording> {
+        self.input_recording.take()
+    }
+
+    /// Ignore the real input and play back the given recording instead, one frame at a time.
+    pub fn replay_input(&mut self, recording: egui::util::InputRecording) {
+        self.input_player = Some(egui::util::InputPlayer::new(recording));
+        self.needs_repaint.set_true();
+    }
+
     pub fn warm_up(&mut self) -> Result<(), JsValue> {
         if self.app.warm_up_enabled() {
             let saved_memory = self.web_backend.ctx.memory().clone();
@@ -194,7 +214,19 @@ impl AppRunner {
     pub fn logic(&mut self) -> Result<(egui::Output, egui::PaintJobs), JsValue> {
         resize_canvas_to_screen_size(self.web_backend.canvas_id());
         let canvas_size = canvas_size_in_points(self.web_backend.canvas_id());
-        let raw_input = self.input.new_frame(canvas_size);
+        let mut raw_input = self.input.new_frame(canvas_size);
+        if let Some(player) = &mut self.input_player {
+            if let Some(replayed_input) = player.next_frame() {
+                raw_input = replayed_input;
+                self.needs_repaint.set_true(); // keep playing
+            } else {
+                console_log("Finished replaying input");
+                self.input_player = None;
+            }
+        }
+        if let Some(recording) = &mut self.input_recording {
+            recording.record(&raw_input);
+        }
         self.web_backend.begin_frame(raw_input);
 
         let mut app_output = epi::backend::AppOutput::default();
@@ -219,6 +251,7 @@ impl AppRunner {
         self.app.update(egui_ctx, &mut frame);
         let (egui_output, paint_jobs) = self.web_backend.end_frame()?;
         handle_output(&egui_output);
+        move_text_cursor(egui_output.text_cursor_rect, self.canvas_id());
 
         {
             let epi::backend::AppOutput {
@@ -251,6 +284,7 @@ fn start_runner(app_runner: AppRunner) -> Result<AppRunnerRef, JsValue> {
     let runner_ref = AppRunnerRef(Arc::new(Mutex::new(app_runner)));
     install_canvas_events(&runner_ref)?;
     install_document_events(&runner_ref)?;
+    install_text_agent(&runner_ref)?;
     repaint_every_ms(&runner_ref, 1000)?; // just in case. TODO: make it a parameter
     paint_and_schedule(runner_ref.clone())?;
     Ok(runner_ref)
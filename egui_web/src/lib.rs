@@ -94,6 +94,18 @@ pub fn pos_from_mouse_event(canvas_id: &str, event: &web_sys::MouseEvent) -> egu
     }
 }
 
+pub fn button_from_mouse_event(event: &web_sys::MouseEvent) -> Option<egui::MouseButton> {
+    // See https://developer.mozilla.org/en-US/docs/Web/API/MouseEvent/button
+    match event.button() {
+        0 => Some(egui::MouseButton::Primary),
+        1 => Some(egui::MouseButton::Middle),
+        2 => Some(egui::MouseButton::Secondary),
+        3 => Some(egui::MouseButton::Extra1),
+        4 => Some(egui::MouseButton::Extra2),
+        _ => None,
+    }
+}
+
 pub fn pos_from_touch_event(event: &web_sys::TouchEvent) -> egui::Pos2 {
     let t = event.touches().get(0).unwrap();
     egui::Pos2 {
@@ -102,6 +114,28 @@ pub fn pos_from_touch_event(event: &web_sys::TouchEvent) -> egui::Pos2 {
     }
 }
 
+/// Push an [`egui::Event::Touch`] for each finger that changed in the given event.
+pub fn push_touches(runner: &mut AppRunner, phase: egui::TouchPhase, event: &web_sys::TouchEvent) {
+    let canvas_rect = canvas_element(runner.canvas_id())
+        .unwrap()
+        .get_bounding_client_rect();
+    let touches = event.changed_touches();
+    for touch_idx in 0..touches.length() {
+        if let Some(touch) = touches.get(touch_idx) {
+            runner.input.raw.events.push(egui::Event::Touch {
+                device_id: egui::TouchDeviceId(0),
+                id: egui::TouchId(touch.identifier() as u64),
+                phase,
+                pos: egui::pos2(
+                    touch.client_x() as f32 - canvas_rect.left() as f32,
+                    touch.client_y() as f32 - canvas_rect.top() as f32,
+                ),
+                force: touch.force(),
+            });
+        }
+    }
+}
+
 pub fn canvas_size_in_points(canvas_id: &str) -> egui::Vec2 {
     let canvas = canvas_element(canvas_id).unwrap();
     let pixels_per_point = native_pixels_per_point();
@@ -207,7 +241,11 @@ pub fn handle_output(output: &egui::Output) {
         cursor_icon,
         open_url,
         copied_text,
-        needs_repaint: _, // handled elsewhere
+        copied_image,
+        request_paste: _,    // handled by `paint_and_schedule`
+        text_cursor_rect: _, // handled by `move_text_cursor`
+        access_tree: _,      // not forwarded to ARIA yet
+        needs_repaint: _,    // handled elsewhere
     } = output;
 
     set_cursor_icon(*cursor_icon);
@@ -222,6 +260,38 @@ pub fn handle_output(output: &egui::Output) {
 
     #[cfg(not(web_sys_unstable_apis))]
     let _ = copied_text;
+
+    if copied_image.is_some() {
+        console_error("Copying images is not supported on the web yet");
+    }
+}
+
+/// Move the text agent to the text cursor, so that the IME candidate window shows up at the right place.
+/// The text agent has keyboard focus while a `TextEdit` has.
+pub fn move_text_cursor(cursor: Option<egui::Rect>, canvas_id: &str) -> Option<()> {
+    use wasm_bindgen::JsCast;
+    let window = web_sys::window()?;
+    let document = window.document()?;
+    let input: web_sys::HtmlInputElement =
+        document.get_element_by_id(TEXT_AGENT_ID)?.dyn_into().ok()?;
+    let text_agent_has_focus = document
+        .active_element()
+        .map_or(false, |element| element.id() == TEXT_AGENT_ID);
+
+    if let Some(cursor) = cursor {
+        let canvas_rect = canvas_element(canvas_id)?.get_bounding_client_rect();
+        let left = window.scroll_x().ok()? + canvas_rect.left() + cursor.left() as f64;
+        let top = window.scroll_y().ok()? + canvas_rect.top() + cursor.bottom() as f64;
+        let style = input.style();
+        style.set_property("left", &format!("{}px", left)).ok()?;
+        style.set_property("top", &format!("{}px", top)).ok()?;
+        if !text_agent_has_focus {
+            input.focus().ok()?;
+        }
+    } else if text_agent_has_focus {
+        input.blur().ok()?;
+    }
+    Some(())
 }
 
 pub fn set_cursor_icon(cursor: egui::CursorIcon) -> Option<()> {
@@ -248,6 +318,31 @@ pub fn set_clipboard_text(s: &str) {
     }
 }
 
+/// Read the clipboard and send its contents to egui as an [`egui::Event::Paste`].
+#[cfg(web_sys_unstable_apis)]
+fn request_paste(runner_ref: &AppRunnerRef) {
+    if let Some(window) = web_sys::window() {
+        let promise = window.navigator().clipboard().read_text();
+        let future = wasm_bindgen_futures::JsFuture::from(promise);
+        let runner_ref = runner_ref.clone();
+        let future = async move {
+            match future.await {
+                Ok(text) => {
+                    if let Some(text) = text.as_string() {
+                        let mut runner_lock = runner_ref.0.lock();
+                        runner_lock.input.raw.events.push(egui::Event::Paste(text));
+                        runner_lock.needs_repaint.set_true();
+                    }
+                }
+                Err(err) => {
+                    console_error(format!("Paste action denied: {:?}", err));
+                }
+            }
+        };
+        wasm_bindgen_futures::spawn_local(future);
+    }
+}
+
 pub fn spawn_future<F>(future: F)
 where
     F: std::future::Future<Output = ()> + 'static,
@@ -333,7 +428,7 @@ pub fn translate_key(key: &str) -> Option<egui::Key> {
         "Tab" => Some(egui::Key::Tab),
         "Backspace" => Some(egui::Key::Backspace),
         "Enter" => Some(egui::Key::Enter),
-        "Space" => Some(egui::Key::Space),
+        " " | "Space" => Some(egui::Key::Space),
 
         "Help" | "Insert" => Some(egui::Key::Insert),
         "Delete" => Some(egui::Key::Delete),
@@ -380,6 +475,44 @@ pub fn translate_key(key: &str) -> Option<egui::Key> {
         "y" | "Y" => Some(egui::Key::Y),
         "z" | "Z" => Some(egui::Key::Z),
 
+        // The numpad keys give the same `key` as the main keyboard,
+        // except in old browsers which use the names:
+        "-" | "Subtract" => Some(egui::Key::Minus),
+        "+" | "Add" => Some(egui::Key::Plus),
+        "=" => Some(egui::Key::Equals),
+        "*" | "Multiply" => Some(egui::Key::Asterisk),
+        "/" | "Divide" => Some(egui::Key::Slash),
+        "\\" => Some(egui::Key::Backslash),
+        "." | "Decimal" => Some(egui::Key::Period),
+        "," => Some(egui::Key::Comma),
+        ";" => Some(egui::Key::Semicolon),
+        ":" => Some(egui::Key::Colon),
+        "'" => Some(egui::Key::Quote),
+        "`" => Some(egui::Key::Backtick),
+        "[" => Some(egui::Key::OpenBracket),
+        "]" => Some(egui::Key::CloseBracket),
+
+        "F1" => Some(egui::Key::F1),
+        "F2" => Some(egui::Key::F2),
+        "F3" => Some(egui::Key::F3),
+        "F4" => Some(egui::Key::F4),
+        "F5" => Some(egui::Key::F5),
+        "F6" => Some(egui::Key::F6),
+        "F7" => Some(egui::Key::F7),
+        "F8" => Some(egui::Key::F8),
+        "F9" => Some(egui::Key::F9),
+        "F10" => Some(egui::Key::F10),
+        "F11" => Some(egui::Key::F11),
+        "F12" => Some(egui::Key::F12),
+        "F13" => Some(egui::Key::F13),
+        "F14" => Some(egui::Key::F14),
+        "F15" => Some(egui::Key::F15),
+        "F16" => Some(egui::Key::F16),
+        "F17" => Some(egui::Key::F17),
+        "F18" => Some(egui::Key::F18),
+        "F19" => Some(egui::Key::F19),
+        "F20" => Some(egui::Key::F20),
+
         _ => None,
     }
 }
@@ -389,6 +522,39 @@ pub fn translate_key(key: &str) -> Option<egui::Key> {
 #[derive(Clone)]
 pub struct AppRunnerRef(Arc<Mutex<AppRunner>>);
 
+impl AppRunnerRef {
+    /// See [`AppRunner::start_recording_input`].
+    pub fn start_recording_input(&self) {
+        self.0.lock().start_recording_input();
+    }
+
+    /// See [`AppRunner::stop_recording_input`].
+    pub fn stop_recording_input(&self) -> Option<egui::util::InputRecording> {
+        self.0.lock().stop_recording_input()
+    }
+
+    /// See [`AppRunner::replay_input`].
+    pub fn replay_input(&self, recording: egui::util::InputRecording) {
+        self.0.lock().replay_input(recording);
+    }
+
+    /// Stop recording and return the recording as JSON, e.g. for attaching to a bug report.
+    #[cfg(feature = "persistence")]
+    pub fn stop_recording_input_json(&self) -> Option<String> {
+        let recording = self.stop_recording_input()?;
+        serde_json::to_string(&recording).ok()
+    }
+
+    /// Replay a recording returned by [`Self::stop_recording_input_json`].
+    #[cfg(feature = "persistence")]
+    pub fn replay_input_json(&self, json: &str) -> Result<(), JsValue> {
+        let recording = serde_json::from_str(json)
+            .map_err(|err| JsValue::from(format!("Failed to parse input recording: {}", err)))?;
+        self.replay_input(recording);
+        Ok(())
+    }
+}
+
 fn paint_and_schedule(runner_ref: AppRunnerRef) -> Result<(), JsValue> {
     fn paint_if_needed(runner_ref: &AppRunnerRef) -> Result<(), JsValue> {
         let mut runner_lock = runner_ref.0.lock();
@@ -398,6 +564,10 @@ fn paint_and_schedule(runner_ref: AppRunnerRef) -> Result<(), JsValue> {
             if output.needs_repaint {
                 runner_lock.needs_repaint.set_true();
             }
+            #[cfg(web_sys_unstable_apis)]
+            if output.request_paste {
+                request_paste(runner_ref);
+            }
             runner_lock.auto_save();
         }
 
@@ -503,7 +673,7 @@ fn install_document_events(runner_ref: &AppRunnerRef) -> Result<(), JsValue> {
             if let Some(data) = event.clipboard_data() {
                 if let Ok(text) = data.get_data("text") {
                     let mut runner_lock = runner_ref.0.lock();
-                    runner_lock.input.raw.events.push(egui::Event::Text(text));
+                    runner_lock.input.raw.events.push(egui::Event::Paste(text));
                     runner_lock.needs_repaint.set_true();
                 }
             }
@@ -583,6 +753,115 @@ fn modifiers_from_event(event: &web_sys::KeyboardEvent) -> egui::Modifiers {
     }
 }
 
+const TEXT_AGENT_ID: &str = "egui_text_agent";
+
+/// Add a hidden `<input>` element which gets keyboard focus while a `TextEdit` has it.
+/// This gives us IME composition events, and lets the browser show the IME candidate window at the text cursor.
+fn install_text_agent(runner_ref: &AppRunnerRef) -> Result<(), JsValue> {
+    use std::{cell::Cell, rc::Rc};
+    use wasm_bindgen::JsCast;
+    let window = web_sys::window().unwrap();
+    let document = window.document().unwrap();
+    let body = document.body().expect("document should have a body");
+
+    let input = document
+        .create_element("input")?
+        .dyn_into::<web_sys::HtmlInputElement>()?;
+    input.set_id(TEXT_AGENT_ID);
+    input.set_type("text");
+    {
+        let style = input.style();
+        style.set_property("position", "absolute")?;
+        style.set_property("opacity", "0")?;
+        style.set_property("width", "1px")?;
+        style.set_property("height", "1px")?;
+        style.set_property("border", "none")?;
+        style.set_property("outline", "none")?;
+        style.set_property("pointer-events", "none")?;
+    }
+    body.append_child(&input)?;
+
+    let is_composing = Rc::new(Cell::new(false));
+
+    {
+        // Text typed without an IME is handled by "keydown", so we only need to clear the input.
+        let input_clone = input.clone();
+        let is_composing = is_composing.clone();
+        let closure = Closure::wrap(Box::new(move |_event: web_sys::Event| {
+            if !is_composing.get() {
+                input_clone.set_value("");
+            }
+        }) as Box<dyn FnMut(_)>);
+        input.add_event_listener_with_callback("input", closure.as_ref().unchecked_ref())?;
+        closure.forget();
+    }
+
+    {
+        let runner_ref = runner_ref.clone();
+        let input_clone = input.clone();
+        let is_composing = is_composing.clone();
+        let closure = Closure::wrap(Box::new(move |_event: web_sys::CompositionEvent| {
+            is_composing.set(true);
+            input_clone.set_value("");
+            let mut runner_lock = runner_ref.0.lock();
+            runner_lock
+                .input
+                .raw
+                .events
+                .push(egui::Event::CompositionStart);
+            runner_lock.needs_repaint.set_true();
+        }) as Box<dyn FnMut(_)>);
+        input.add_event_listener_with_callback(
+            "compositionstart",
+            closure.as_ref().unchecked_ref(),
+        )?;
+        closure.forget();
+    }
+
+    {
+        let runner_ref = runner_ref.clone();
+        let closure = Closure::wrap(Box::new(move |event: web_sys::CompositionEvent| {
+            let mut runner_lock = runner_ref.0.lock();
+            runner_lock
+                .input
+                .raw
+                .events
+                .push(egui::Event::CompositionUpdate(
+                    event.data().unwrap_or_default(),
+                ));
+            runner_lock.needs_repaint.set_true();
+        }) as Box<dyn FnMut(_)>);
+        input.add_event_listener_with_callback(
+            "compositionupdate",
+            closure.as_ref().unchecked_ref(),
+        )?;
+        closure.forget();
+    }
+
+    {
+        let runner_ref = runner_ref.clone();
+        let input_clone = input.clone();
+        let closure = Closure::wrap(Box::new(move |event: web_sys::CompositionEvent| {
+            is_composing.set(false);
+            input_clone.set_value("");
+            let mut runner_lock = runner_ref.0.lock();
+            runner_lock
+                .input
+                .raw
+                .events
+                .push(egui::Event::CompositionEnd(
+                    event.data().unwrap_or_default(),
+                ));
+            runner_lock.needs_repaint.set_true();
+        }) as Box<dyn FnMut(_)>);
+        input
+            .add_event_listener_with_callback("compositionend", closure.as_ref().unchecked_ref())?;
+        closure.forget();
+    }
+
+    Ok(())
+}
+
 fn install_canvas_events(runner_ref: &AppRunnerRef) -> Result<(), JsValue> {
     use wasm_bindgen::JsCast;
     let canvas = canvas_element(runner_ref.0.lock().canvas_id()).unwrap();
@@ -595,7 +874,9 @@ fn install_canvas_events(runner_ref: &AppRunnerRef) -> Result<(), JsValue> {
             if !runner_lock.input.is_touch {
                 runner_lock.input.raw.mouse_pos =
                     Some(pos_from_mouse_event(runner_lock.canvas_id(), &event));
-                runner_lock.input.raw.mouse_down = true;
+                if let Some(button) = button_from_mouse_event(&event) {
+                    runner_lock.input.raw.mouse_down[button as usize] = true;
+                }
                 runner_lock.logic().unwrap(); // in case we get "mouseup" the same frame. TODO: handle via events instead
                 runner_lock.needs_repaint.set_true();
                 event.stop_propagation();
@@ -631,7 +912,9 @@ fn install_canvas_events(runner_ref: &AppRunnerRef) -> Result<(), JsValue> {
             if !runner_lock.input.is_touch {
                 runner_lock.input.raw.mouse_pos =
                     Some(pos_from_mouse_event(runner_lock.canvas_id(), &event));
-                runner_lock.input.raw.mouse_down = false;
+                if let Some(button) = button_from_mouse_event(&event) {
+                    runner_lock.input.raw.mouse_down[button as usize] = false;
+                }
                 runner_lock.needs_repaint.set_true();
                 event.stop_propagation();
                 event.prevent_default();
@@ -657,14 +940,25 @@ fn install_canvas_events(runner_ref: &AppRunnerRef) -> Result<(), JsValue> {
         closure.forget();
     }
 
+    {
+        // Egui has its own context menus, so don't show the one of the browser:
+        let event_name = "contextmenu";
+        let closure = Closure::wrap(Box::new(move |event: web_sys::MouseEvent| {
+            event.prevent_default();
+        }) as Box<dyn FnMut(_)>);
+        canvas.add_event_listener_with_callback(event_name, closure.as_ref().unchecked_ref())?;
+        closure.forget();
+    }
+
     {
         let event_name = "touchstart";
         let runner_ref = runner_ref.clone();
         let closure = Closure::wrap(Box::new(move |event: web_sys::TouchEvent| {
             let mut runner_lock = runner_ref.0.lock();
             runner_lock.input.is_touch = true;
+            push_touches(&mut runner_lock, egui::TouchPhase::Start, &event);
             runner_lock.input.raw.mouse_pos = Some(pos_from_touch_event(&event));
-            runner_lock.input.raw.mouse_down = true;
+            runner_lock.input.raw.mouse_down[egui::MouseButton::Primary as usize] = true;
             runner_lock.needs_repaint.set_true();
             event.stop_propagation();
             event.prevent_default();
@@ -679,7 +973,11 @@ fn install_canvas_events(runner_ref: &AppRunnerRef) -> Result<(), JsValue> {
         let closure = Closure::wrap(Box::new(move |event: web_sys::TouchEvent| {
             let mut runner_lock = runner_ref.0.lock();
             runner_lock.input.is_touch = true;
-            runner_lock.input.raw.mouse_pos = Some(pos_from_touch_event(&event));
+            push_touches(&mut runner_lock, egui::TouchPhase::Move, &event);
+            if event.touches().length() == 1 {
+                // Only drag with one finger. More fingers are gestures (e.g. pinch zoom).
+                runner_lock.input.raw.mouse_pos = Some(pos_from_touch_event(&event));
+            }
             runner_lock.needs_repaint.set_true();
             event.stop_propagation();
             event.prevent_default();
@@ -694,9 +992,32 @@ fn install_canvas_events(runner_ref: &AppRunnerRef) -> Result<(), JsValue> {
         let closure = Closure::wrap(Box::new(move |event: web_sys::TouchEvent| {
             let mut runner_lock = runner_ref.0.lock();
             runner_lock.input.is_touch = true;
-            runner_lock.input.raw.mouse_down = false; // First release mouse to click...
-            runner_lock.logic().unwrap(); // ...do the clicking... (TODO: handle via events instead)
-            runner_lock.input.raw.mouse_pos = None; // ...remove hover effect
+            push_touches(&mut runner_lock, egui::TouchPhase::End, &event);
+            if event.touches().length() == 0 {
+                runner_lock.input.raw.mouse_down[egui::MouseButton::Primary as usize] = false; // First release mouse to click...
+                runner_lock.logic().unwrap(); // ...do the clicking... (TODO: handle via events instead)
+                runner_lock.input.raw.mouse_pos = None; // ...remove hover effect
+            }
+            runner_lock.needs_repaint.set_true();
+            event.stop_propagation();
+            event.prevent_default();
+        }) as Box<dyn FnMut(_)>);
+        canvas.add_event_listener_with_callback(event_name, closure.as_ref().unchecked_ref())?;
+        closure.forget();
+    }
+
+    {
+        let event_name = "touchcancel";
+        let runner_ref = runner_ref.clone();
+        let closure = Closure::wrap(Box::new(move |event: web_sys::TouchEvent| {
+            let mut runner_lock = runner_ref.0.lock();
+            runner_lock.input.is_touch = true;
+            push_touches(&mut runner_lock, egui::TouchPhase::Cancel, &event);
+            if event.touches().length() == 0 {
+                // No click: just let go.
+                runner_lock.input.raw.mouse_down[egui::MouseButton::Primary as usize] = false;
+                runner_lock.input.raw.mouse_pos = None;
+            }
             runner_lock.needs_repaint.set_true();
             event.stop_propagation();
             event.prevent_default();
@@ -720,5 +1041,100 @@ fn install_canvas_events(runner_ref: &AppRunnerRef) -> Result<(), JsValue> {
         closure.forget();
     }
 
+    {
+        let event_name = "dragover";
+        let runner_ref = runner_ref.clone();
+        let closure = Closure::wrap(Box::new(move |event: web_sys::DragEvent| {
+            if let Some(data_transfer) = event.data_transfer() {
+                let mut runner_lock = runner_ref.0.lock();
+                runner_lock.input.raw.hovered_files.clear();
+                // Only the MIME types are known before the drop:
+                let items = data_transfer.items();
+                for i in 0..items.length() {
+                    if let Some(item) = items.get(i) {
+                        if item.kind() == "file" {
+                            runner_lock.input.raw.hovered_files.push(egui::HoveredFile {
+                                mime: item.type_(),
+                                ..Default::default()
+                            });
+                        }
+                    }
+                }
+                runner_lock.needs_repaint.set_true();
+                event.stop_propagation();
+                event.prevent_default();
+            }
+        }) as Box<dyn FnMut(_)>);
+        canvas.add_event_listener_with_callback(event_name, closure.as_ref().unchecked_ref())?;
+        closure.forget();
+    }
+
+    {
+        let event_name = "dragleave";
+        let runner_ref = runner_ref.clone();
+        let closure = Closure::wrap(Box::new(move |event: web_sys::DragEvent| {
+            let mut runner_lock = runner_ref.0.lock();
+            runner_lock.input.raw.hovered_files.clear();
+            runner_lock.needs_repaint.set_true();
+            event.stop_propagation();
+            event.prevent_default();
+        }) as Box<dyn FnMut(_)>);
+        canvas.add_event_listener_with_callback(event_name, closure.as_ref().unchecked_ref())?;
+        closure.forget();
+    }
+
+    {
+        let event_name = "drop";
+        let runner_ref = runner_ref.clone();
+        let closure = Closure::wrap(Box::new(move |event: web_sys::DragEvent| {
+            if let Some(data_transfer) = event.data_transfer() {
+                {
+                    let mut runner_lock = runner_ref.0.lock();
+                    runner_lock.input.raw.hovered_files.clear();
+                    runner_lock.needs_repaint.set_true();
+                }
+
+                if let Some(files) = data_transfer.files() {
+                    for i in 0..files.length() {
+                        if let Some(file) = files.get(i) {
+                            let name = file.name();
+                            let mime = file.type_();
+                            let future = wasm_bindgen_futures::JsFuture::from(file.array_buffer());
+                            let runner_ref = runner_ref.clone();
+                            let future = async move {
+                                match future.await {
+                                    Ok(array_buffer) => {
+                                        let bytes = js_sys::Uint8Array::new(&array_buffer).to_vec();
+                                        let mut runner_lock = runner_ref.0.lock();
+                                        runner_lock.input.raw.dropped_files.push(
+                                            egui::DroppedFile {
+                                                name,
+                                                mime,
+                                                bytes: Some(bytes.into()),
+                                                ..Default::default()
+                                            },
+                                        );
+                                        runner_lock.needs_repaint.set_true();
+                                    }
+                                    Err(err) => {
+                                        console_error(format!(
+                                            "Failed to read dropped file {:?}: {:?}",
+                                            name, err
+                                        ));
+                                    }
+                                }
+                            };
+                            wasm_bindgen_futures::spawn_local(future);
+                        }
+                    }
+                }
+                event.stop_propagation();
+                event.prevent_default();
+            }
+        }) as Box<dyn FnMut(_)>);
+        canvas.add_event_listener_with_callback(event_name, closure.as_ref().unchecked_ref())?;
+        closure.forget();
+    }
+
     Ok(())
 }
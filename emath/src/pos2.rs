@@ -91,6 +91,29 @@ impl Pos2 {
     }
 }
 
+impl std::ops::Index<usize> for Pos2 {
+    type Output = f32;
+
+    /// `0` is `x` and `1` is `y`.
+    fn index(&self, index: usize) -> &f32 {
+        match index {
+            0 => &self.x,
+            1 => &self.y,
+            _ => panic!("Pos2 index out of bounds: {}", index),
+        }
+    }
+}
+
+impl std::ops::IndexMut<usize> for Pos2 {
+    fn index_mut(&mut self, index: usize) -> &mut f32 {
+        match index {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            _ => panic!("Pos2 index out of bounds: {}", index),
+        }
+    }
+}
+
 impl PartialEq for Pos2 {
     fn eq(&self, other: &Self) -> bool {
         self.x == other.x && self.y == other.y
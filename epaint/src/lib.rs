@@ -46,6 +46,7 @@
 
 pub mod color;
 pub mod mutex;
+pub mod rasterizer;
 mod shadow;
 pub mod shape;
 pub mod stats;
@@ -0,0 +1,443 @@
+//! A pure-Rust software rasterizer for [`PaintJobs`](crate::PaintJobs).
+//!
+//! This is useful for rendering Egui frames where there is no GPU available,
+//! e.g. for screenshots and regression tests on CI machines.
+//!
+//! The [`Rasterizer`] mimics what `egui_glium::Painter` does on the GPU:
+//! vertex colors are converted from premultiplied `sRGBA` to linear space,
+//! textures are sampled bilinearly (clamped) in linear space,
+//! and the result is blended (premultiplied alpha) into a linear framebuffer
+//! restricted to the clip rectangle of each [`PaintJob`].
+
+use crate::{Color32, PaintJob, Rgba, Texture, TextureId, Triangles, Vertex};
+use emath::*;
+
+/// An image with premultiplied `sRGBA` pixels, row by row, top to bottom.
+#[derive(Clone, Default, PartialEq)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<Color32>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, color: Color32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![color; width * height],
+        }
+    }
+
+    /// `[width, height]`
+    pub fn size(&self) -> [usize; 2] {
+        [self.width, self.height]
+    }
+
+    /// The pixels as premultiplied `sRGBA` bytes, row by row, top to bottom.
+    pub fn to_srgba_premultiplied_bytes(&self) -> Vec<u8> {
+        self.pixels.iter().flat_map(|c| c.to_array()).collect()
+    }
+
+    /// The pixels as `sRGBA` bytes without premultiplied alpha (as used by e.g. PNG).
+    pub fn to_srgba_unmultiplied_bytes(&self) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flat_map(|&c| {
+                let [r, g, b, a] = c.to_array();
+                if a == 0 || a == 255 {
+                    [r, g, b, a]
+                } else {
+                    let rgba = Rgba::from(c);
+                    let unmultiplied = Rgba::from_rgba_premultiplied(
+                        rgba.r() / rgba.a(),
+                        rgba.g() / rgba.a(),
+                        rgba.b() / rgba.a(),
+                        1.0,
+                    );
+                    let [r, g, b, _] = Color32::from(unmultiplied).to_array();
+                    [r, g, b, a]
+                }
+            })
+            .collect()
+    }
+}
+
+impl std::ops::Index<(usize, usize)> for Image {
+    type Output = Color32;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Color32 {
+        assert!(x < self.width);
+        assert!(y < self.height);
+        &self.pixels[y * self.width + x]
+    }
+}
+
+impl std::ops::IndexMut<(usize, usize)> for Image {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Color32 {
+        assert!(x < self.width);
+        assert!(y < self.height);
+        &mut self.pixels[y * self.width + x]
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A texture decoded to linear space, ready for sampling.
+#[derive(Clone, Default)]
+struct LinearTexture {
+    width: usize,
+    height: usize,
+    /// Linear space, premultiplied alpha.
+    pixels: Vec<Rgba>,
+}
+
+impl LinearTexture {
+    fn from_srgba(width: usize, height: usize, srgba: impl Iterator<Item = Color32>) -> Self {
+        let pixels: Vec<Rgba> = srgba.map(Rgba::from).collect();
+        assert_eq!(width * height, pixels.len());
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    fn texel(&self, x: isize, y: isize) -> Rgba {
+        // Clamp to edge:
+        let x = clamp(x, 0..=(self.width as isize - 1)) as usize;
+        let y = clamp(y, 0..=(self.height as isize - 1)) as usize;
+        self.pixels[y * self.width + x]
+    }
+
+    /// Bilinear sampling with clamp-to-edge, like a `Linear` GPU sampler.
+    fn sample(&self, uv: Pos2) -> Rgba {
+        if self.width == 0 || self.height == 0 {
+            return Rgba::TRANSPARENT;
+        }
+
+        let x = uv.x * self.width as f32 - 0.5;
+        let y = uv.y * self.height as f32 - 0.5;
+        let x0 = x.floor();
+        let y0 = y.floor();
+        let tx = x - x0;
+        let ty = y - y0;
+        let (x0, y0) = (x0 as isize, y0 as isize);
+
+        let top = lerp_rgba(self.texel(x0, y0), self.texel(x0 + 1, y0), tx);
+        let bottom = lerp_rgba(self.texel(x0, y0 + 1), self.texel(x0 + 1, y0 + 1), tx);
+        lerp_rgba(top, bottom, ty)
+    }
+}
+
+fn lerp_rgba(a: Rgba, b: Rgba, t: f32) -> Rgba {
+    (1.0 - t) * a + t * b
+}
+
+// ----------------------------------------------------------------------------
+
+/// Paints [`PaintJobs`](crate::PaintJobs) into an [`Image`] on the CPU.
+///
+/// Keeps track of the Egui font texture and any user textures,
+/// with the same API as the GPU painters in `egui_glium` and `egui_web`.
+#[derive(Default)]
+pub struct Rasterizer {
+    egui_texture: LinearTexture,
+    egui_texture_version: Option<u64>,
+
+    /// `None` means unallocated (freed) slot.
+    user_textures: Vec<Option<LinearTexture>>,
+}
+
+impl Rasterizer {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn upload_egui_texture(&mut self, texture: &Texture) {
+        if self.egui_texture_version == Some(texture.version) {
+            return; // No change
+        }
+
+        self.egui_texture =
+            LinearTexture::from_srgba(texture.width, texture.height, texture.srgba_pixels());
+        self.egui_texture_version = Some(texture.version);
+    }
+
+    pub fn alloc_user_texture(&mut self) -> TextureId {
+        for (i, tex) in self.user_textures.iter_mut().enumerate() {
+            if tex.is_none() {
+                *tex = Some(Default::default());
+                return TextureId::User(i as u64);
+            }
+        }
+        let id = TextureId::User(self.user_textures.len() as u64);
+        self.user_textures.push(Some(Default::default()));
+        id
+    }
+
+    /// `pixels` are premultiplied `sRGBA`, row by row, top to bottom.
+    pub fn set_user_texture(&mut self, id: TextureId, size: (usize, usize), pixels: &[Color32]) {
+        assert_eq!(size.0 * size.1, pixels.len());
+
+        if let TextureId::User(id) = id {
+            if let Some(Some(user_texture)) = self.user_textures.get_mut(id as usize) {
+                *user_texture = LinearTexture::from_srgba(size.0, size.1, pixels.iter().copied());
+            }
+        }
+    }
+
+    pub fn free_user_texture(&mut self, id: TextureId) {
+        if let TextureId::User(id) = id {
+            let index = id as usize;
+            if index < self.user_textures.len() {
+                self.user_textures[index] = None;
+            }
+        }
+    }
+
+    fn get_texture(&self, texture_id: TextureId) -> Option<&LinearTexture> {
+        match texture_id {
+            TextureId::Egui => Some(&self.egui_texture),
+            TextureId::User(id) => self.user_textures.get(id as usize)?.as_ref(),
+        }
+    }
+
+    /// Main entry-point for painting a frame.
+    ///
+    /// * `size_in_pixels`: the size of the resulting image, in physical pixels.
+    /// * `pixels_per_point`: as given to Egui in `RawInput`.
+    /// * `clear_color`: the background color (linear space).
+    /// * `jobs`: the output of `Context::tessellate`.
+    /// * `egui_texture`: the font texture (`Context::texture`).
+    pub fn paint_jobs(
+        &mut self,
+        size_in_pixels: (usize, usize),
+        pixels_per_point: f32,
+        clear_color: Rgba,
+        jobs: &[PaintJob],
+        egui_texture: &Texture,
+    ) -> Image {
+        self.upload_egui_texture(egui_texture);
+
+        let (width, height) = size_in_pixels;
+        let mut target = Target {
+            width,
+            height,
+            pixels: vec![clear_color; width * height],
+        };
+
+        for (clip_rect, triangles) in jobs {
+            self.paint_job(&mut target, pixels_per_point, *clip_rect, triangles);
+        }
+
+        Image {
+            width,
+            height,
+            pixels: target.pixels.into_iter().map(Color32::from).collect(),
+        }
+    }
+
+    fn paint_job(
+        &self,
+        target: &mut Target,
+        pixels_per_point: f32,
+        clip_rect: Rect,
+        triangles: &Triangles,
+    ) {
+        debug_assert!(triangles.is_valid());
+
+        let texture = match self.get_texture(triangles.texture_id) {
+            Some(texture) => texture,
+            None => return,
+        };
+
+        // Transform clip rect to physical pixels, rounding the same way the GPU painters do:
+        let (width, height) = (target.width as f32, target.height as f32);
+        let clip_min_x = clamp(pixels_per_point * clip_rect.min.x, 0.0..=width);
+        let clip_min_y = clamp(pixels_per_point * clip_rect.min.y, 0.0..=height);
+        let clip_max_x = clamp(pixels_per_point * clip_rect.max.x, clip_min_x..=width);
+        let clip_max_y = clamp(pixels_per_point * clip_rect.max.y, clip_min_y..=height);
+        let clip = [
+            clip_min_x.round() as usize,
+            clip_min_y.round() as usize,
+            clip_max_x.round() as usize,
+            clip_max_y.round() as usize,
+        ];
+
+        let to_pixels = |v: &Vertex| RasterVertex {
+            pos: pos2(pixels_per_point * v.pos.x, pixels_per_point * v.pos.y),
+            uv: v.uv,
+            color: Rgba::from(v.color),
+        };
+
+        for indices in triangles.indices.chunks_exact(3) {
+            let a = to_pixels(&triangles.vertices[indices[0] as usize]);
+            let b = to_pixels(&triangles.vertices[indices[1] as usize]);
+            let c = to_pixels(&triangles.vertices[indices[2] as usize]);
+            target.fill_triangle(texture, clip, [a, b, c]);
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[derive(Clone, Copy)]
+struct RasterVertex {
+    /// In physical pixels.
+    pos: Pos2,
+    uv: Pos2,
+    /// Linear space, premultiplied alpha.
+    color: Rgba,
+}
+
+/// The framebuffer, in linear space with premultiplied alpha.
+struct Target {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgba>,
+}
+
+impl Target {
+    /// `clip` is `[min_x, min_y, max_x, max_y]` in pixels (max exclusive).
+    fn fill_triangle(
+        &mut self,
+        texture: &LinearTexture,
+        clip: [usize; 4],
+        [a, b, c]: [RasterVertex; 3],
+    ) {
+        let area = edge(a.pos, b.pos, c.pos);
+        if area == 0.0 || !area.is_finite() {
+            return; // Degenerate
+        }
+        // Egui does not care about winding order, so handle both:
+        let (b, c, area) = if area < 0.0 {
+            (c, b, -area)
+        } else {
+            (b, c, area)
+        };
+
+        let min_x = a
+            .pos
+            .x
+            .min(b.pos.x)
+            .min(c.pos.x)
+            .floor()
+            .max(clip[0] as f32) as usize;
+        let min_y = a
+            .pos
+            .y
+            .min(b.pos.y)
+            .min(c.pos.y)
+            .floor()
+            .max(clip[1] as f32) as usize;
+        let max_x = a.pos.x.max(b.pos.x).max(c.pos.x).ceil().min(clip[2] as f32) as usize;
+        let max_y = a.pos.y.max(b.pos.y).max(c.pos.y).ceil().min(clip[3] as f32) as usize;
+
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                // Sample at the pixel center, like the GPU does:
+                let p = pos2(x as f32 + 0.5, y as f32 + 0.5);
+                let w_a = edge(b.pos, c.pos, p);
+                let w_b = edge(c.pos, a.pos, p);
+                let w_c = edge(a.pos, b.pos, p);
+
+                let inside = covers(w_a, b.pos, c.pos)
+                    && covers(w_b, c.pos, a.pos)
+                    && covers(w_c, a.pos, b.pos);
+                if !inside {
+                    continue;
+                }
+
+                let (w_a, w_b, w_c) = (w_a / area, w_b / area, w_c / area);
+                let color = w_a * a.color + w_b * b.color + w_c * c.color;
+                let uv = pos2(
+                    w_a * a.uv.x + w_b * b.uv.x + w_c * c.uv.x,
+                    w_a * a.uv.y + w_b * b.uv.y + w_c * c.uv.y,
+                );
+                let src = color * texture.sample(uv);
+                self.blend(x, y, src);
+            }
+        }
+    }
+
+    /// Premultiplied alpha blending, same as the blend functions used by `egui_glium`.
+    fn blend(&mut self, x: usize, y: usize, src: Rgba) {
+        let dst = &mut self.pixels[y * self.width + x];
+        let rgb_factor = 1.0 - src.a();
+        let alpha_factor = 1.0 - dst.a();
+        *dst = Rgba::from_rgba_premultiplied(
+            src.r() + rgb_factor * dst.r(),
+            src.g() + rgb_factor * dst.g(),
+            src.b() + rgb_factor * dst.b(),
+            alpha_factor * src.a() + dst.a(),
+        );
+    }
+}
+
+/// Twice the signed area of the triangle `a, b, p`.
+fn edge(a: Pos2, b: Pos2, p: Pos2) -> f32 {
+    (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x)
+}
+
+/// Top-left fill rule, so that pixels on an edge shared by two triangles are only painted once.
+fn covers(w: f32, from: Pos2, to: Pos2) -> bool {
+    if w != 0.0 {
+        return w > 0.0;
+    }
+    let d = to - from;
+    let is_top = d.y == 0.0 && d.x < 0.0;
+    let is_left = d.y > 0.0;
+    is_top || is_left
+}
+
+/// Convenience function for painting all of `jobs` with no user textures.
+pub fn rasterize(
+    size_in_pixels: (usize, usize),
+    pixels_per_point: f32,
+    clear_color: Rgba,
+    jobs: &[PaintJob],
+    egui_texture: &Texture,
+) -> Image {
+    Rasterizer::new().paint_jobs(
+        size_in_pixels,
+        pixels_per_point,
+        clear_color,
+        jobs,
+        egui_texture,
+    )
+}
+
+#[test]
+fn test_rasterize_clipped_rect() {
+    let texture = Texture {
+        version: 0,
+        width: 1,
+        height: 1,
+        pixels: vec![255],
+    };
+
+    let mut triangles = Triangles::default();
+    triangles.add_colored_rect(
+        Rect::from_min_max(pos2(0.0, 0.0), pos2(4.0, 4.0)),
+        Color32::RED,
+    );
+    let clip_rect = Rect::from_min_max(pos2(0.0, 0.0), pos2(2.0, 4.0));
+    let jobs = vec![(clip_rect, triangles)];
+
+    let image = rasterize((8, 8), 2.0, Rgba::BLACK, &jobs, &texture);
+    assert_eq!(image.size(), [8, 8]);
+    assert_eq!(image[(0, 0)], Color32::RED);
+    assert_eq!(image[(3, 7)], Color32::RED);
+    assert_eq!(image[(4, 0)], Color32::BLACK, "clipped");
+
+    // Each pixel should be painted exactly once, even along the shared diagonal:
+    let mut triangles = Triangles::default();
+    triangles.add_colored_rect(
+        Rect::from_min_max(pos2(0.0, 0.0), pos2(8.0, 8.0)),
+        Color32::from_white_alpha(128),
+    );
+    let jobs = vec![(Rect::everything(), triangles)];
+    let image = rasterize((8, 8), 1.0, Rgba::BLACK, &jobs, &texture);
+    assert!(image.pixels.iter().all(|&p| p == image.pixels[0]));
+}
@@ -131,6 +131,28 @@ impl AllocInfo {
     }
 }
 
+/// How full the font texture atlas is, and how often glyphs have been evicted from it.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FontAtlasStats {
+    /// Width of the font texture, in pixels.
+    pub width: usize,
+
+    /// Height of the font texture, in pixels. Grows as more glyphs are added.
+    pub height: usize,
+
+    /// How many rows of pixels of the font texture are in use.
+    pub used_height: usize,
+
+    /// Number of glyphs in the atlas.
+    pub num_glyphs: usize,
+
+    /// Total number of glyphs evicted because they weren't used for a while.
+    pub num_evicted: usize,
+
+    /// How many times glyphs have been evicted to keep the atlas from growing.
+    pub num_evictions: usize,
+}
+
 #[derive(Clone, Copy, Default)]
 pub struct PaintStats {
     pub shapes: AllocInfo,
@@ -143,6 +165,8 @@ pub struct PaintStats {
     pub jobs: AllocInfo,
     pub vertices: AllocInfo,
     pub indices: AllocInfo,
+
+    pub font_atlas: FontAtlasStats,
 }
 
 impl PaintStats {
@@ -183,6 +207,11 @@ impl PaintStats {
         }
     }
 
+    pub fn with_font_atlas(mut self, font_atlas: FontAtlasStats) -> Self {
+        self.font_atlas = font_atlas;
+        self
+    }
+
     pub fn with_paint_jobs(mut self, paint_jobs: &[crate::PaintJob]) -> Self {
         self.jobs += AllocInfo::from_slice(paint_jobs);
         for (_, indices) in paint_jobs {
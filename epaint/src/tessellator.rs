@@ -606,10 +606,16 @@ impl Tessellator {
         color: Color32,
         out: &mut Triangles,
     ) {
+        galley.sanity_check();
+
+        if !galley.sections.is_empty() {
+            self.tessellate_rich_text(fonts, pos, galley, color, out);
+            return;
+        }
+
         if color == Color32::TRANSPARENT {
             return;
         }
-        galley.sanity_check();
 
         let num_chars = galley.text.chars().count();
         out.reserve_triangles(num_chars * 2);
@@ -621,32 +627,53 @@ impl Tessellator {
         let clip_rect = self.clip_rect.expand(2.0); // Some fudge to handle letters that are slightly larger than expected.
 
         let font = &fonts[text_style];
+        let add_glyph = |out: &mut Triangles, left_top: Pos2, glyph: super::text::UvRect| {
+            let mut left_top = left_top + glyph.offset;
+            left_top.x = font.round_to_pixel(left_top.x); // Pixel-perfection.
+            left_top.y = font.round_to_pixel(left_top.y); // Pixel-perfection.
+
+            let pos = Rect::from_min_max(left_top, left_top + glyph.size);
+            let uv = Rect::from_min_max(
+                pos2(glyph.min.0 as f32 / tex_w, glyph.min.1 as f32 / tex_h),
+                pos2(glyph.max.0 as f32 / tex_w, glyph.max.1 as f32 / tex_h),
+            );
+            out.add_rect_with_uv(pos, uv, color);
+        };
+
         let mut chars = galley.text.chars();
         for line in &galley.rows {
             let line_min_y = pos.y + line.y_min;
             let line_max_y = line_min_y + font.row_height();
             let is_line_visible = line_max_y >= clip_rect.min.y && line_min_y <= clip_rect.max.y;
 
-            for x_offset in line.x_offsets.iter().take(line.x_offsets.len() - 1) {
-                let c = chars.next().unwrap();
-
-                if self.options.coarse_tessellation_culling && !is_line_visible {
-                    // culling individual lines of text is important, since a single `Shape::Text`
-                    // can span hundreds of lines.
-                    continue;
+            if !line.glyphs.is_empty() {
+                // Shaped text, so paint the glyphs rather than the characters:
+                #[cfg(feature = "shaping")]
+                if !self.options.coarse_tessellation_culling || is_line_visible {
+                    for glyph in &line.glyphs {
+                        if let Some(uv_rect) =
+                            font.shaped_glyph_uv_rect(glyph.font_index, glyph.glyph_id)
+                        {
+                            add_glyph(out, pos + vec2(0.0, line.y_min) + glyph.pos, uv_rect);
+                        }
+                    }
                 }
+                for _ in 0..line.char_count_excluding_newline() {
+                    chars.next().unwrap();
+                }
+            } else {
+                for x_offset in line.x_offsets.iter().take(line.x_offsets.len() - 1) {
+                    let c = chars.next().unwrap();
+
+                    if self.options.coarse_tessellation_culling && !is_line_visible {
+                        // culling individual lines of text is important, since a single `Shape::Text`
+                        // can span hundreds of lines.
+                        continue;
+                    }
 
-                if let Some(glyph) = font.uv_rect(c) {
-                    let mut left_top = pos + glyph.offset + vec2(*x_offset, line.y_min);
-                    left_top.x = font.round_to_pixel(left_top.x); // Pixel-perfection.
-                    left_top.y = font.round_to_pixel(left_top.y); // Pixel-perfection.
-
-                    let pos = Rect::from_min_max(left_top, left_top + glyph.size);
-                    let uv = Rect::from_min_max(
-                        pos2(glyph.min.0 as f32 / tex_w, glyph.min.1 as f32 / tex_h),
-                        pos2(glyph.max.0 as f32 / tex_w, glyph.max.1 as f32 / tex_h),
-                    );
-                    out.add_rect_with_uv(pos, uv, color);
+                    if let Some(glyph) = font.uv_rect(c) {
+                        add_glyph(out, pos + vec2(*x_offset, line.y_min), glyph);
+                    }
                 }
             }
             if line.ends_with_newline {
@@ -656,6 +683,120 @@ impl Tessellator {
         }
         assert_eq!(chars.next(), None);
     }
+
+    /// Paint a [`Galley`] laid out from a [`super::text::LayoutJob`].
+    /// `default_color` is used for sections without a color.
+    fn tessellate_rich_text(
+        &mut self,
+        fonts: &Fonts,
+        pos: Pos2,
+        galley: &super::Galley,
+        default_color: Color32,
+        out: &mut Triangles,
+    ) {
+        use super::text::TextFormat;
+
+        let clip_rect = self.clip_rect.expand(2.0); // Some fudge to handle letters that are slightly larger than expected.
+
+        // First find where each visible character goes, and with what format:
+        let mut glyphs = vec![]; // (char, cell rect, section index)
+        let mut chars = galley.text.char_indices();
+        let mut section_index = 0;
+        for row in &galley.rows {
+            let row_min_y = pos.y + row.y_min;
+            let row_max_y = pos.y + row.y_max;
+            let is_row_visible = row_max_y >= clip_rect.min.y && row_min_y <= clip_rect.max.y;
+
+            for x_range in row.x_offsets.windows(2) {
+                let (byte_index, c) = chars.next().unwrap();
+                while galley.sections[section_index].byte_range.end <= byte_index {
+                    section_index += 1;
+                }
+                if self.options.coarse_tessellation_culling && !is_row_visible {
+                    continue;
+                }
+                let cell = Rect::from_min_max(
+                    pos2(pos.x + x_range[0], row_min_y),
+                    pos2(pos.x + x_range[1], row_max_y),
+                );
+                glyphs.push((c, cell, section_index));
+            }
+            if row.ends_with_newline {
+                let (_, newline) = chars.next().unwrap();
+                debug_assert_eq!(newline, '\n');
+            }
+        }
+        assert_eq!(chars.next(), None);
+
+        out.reserve_triangles(glyphs.len() * 4);
+        out.reserve_vertices(glyphs.len() * 8);
+
+        // Backgrounds go behind all the text:
+        for &(_, cell, section_index) in &glyphs {
+            let background = galley.sections[section_index].format.background;
+            if background != Color32::TRANSPARENT {
+                out.add_colored_rect(cell, background);
+            }
+        }
+
+        let tex_w = fonts.texture().width as f32;
+        let tex_h = fonts.texture().height as f32;
+
+        for &(c, cell, section_index) in &glyphs {
+            let format = &galley.sections[section_index].format;
+            let font = &fonts[format.style];
+            let color = format.color.unwrap_or(default_color);
+            if let Some(glyph) = font.uv_rect(c) {
+                // Align smaller text to the bottom of the row:
+                let mut left_top = pos2(cell.min.x, cell.max.y - font.row_height()) + glyph.offset;
+                left_top.x = font.round_to_pixel(left_top.x); // Pixel-perfection.
+                left_top.y = font.round_to_pixel(left_top.y); // Pixel-perfection.
+
+                let rect = Rect::from_min_max(left_top, left_top + glyph.size);
+                let uv = Rect::from_min_max(
+                    pos2(glyph.min.0 as f32 / tex_w, glyph.min.1 as f32 / tex_h),
+                    pos2(glyph.max.0 as f32 / tex_w, glyph.max.1 as f32 / tex_h),
+                );
+
+                let bold_offset = TextFormat::bold_offset(font.row_height());
+                let offsets: &[f32] = if format.bold {
+                    &[0.0, bold_offset]
+                } else {
+                    &[0.0]
+                };
+                for &offset in offsets {
+                    let first_vertex = out.vertices.len();
+                    out.add_rect_with_uv(rect.translate(vec2(offset, 0.0)), uv, color);
+                    if format.italics {
+                        for vertex in &mut out.vertices[first_vertex..] {
+                            vertex.pos.x += (cell.max.y - vertex.pos.y) * TextFormat::ITALICS_SKEW;
+                        }
+                    }
+                }
+            }
+        }
+
+        // Lines go on top of the text:
+        for &(_, cell, section_index) in &glyphs {
+            let format = &galley.sections[section_index].format;
+            let font = &fonts[format.style];
+            let underline = format.underline;
+            if underline.width > 0.0 {
+                let rect =
+                    Rect::from_min_max(pos2(cell.min.x, cell.max.y - underline.width), cell.max);
+                out.add_colored_rect(rect, underline.color);
+            }
+            let strikethrough = format.strikethrough;
+            if strikethrough.width > 0.0 {
+                let center_y = cell.max.y - 0.5 * font.row_height();
+                let rect = Rect::from_min_max(
+                    pos2(cell.min.x, center_y - 0.5 * strikethrough.width),
+                    pos2(cell.max.x, center_y + 0.5 * strikethrough.width),
+                );
+                out.add_colored_rect(rect, strikethrough.color);
+            }
+        }
+    }
 }
 
 /// Turns [`Shape`]:s into sets of triangles.
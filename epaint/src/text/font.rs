@@ -1,4 +1,7 @@
-use std::sync::Arc;
+use std::sync::{
+    atomic::{AtomicU64, Ordering::Relaxed},
+    Arc,
+};
 
 use {
     ahash::AHashMap,
@@ -12,9 +15,14 @@ use crate::{
 };
 use emath::{vec2, Vec2};
 
+#[cfg(feature = "shaping")]
+use super::shaping::ShapedParagraph;
+#[cfg(feature = "system_fonts")]
+use super::system_fonts::SystemFonts;
+
 // ----------------------------------------------------------------------------
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct UvRect {
     /// X/Y offset for nice rendering (unit: points).
     pub offset: Vec2,
@@ -53,6 +61,8 @@ impl Default for GlyphInfo {
 /// A specific font with a size.
 /// The interface uses points as the unit for everything.
 pub struct FontImpl {
+    /// The name in [`super::FontDefinitions::font_data`], or the file name of a system font.
+    name: String,
     rusttype_font: Arc<rusttype::Font<'static>>,
     /// Maximum character height
     scale_in_pixels: f32,
@@ -61,6 +71,12 @@ pub struct FontImpl {
     y_offset: f32,
     pixels_per_point: f32,
     glyph_info_cache: RwLock<AHashMap<char, GlyphInfo>>, // TODO: standard Mutex
+    /// Glyphs looked up by id, as given by the text shaper.
+    #[cfg(feature = "shaping")]
+    glyph_id_cache: RwLock<AHashMap<u16, GlyphInfo>>,
+    /// Used by the text shaper.
+    #[cfg(feature = "shaping")]
+    font_data: Option<Arc<super::fonts::FontData>>,
     atlas: Arc<Mutex<TextureAtlas>>,
 }
 
@@ -68,6 +84,7 @@ impl FontImpl {
     pub fn new(
         atlas: Arc<Mutex<TextureAtlas>>,
         pixels_per_point: f32,
+        name: String,
         rusttype_font: Arc<rusttype::Font<'static>>,
         scale_in_points: f32,
         y_offset: f32,
@@ -84,16 +101,77 @@ impl FontImpl {
         // let height_in_points = height_in_pixels / pixels_per_point;
 
         Self {
+            name,
             rusttype_font,
             scale_in_pixels,
             height_in_points,
             y_offset,
             pixels_per_point,
             glyph_info_cache: Default::default(),
+            #[cfg(feature = "shaping")]
+            glyph_id_cache: Default::default(),
+            #[cfg(feature = "shaping")]
+            font_data: None,
             atlas,
         }
     }
 
+    /// Another font of the same size, e.g. a fallback found among the system fonts.
+    #[cfg(feature = "system_fonts")]
+    fn with_same_size(&self, name: String, rusttype_font: Arc<rusttype::Font<'static>>) -> Self {
+        Self::new(
+            self.atlas.clone(),
+            self.pixels_per_point,
+            name,
+            rusttype_font,
+            self.scale_in_pixels / self.pixels_per_point,
+            self.y_offset,
+        )
+    }
+
+    /// Give the text shaper access to the font file.
+    #[cfg(feature = "shaping")]
+    pub(crate) fn with_font_data(mut self, font_data: Arc<super::fonts::FontData>) -> Self {
+        self.font_data = Some(font_data);
+        self
+    }
+
+    #[cfg(feature = "shaping")]
+    pub(crate) fn font_data(&self) -> Option<&[u8]> {
+        self.font_data.as_deref().map(|font_data| &font_data[..])
+    }
+
+    /// How many points one font unit (as used by the text shaper) is,
+    /// matching the scale used by `rusttype`.
+    #[cfg(feature = "shaping")]
+    pub(crate) fn points_per_font_unit(&self) -> f32 {
+        let v = self.rusttype_font.v_metrics_unscaled();
+        self.scale_in_pixels / (v.ascent - v.descent) / self.pixels_per_point
+    }
+
+    /// A glyph by its id in this font, as given by the text shaper.
+    ///
+    /// Glyphs that are also looked up by character share the same place in the [`TextureAtlas`].
+    #[cfg(feature = "shaping")]
+    fn glyph_info_by_id(&self, glyph_id: u16) -> GlyphInfo {
+        {
+            if let Some(glyph_info) = self.glyph_id_cache.read().get(&glyph_id) {
+                return *glyph_info;
+            }
+        }
+
+        let glyph = self.rusttype_font.glyph(rusttype::GlyphId(glyph_id));
+        let glyph_info = allocate_glyph(
+            &mut self.atlas.lock(),
+            glyph,
+            self.scale_in_pixels,
+            self.y_offset,
+            self.pixels_per_point,
+        );
+        self.glyph_id_cache.write().insert(glyph_id, glyph_info);
+        glyph_info
+    }
+
     /// `\n` will result in `None`
     fn glyph_info(&self, c: char) -> Option<GlyphInfo> {
         {
@@ -107,6 +185,9 @@ impl FontImpl {
         if glyph.id().0 == 0 {
             None
         } else {
+            #[cfg(feature = "shaping")]
+            let glyph_info = self.glyph_info_by_id(glyph.id().0);
+            #[cfg(not(feature = "shaping"))]
             let glyph_info = allocate_glyph(
                 &mut self.atlas.lock(),
                 glyph,
@@ -119,6 +200,13 @@ impl FontImpl {
         }
     }
 
+    /// Forget all glyphs, e.g. because the [`TextureAtlas`] was cleared.
+    pub(crate) fn clear_glyph_cache(&self) {
+        self.glyph_info_cache.write().clear();
+        #[cfg(feature = "shaping")]
+        self.glyph_id_cache.write().clear();
+    }
+
     pub fn pair_kerning(
         &self,
         last_glyph_id: rusttype::GlyphId,
@@ -138,6 +226,10 @@ impl FontImpl {
     pub fn pixels_per_point(&self) -> f32 {
         self.pixels_per_point
     }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
 }
 
 type FontIndex = usize;
@@ -149,15 +241,49 @@ fn is_chinese(c: char) -> bool {
         || (c >= '\u{2B740}' && c <= '\u{2B81F}')
 }
 
+/// How a glyph is found in the [`Font`] cache.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(crate) enum GlyphKey {
+    /// The glyph shown for a character.
+    Char(char),
+
+    /// A glyph found by the text shaper, by its id in one of the fonts.
+    #[cfg(feature = "shaping")]
+    Shaped {
+        font_index: FontIndex,
+        glyph_id: u16,
+    },
+}
+
+#[derive(Debug)]
+struct CachedGlyph {
+    font_index: FontIndex,
+    glyph_info: GlyphInfo,
+
+    /// The last frame the glyph was laid out or painted.
+    /// Atomic, so that it can be updated without write-locking the cache.
+    last_used: AtomicU64,
+}
+
 // TODO: rename?
 /// Wrapper over multiple `FontImpl` (e.g. a primary + fallbacks for emojis)
 #[derive(Default)]
 pub struct Font {
     fonts: Vec<Arc<FontImpl>>,
-    replacement_glyph: (FontIndex, GlyphInfo),
+    /// Shown for characters missing in all fonts.
+    replacement_char: char,
     pixels_per_point: f32,
     row_height: f32,
-    glyph_info_cache: RwLock<AHashMap<char, (FontIndex, GlyphInfo)>>,
+    glyph_info_cache: RwLock<AHashMap<GlyphKey, CachedGlyph>>,
+    /// Set by [`super::Fonts::begin_frame`].
+    current_frame: AtomicU64,
+    /// Where to look for characters missing in `fonts`.
+    #[cfg(feature = "system_fonts")]
+    system_fonts: Option<Arc<SystemFonts>>,
+    /// System fonts used for missing characters, in the order they were needed.
+    /// Their font indices come after those of `fonts`.
+    #[cfg(feature = "system_fonts")]
+    system_fallbacks: RwLock<Vec<Arc<FontImpl>>>,
 }
 
 impl Font {
@@ -171,25 +297,36 @@ impl Font {
 
         let mut slf = Self {
             fonts,
-            replacement_glyph: Default::default(),
+            replacement_char: Default::default(),
             pixels_per_point,
             row_height,
             glyph_info_cache: Default::default(),
+            current_frame: Default::default(),
+            #[cfg(feature = "system_fonts")]
+            system_fonts: None,
+            #[cfg(feature = "system_fonts")]
+            system_fallbacks: Default::default(),
         };
 
         const PRIMARY_REPLACEMENT_CHAR: char = '◻'; // white medium square
         const FALLBACK_REPLACEMENT_CHAR: char = '?'; // fallback for the fallback
 
-        let replacement_glyph = slf
+        slf.replacement_char = if slf
             .glyph_info_no_cache_or_fallback(PRIMARY_REPLACEMENT_CHAR)
-            .or_else(|| slf.glyph_info_no_cache_or_fallback(FALLBACK_REPLACEMENT_CHAR))
-            .unwrap_or_else(|| {
-                panic!(
-                    "Failed to find replacement characters {:?} or {:?}",
-                    PRIMARY_REPLACEMENT_CHAR, FALLBACK_REPLACEMENT_CHAR
-                )
-            });
-        slf.replacement_glyph = replacement_glyph;
+            .is_some()
+        {
+            PRIMARY_REPLACEMENT_CHAR
+        } else if slf
+            .glyph_info_no_cache_or_fallback(FALLBACK_REPLACEMENT_CHAR)
+            .is_some()
+        {
+            FALLBACK_REPLACEMENT_CHAR
+        } else {
+            panic!(
+                "Failed to find replacement characters {:?} or {:?}",
+                PRIMARY_REPLACEMENT_CHAR, FALLBACK_REPLACEMENT_CHAR
+            )
+        };
 
         // Preload the printable ASCII characters [32, 126] (which excludes control codes):
         const FIRST_ASCII: usize = 32; // 32 == space
@@ -202,6 +339,13 @@ impl Font {
         slf
     }
 
+    /// Use fonts installed on the system for characters missing in all of our fonts.
+    #[cfg(feature = "system_fonts")]
+    pub(crate) fn with_system_fonts(mut self, system_fonts: Arc<SystemFonts>) -> Self {
+        self.system_fonts = Some(system_fonts);
+        self
+    }
+
     pub fn round_to_pixel(&self, point: f32) -> f32 {
         (point * self.pixels_per_point).round() / self.pixels_per_point
     }
@@ -211,49 +355,242 @@ impl Font {
         self.row_height
     }
 
+    /// Where in the font texture the glyph for `c` is.
+    /// Marks the glyph as used this frame, so it isn't evicted from the [`TextureAtlas`].
+    ///
+    /// `None` for glyphs without pixels (e.g. space), and for characters that haven't been
+    /// laid out since they were evicted. This never adds glyphs to the texture,
+    /// so that the texture doesn't change while painting.
     pub fn uv_rect(&self, c: char) -> Option<UvRect> {
-        self.glyph_info_cache
-            .read()
-            .get(&c)
-            .and_then(|gi| gi.1.uv_rect)
+        self.cached_uv_rect(&GlyphKey::Char(c))
+    }
+
+    /// Like [`Self::uv_rect`], but for a glyph found by the text shaper (see [`super::ShapedGlyph`]).
+    #[cfg(feature = "shaping")]
+    pub fn shaped_glyph_uv_rect(&self, font_index: FontIndex, glyph_id: u16) -> Option<UvRect> {
+        self.cached_uv_rect(&GlyphKey::Shaped {
+            font_index,
+            glyph_id,
+        })
+    }
+
+    fn cached_uv_rect(&self, key: &GlyphKey) -> Option<UvRect> {
+        let cache = self.glyph_info_cache.read();
+        let cached = cache.get(key)?;
+        cached
+            .last_used
+            .store(self.current_frame.load(Relaxed), Relaxed);
+        cached.glyph_info.uv_rect
     }
 
     pub fn glyph_width(&self, c: char) -> f32 {
         self.glyph_info(c).1.advance_width
     }
 
+    /// The name of the font that `c` is shown with,
+    /// or `None` if no font has it (and the replacement character is shown instead).
+    ///
+    /// This is either a name from [`super::FontDefinitions::font_data`],
+    /// or the file name of a system font (with the `system_fonts` feature).
+    pub fn font_name_for_char(&self, c: char) -> Option<String> {
+        let (font_index, _) = self.find_glyph(c)?;
+        Some(self.font_impl(font_index).name().to_owned())
+    }
+
+    /// Add a glyph found by the text shaper to the [`TextureAtlas`] (if it isn't there already),
+    /// and mark it as used this frame.
+    ///
+    /// Only called while laying out text, so that the texture doesn't change while painting.
+    #[cfg(feature = "shaping")]
+    pub(crate) fn load_shaped_glyph(&self, font_index: FontIndex, glyph_id: u16) {
+        let key = GlyphKey::Shaped {
+            font_index,
+            glyph_id,
+        };
+        if let Some(cached) = self.glyph_info_cache.read().get(&key) {
+            cached
+                .last_used
+                .store(self.current_frame.load(Relaxed), Relaxed);
+            return;
+        }
+        let glyph_info = self.font_impl(font_index).glyph_info_by_id(glyph_id);
+        self.cache_glyph(key, (font_index, glyph_info));
+    }
+
+    /// All our fonts, including any system fonts used as fallbacks so far.
+    #[cfg(feature = "shaping")]
+    pub(crate) fn font_impls(&self) -> Vec<Arc<FontImpl>> {
+        #[allow(unused_mut)]
+        let mut font_impls = self.fonts.clone();
+        #[cfg(feature = "system_fonts")]
+        font_impls.extend(self.system_fallbacks.read().iter().cloned());
+        font_impls
+    }
+
+    fn font_impl(&self, font_index: FontIndex) -> Arc<FontImpl> {
+        if let Some(font_impl) = self.fonts.get(font_index) {
+            return font_impl.clone();
+        }
+        #[cfg(feature = "system_fonts")]
+        if let Some(font_impl) = self
+            .system_fallbacks
+            .read()
+            .get(font_index - self.fonts.len())
+        {
+            return font_impl.clone();
+        }
+        panic!("Font index {} out of range", font_index)
+    }
+
+    /// Which of the fonts `c` is shown with.
+    #[cfg(feature = "shaping")]
+    pub(crate) fn font_index(&self, c: char) -> FontIndex {
+        self.glyph_info(c).0
+    }
+
+    /// The font index and glyph id of the replacement character.
+    #[cfg(feature = "shaping")]
+    pub(crate) fn replacement_glyph(&self) -> (FontIndex, u16) {
+        let (font_index, glyph_info) = self.glyph_info(self.replacement_char);
+        (font_index, glyph_info.id.0)
+    }
+
     /// `\n` will (intentionally) show up as the replacement character.
     fn glyph_info(&self, c: char) -> (FontIndex, GlyphInfo) {
         {
-            if let Some(glyph_info) = self.glyph_info_cache.read().get(&c) {
-                return *glyph_info;
+            if let Some(cached) = self.glyph_info_cache.read().get(&GlyphKey::Char(c)) {
+                cached
+                    .last_used
+                    .store(self.current_frame.load(Relaxed), Relaxed);
+                return (cached.font_index, cached.glyph_info);
             }
         }
 
-        let font_index_glyph_info = self.glyph_info_no_cache_or_fallback(c);
-        let font_index_glyph_info = font_index_glyph_info.unwrap_or(self.replacement_glyph);
-        self.glyph_info_cache
-            .write()
-            .insert(c, font_index_glyph_info);
+        let font_index_glyph_info = self.find_glyph(c);
+        let font_index_glyph_info = font_index_glyph_info.unwrap_or_else(|| {
+            self.glyph_info_no_cache_or_fallback(self.replacement_char)
+                .unwrap_or_default()
+        });
+        self.cache_glyph(GlyphKey::Char(c), font_index_glyph_info);
         font_index_glyph_info
     }
 
+    /// Look for `c` in our fonts, and then among the system fonts.
+    fn find_glyph(&self, c: char) -> Option<(FontIndex, GlyphInfo)> {
+        let found = self.glyph_info_no_cache_or_fallback(c);
+        #[cfg(feature = "system_fonts")]
+        let found = found.or_else(|| self.glyph_info_from_system_fonts(c));
+        found
+    }
+
+    #[cfg(feature = "system_fonts")]
+    fn glyph_info_from_system_fonts(&self, c: char) -> Option<(FontIndex, GlyphInfo)> {
+        let system_fonts = self.system_fonts.as_ref()?;
+        let primary = self.fonts.first()?;
+
+        {
+            let fallbacks = self.system_fallbacks.read();
+            for (i, font_impl) in fallbacks.iter().enumerate() {
+                if let Some(glyph_info) = font_impl.glyph_info(c) {
+                    let font_index = self.fonts.len() + i;
+                    self.cache_glyph(GlyphKey::Char(c), (font_index, glyph_info));
+                    return Some((font_index, glyph_info));
+                }
+            }
+        }
+
+        let system_font = system_fonts.font_for_char(c)?;
+        let font_impl = primary.with_same_size(system_font.name, system_font.rusttype_font);
+        #[cfg(feature = "shaping")]
+        let font_impl = font_impl.with_font_data(system_font.font_data);
+        let glyph_info = font_impl.glyph_info(c)?;
+
+        let mut fallbacks = self.system_fallbacks.write();
+        fallbacks.push(Arc::new(font_impl));
+        let font_index = self.fonts.len() + fallbacks.len() - 1;
+        self.cache_glyph(GlyphKey::Char(c), (font_index, glyph_info));
+        Some((font_index, glyph_info))
+    }
+
     fn glyph_info_no_cache_or_fallback(&self, c: char) -> Option<(FontIndex, GlyphInfo)> {
         for (font_index, font_impl) in self.fonts.iter().enumerate() {
             if let Some(glyph_info) = font_impl.glyph_info(c) {
-                self.glyph_info_cache
-                    .write()
-                    .insert(c, (font_index, glyph_info));
+                self.cache_glyph(GlyphKey::Char(c), (font_index, glyph_info));
                 return Some((font_index, glyph_info));
             }
         }
         None
     }
 
+    fn cache_glyph(&self, key: GlyphKey, (font_index, glyph_info): (FontIndex, GlyphInfo)) {
+        let cached = CachedGlyph {
+            font_index,
+            glyph_info,
+            last_used: AtomicU64::new(self.current_frame.load(Relaxed)),
+        };
+        self.glyph_info_cache.write().insert(key, cached);
+    }
+
+    /// Called by [`super::Fonts::begin_frame`].
+    pub(crate) fn begin_frame(&self, frame_nr: u64) {
+        self.current_frame.store(frame_nr, Relaxed);
+    }
+
+    /// Where in the font texture the glyphs used since `min_frame` are.
+    pub(crate) fn used_uv_rects(&self, min_frame: u64) -> Vec<UvRect> {
+        self.glyph_info_cache
+            .read()
+            .values()
+            .filter(|cached| cached.last_used.load(Relaxed) >= min_frame)
+            .filter_map(|cached| cached.glyph_info.uv_rect)
+            .collect()
+    }
+
+    /// Forget all glyphs, including those in the underlying `FontImpl`:s.
+    ///
+    /// Returns the glyphs used since `min_frame` (and when they were last used),
+    /// so they can be added back with [`Self::reload_glyphs`] once the [`TextureAtlas`] is cleared.
+    pub(crate) fn forget_glyphs(&self, min_frame: u64) -> Vec<(GlyphKey, u64)> {
+        let mut cache = self.glyph_info_cache.write();
+        let used = cache
+            .iter()
+            .map(|(&key, cached)| (key, cached.last_used.load(Relaxed)))
+            .filter(|&(_, last_used)| last_used >= min_frame)
+            .collect();
+        cache.clear();
+        for font_impl in &self.fonts {
+            font_impl.clear_glyph_cache();
+        }
+        #[cfg(feature = "system_fonts")]
+        for font_impl in self.system_fallbacks.read().iter() {
+            font_impl.clear_glyph_cache();
+        }
+        used
+    }
+
+    /// Add back the glyphs returned by [`Self::forget_glyphs`].
+    pub(crate) fn reload_glyphs(&self, glyphs: &[(GlyphKey, u64)]) {
+        for &(key, last_used) in glyphs {
+            match key {
+                GlyphKey::Char(c) => {
+                    self.glyph_info(c);
+                }
+                #[cfg(feature = "shaping")]
+                GlyphKey::Shaped {
+                    font_index,
+                    glyph_id,
+                } => self.load_shaped_glyph(font_index, glyph_id),
+            }
+            if let Some(cached) = self.glyph_info_cache.read().get(&key) {
+                cached.last_used.store(last_used, Relaxed);
+            }
+        }
+    }
+
     /// Typeset the given text onto one row.
     /// Assumes there are no `\n` in the text.
     /// Return `x_offsets`, one longer than the number of characters in the text.
-    fn layout_single_row_fragment(&self, text: &str) -> Vec<f32> {
+    pub(crate) fn layout_single_row_fragment(&self, text: &str) -> Vec<f32> {
         let mut x_offsets = Vec::with_capacity(text.chars().count() + 1);
         x_offsets.push(0.0);
 
@@ -263,7 +600,7 @@ impl Font {
         for c in text.chars() {
             if !self.fonts.is_empty() {
                 let (font_index, glyph_info) = self.glyph_info(c);
-                let font_impl = &self.fonts[font_index];
+                let font_impl = self.font_impl(font_index);
 
                 if let Some(last_glyph_id) = last_glyph_id {
                     cursor_x_in_points += font_impl.pair_kerning(last_glyph_id, glyph_info.id)
@@ -284,18 +621,26 @@ impl Font {
     /// Always returns exactly one `Row` in the `Galley`.
     pub fn layout_single_line(&self, text: String) -> Galley {
         let x_offsets = self.layout_single_row_fragment(&text);
-        let row = Row {
+        #[allow(unused_mut)]
+        let mut row = Row {
             x_offsets,
             y_min: 0.0,
             y_max: self.row_height(),
             ends_with_newline: false,
+            glyphs: vec![],
         };
+        #[cfg(feature = "shaping")]
+        if !self.fonts.is_empty() && !text.contains('\n') {
+            let shaped = ShapedParagraph::new(self, &text);
+            shaped.shape_row(self, &mut row, 0..text.chars().count());
+        }
         let width = row.max_x();
         let size = vec2(width, self.row_height());
         let galley = Galley {
             text,
             rows: vec![row],
             size,
+            sections: vec![],
         };
         galley.sanity_check();
         galley
@@ -360,6 +705,7 @@ impl Font {
                 y_min: cursor_y,
                 y_max: cursor_y + row_height,
                 ends_with_newline: false,
+                glyphs: vec![],
             });
         }
 
@@ -369,7 +715,12 @@ impl Font {
         }
         let size = vec2(widest_row, rows.last().unwrap().y_max);
 
-        let galley = Galley { text, rows, size };
+        let galley = Galley {
+            text,
+            rows,
+            size,
+            sections: vec![],
+        };
         galley.sanity_check();
         galley
     }
@@ -380,7 +731,7 @@ impl Font {
     fn layout_paragraph_max_width(
         &self,
         text: &str,
-        mut first_row_indentation: f32,
+        first_row_indentation: f32,
         max_width_in_points: f32,
     ) -> Vec<Row> {
         if text.is_empty() {
@@ -389,11 +740,48 @@ impl Font {
                 y_min: 0.0,
                 y_max: self.row_height(),
                 ends_with_newline: false,
+                glyphs: vec![],
             }];
         }
 
+        #[cfg(feature = "shaping")]
+        if !self.fonts.is_empty() {
+            let shaped = ShapedParagraph::new(self, text);
+            let mut rows = self.wrap_paragraph(
+                text,
+                &shaped.x_offsets,
+                first_row_indentation,
+                max_width_in_points,
+            );
+            let mut row_start = 0;
+            for row in &mut rows {
+                let row_end = row_start + row.char_count_excluding_newline();
+                shaped.shape_row(self, row, row_start..row_end);
+                row_start = row_end;
+            }
+            return rows;
+        }
+
         let full_x_offsets = self.layout_single_row_fragment(text);
+        self.wrap_paragraph(
+            text,
+            &full_x_offsets,
+            first_row_indentation,
+            max_width_in_points,
+        )
+    }
 
+    /// Break a paragraph into rows no wider than `max_width_in_points`.
+    ///
+    /// `full_x_offsets` is the start of each character in `text` (and the end of the last one),
+    /// as if it was all on one row. All rows get the height of this font.
+    pub(crate) fn wrap_paragraph(
+        &self,
+        text: &str,
+        full_x_offsets: &[f32],
+        mut first_row_indentation: f32,
+        max_width_in_points: f32,
+    ) -> Vec<Row> {
         let mut row_start_x = 0.0; // NOTE: BEFORE the `first_row_indentation`.
 
         let mut cursor_y = 0.0;
@@ -419,6 +807,7 @@ impl Font {
                         y_min: cursor_y,
                         y_max: cursor_y + self.row_height(),
                         ends_with_newline: false,
+                        glyphs: vec![],
                     };
                     row.sanity_check();
                     out_rows.push(row);
@@ -435,6 +824,7 @@ impl Font {
                         y_min: cursor_y,
                         y_max: cursor_y + self.row_height(),
                         ends_with_newline: false,
+                        glyphs: vec![],
                     };
                     row.sanity_check();
                     out_rows.push(row);
@@ -458,6 +848,7 @@ impl Font {
                 y_min: cursor_y,
                 y_max: cursor_y + self.row_height(),
                 ends_with_newline: false,
+                glyphs: vec![],
             };
             row.sanity_check();
             out_rows.push(row);
@@ -6,15 +6,18 @@ use std::{
 
 use crate::{
     mutex::Mutex,
-    text::font::{Font, FontImpl},
+    stats::FontAtlasStats,
+    text::{
+        font::{Font, FontImpl, UvRect},
+        Galley, LayoutJob, Row, TextFormat,
+    },
     Texture, TextureAtlas,
 };
+use emath::vec2;
 
 // TODO: rename
 /// One of a few categories of styles of text, e.g. body, button or heading.
 #[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
-#[cfg_attr(feature = "persistence", serde(rename_all = "snake_case"))]
 pub enum TextStyle {
     /// Used when small text is needed.
     Small,
@@ -26,9 +29,15 @@ pub enum TextStyle {
     Heading,
     /// Same size as `Body`, but used when monospace is important (for aligning number, code snippets, etc).
     Monospace,
+    /// A style of your own, e.g. `TextStyle::Custom("title")`.
+    ///
+    /// Give it a family and size in [`FontDefinitions::family_and_size`] before using it.
+    /// Don't use the name of a built-in style (e.g. `"body"`), as it would be loaded as that style.
+    Custom(&'static str),
 }
 
 impl TextStyle {
+    /// The built-in styles. See [`FontDefinitions::family_and_size`] for all the styles in use.
     pub fn all() -> impl Iterator<Item = TextStyle> {
         [
             TextStyle::Small,
@@ -44,13 +53,85 @@ impl TextStyle {
 
 /// Which style of font: [`Monospace`][`FontFamily::Monospace`] or [`Proportional`][`FontFamily::Proportional`].
 #[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
-#[cfg_attr(feature = "persistence", serde(rename_all = "snake_case"))]
 pub enum FontFamily {
     /// A font where each character is the same width (`w` is the same width as `i`).
     Monospace,
     /// A font where some characters are wider than other (e.g. 'w' is wider than 'i').
     Proportional,
+    /// A family of your own, e.g. `FontFamily::Custom("serif")`.
+    ///
+    /// Give it some fonts in [`FontDefinitions::fonts_for_family`] before using it.
+    /// Don't use the name of a built-in family (e.g. `"monospace"`), as it would be loaded as that family.
+    Custom(&'static str),
+}
+
+// Serialized as strings (e.g. `"body"` or `"title"`), so they can be used as keys in JSON maps.
+#[cfg(feature = "persistence")]
+impl serde::Serialize for TextStyle {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(match self {
+            Self::Small => "small",
+            Self::Body => "body",
+            Self::Button => "button",
+            Self::Heading => "heading",
+            Self::Monospace => "monospace",
+            Self::Custom(name) => name,
+        })
+    }
+}
+
+#[cfg(feature = "persistence")]
+impl<'de> serde::Deserialize<'de> for TextStyle {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = <String as serde::Deserialize>::deserialize(deserializer)?;
+        Ok(match name.as_str() {
+            "small" => Self::Small,
+            "body" => Self::Body,
+            "button" => Self::Button,
+            "heading" => Self::Heading,
+            "monospace" => Self::Monospace,
+            _ => Self::Custom(leak_name(name)),
+        })
+    }
+}
+
+#[cfg(feature = "persistence")]
+impl serde::Serialize for FontFamily {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(match self {
+            Self::Monospace => "monospace",
+            Self::Proportional => "proportional",
+            Self::Custom(name) => name,
+        })
+    }
+}
+
+#[cfg(feature = "persistence")]
+impl<'de> serde::Deserialize<'de> for FontFamily {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = <String as serde::Deserialize>::deserialize(deserializer)?;
+        Ok(match name.as_str() {
+            "monospace" => Self::Monospace,
+            "proportional" => Self::Proportional,
+            _ => Self::Custom(leak_name(name)),
+        })
+    }
+}
+
+/// `&'static str` can't be deserialized, so we leak the names of custom styles and families.
+/// Each distinct name is only leaked once, so loading the same styles over and over doesn't leak memory.
+#[cfg(feature = "persistence")]
+fn leak_name(name: String) -> &'static str {
+    static NAMES: std::sync::Mutex<std::collections::BTreeSet<&'static str>> =
+        std::sync::Mutex::new(std::collections::BTreeSet::new());
+
+    let mut names = NAMES.lock().unwrap();
+    if let Some(name) = names.get(name.as_str()) {
+        return name;
+    }
+    let name: &'static str = Box::leak(name.into_boxed_str());
+    names.insert(name);
+    name
 }
 
 /// The data of a `.ttf` or `.otf` file.
@@ -79,6 +160,25 @@ fn rusttype_font_from_font_data(name: &str, data: &FontData) -> rusttype::Font<'
 ///     (egui::FontFamily::Proportional, 32.0));
 /// ctx.set_fonts(fonts);
 /// ```
+///
+/// You can also add text styles and font families of your own:
+///
+/// ``` ignore
+/// # let mut ctx = egui::CtxRef::default();
+/// # let ui = &mut egui::Ui::__test();
+/// const TITLE: egui::TextStyle = egui::TextStyle::Custom("title");
+/// const SERIF: egui::FontFamily = egui::FontFamily::Custom("serif");
+///
+/// let mut fonts = egui::FontDefinitions::default();
+/// fonts.font_data.insert(
+///     "MySerif".to_owned(),
+///     std::borrow::Cow::Borrowed(include_bytes!("MySerif.ttf")));
+/// fonts.fonts_for_family.insert(SERIF, vec!["MySerif".to_owned()]);
+/// fonts.family_and_size.insert(TITLE, (SERIF, 48.0));
+/// ctx.set_fonts(fonts);
+///
+/// ui.add(egui::Label::new("My title").text_style(TITLE));
+/// ```
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
 #[cfg_attr(feature = "persistence", serde(default))]
@@ -175,6 +275,23 @@ impl Default for FontDefinitions {
     }
 }
 
+/// Glyphs that haven't been laid out or painted for this many frames
+/// are evicted from the font atlas when it gets too big.
+/// This is also the minimum number of frames between evictions.
+const MAX_UNUSED_FRAMES: u64 = 300;
+
+/// Evict unused glyphs when the font texture gets taller than this (in pixels).
+const EVICTION_HEIGHT: usize = 2048;
+
+#[derive(Default)]
+struct AtlasUsage {
+    frame_nr: u64,
+    /// The last time we evicted glyphs, or found that doing so wouldn't help.
+    last_eviction_frame_nr: u64,
+    num_evicted: usize,
+    num_evictions: usize,
+}
+
 /// The collection of fonts used by Egui.
 ///
 /// Note: `Fonts::default()` is invalid (missing `pixels_per_point`).
@@ -187,6 +304,7 @@ pub struct Fonts {
     /// Copy of the texture in the texture atlas.
     /// This is so we can return a reference to it (the texture atlas is behind a lock).
     buffered_texture: Mutex<Arc<Texture>>,
+    atlas_usage: Mutex<AtlasUsage>,
 }
 
 impl Fonts {
@@ -194,17 +312,13 @@ impl Fonts {
         // We want an atlas big enough to be able to include all the Emojis in the `TextStyle::Heading`,
         // so we can show the Emoji picker demo window.
         let mut atlas = TextureAtlas::new(2048, 64);
-
-        {
-            // Make the top left pixel fully white:
-            let pos = atlas.allocate((1, 1));
-            assert_eq!(pos, (0, 0));
-            atlas.texture_mut()[pos] = 255;
-        }
+        allocate_white_pixel(&mut atlas);
 
         let atlas = Arc::new(Mutex::new(atlas));
 
         let mut font_impl_cache = FontImplCache::new(atlas.clone(), pixels_per_point, &definitions);
+        #[cfg(feature = "system_fonts")]
+        let system_fonts = super::system_fonts::SystemFonts::global();
 
         let fonts = definitions
             .family_and_size
@@ -219,7 +333,10 @@ impl Fonts {
                     .map(|font_name| font_impl_cache.font_impl(font_name, scale_in_points))
                     .collect();
 
-                (text_style, Font::new(fonts))
+                let font = Font::new(fonts);
+                #[cfg(feature = "system_fonts")]
+                let font = font.with_system_fonts(system_fonts.clone());
+                (text_style, font)
             })
             .collect();
 
@@ -239,6 +356,229 @@ impl Fonts {
             fonts,
             atlas,
             buffered_texture: Default::default(), //atlas.lock().texture().clone();
+            atlas_usage: Default::default(),
+        }
+    }
+
+    /// Call at the start of each frame.
+    ///
+    /// If the font texture has grown too big, this evicts the glyphs that haven't been painted for a while.
+    /// This clears the texture atlas and adds back the glyphs that are still in use,
+    /// so text laid out in previous frames can still be painted.
+    pub fn begin_frame(&self) {
+        let mut usage = self.atlas_usage.lock();
+        usage.frame_nr += 1;
+        for font in self.fonts.values() {
+            font.begin_frame(usage.frame_nr);
+        }
+
+        let too_big = self.atlas.lock().texture().height > EVICTION_HEIGHT;
+        if too_big && usage.frame_nr > usage.last_eviction_frame_nr + MAX_UNUSED_FRAMES {
+            self.evict_unused_glyphs(&mut usage);
+        }
+    }
+
+    fn evict_unused_glyphs(&self, usage: &mut AtlasUsage) {
+        // Keep the glyphs used in the last `MAX_UNUSED_FRAMES` frames (including this one):
+        let min_frame = (usage.frame_nr + 1).saturating_sub(MAX_UNUSED_FRAMES);
+        usage.last_eviction_frame_nr = usage.frame_nr;
+
+        // Don't bother if the glyphs we keep would still make the texture too big:
+        let mut kept: Vec<UvRect> = self
+            .fonts
+            .values()
+            .flat_map(|font| font.used_uv_rects(min_frame))
+            .collect();
+        kept.sort_by_key(|uv_rect| (uv_rect.min.1, uv_rect.min.0));
+        kept.dedup_by_key(|uv_rect| uv_rect.min); // e.g. the replacement glyph of many characters
+        let white_pixel = (1, 1);
+        let sizes = kept.iter().map(|uv_rect| {
+            let (w, h) = (uv_rect.max.0 - uv_rect.min.0, uv_rect.max.1 - uv_rect.min.1);
+            (w as usize, h as usize)
+        });
+        let height = self
+            .atlas
+            .lock()
+            .height_after_clear(std::iter::once(white_pixel).chain(sizes));
+        if height > EVICTION_HEIGHT {
+            return;
+        }
+
+        let num_glyphs_before = self.atlas.lock().num_allocations();
+
+        let used_glyphs: Vec<_> = self
+            .fonts
+            .values()
+            .map(|font| font.forget_glyphs(min_frame))
+            .collect();
+        {
+            let mut atlas = self.atlas.lock();
+            atlas.clear();
+            allocate_white_pixel(&mut atlas);
+        }
+        for (font, glyphs) in self.fonts.values().zip(&used_glyphs) {
+            font.reload_glyphs(glyphs);
+        }
+
+        let atlas = self.atlas.lock();
+        usage.num_evicted += num_glyphs_before.saturating_sub(atlas.num_allocations());
+        usage.num_evictions += 1;
+    }
+
+    /// Lay out rich text with several sections of different formatting,
+    /// wrapping it at `max_width_in_points`.
+    ///
+    /// Always returns at least one row.
+    /// Each row is as high as the highest [`TextStyle`] in it, and smaller text is aligned to the bottom of the row.
+    pub fn layout_job(&self, job: LayoutJob, max_width_in_points: f32) -> Galley {
+        self.layout_job_with_indentation_and_max_width(job, 0.0, max_width_in_points)
+    }
+
+    /// Like [`Self::layout_job`], but with extra space before the very first character (in points).
+    pub fn layout_job_with_indentation_and_max_width(
+        &self,
+        mut job: LayoutJob,
+        first_row_indentation: f32,
+        max_width_in_points: f32,
+    ) -> Galley {
+        job.sanitize();
+        let LayoutJob { text, sections } = job;
+
+        let mut cursor_y = 0.0;
+        let mut rows = Vec::new();
+
+        let mut paragraph_start = 0;
+        let mut section_index = 0; // The section containing `paragraph_start`.
+
+        while paragraph_start < text.len() {
+            let next_newline = text[paragraph_start..].find('\n');
+            let paragraph_end = next_newline
+                .map(|newline| paragraph_start + newline)
+                .unwrap_or_else(|| text.len());
+            let paragraph_text = &text[paragraph_start..paragraph_end];
+
+            // Lay out the paragraph as if it was all on one row, one section at a time:
+            let mut full_x_offsets = vec![0.0];
+            let mut char_styles = vec![];
+            for section in &sections[section_index..] {
+                if paragraph_end <= section.byte_range.start {
+                    break;
+                }
+                let start = section.byte_range.start.max(paragraph_start);
+                let end = section.byte_range.end.min(paragraph_end);
+                if end <= start {
+                    continue;
+                }
+                let piece = &text[start..end];
+                let font = &self[section.format.style];
+                let bold_offset = if section.format.bold {
+                    TextFormat::bold_offset(font.row_height())
+                } else {
+                    0.0
+                };
+                let start_x = *full_x_offsets.last().unwrap();
+                let piece_x_offsets = font.layout_single_row_fragment(piece);
+                for (i, x) in piece_x_offsets.iter().enumerate().skip(1) {
+                    full_x_offsets.push(start_x + x + i as f32 * bold_offset);
+                    char_styles.push(section.format.style);
+                }
+            }
+
+            let font = &self[sections[section_index].format.style];
+            let indentation = if rows.is_empty() {
+                first_row_indentation
+            } else {
+                0.0
+            };
+            let mut paragraph_rows = if paragraph_text.is_empty() {
+                vec![Row {
+                    x_offsets: vec![indentation],
+                    y_min: 0.0,
+                    y_max: font.row_height(),
+                    ends_with_newline: false,
+                    glyphs: vec![],
+                }]
+            } else {
+                font.wrap_paragraph(
+                    paragraph_text,
+                    &full_x_offsets,
+                    indentation,
+                    max_width_in_points,
+                )
+            };
+
+            // Make each row as high as the highest character in it:
+            let mut char_index = 0;
+            for row in &mut paragraph_rows {
+                let num_chars = row.char_count_excluding_newline();
+                let min_height = if num_chars == 0 {
+                    font.row_height()
+                } else {
+                    0.0
+                };
+                let height = char_styles[char_index..char_index + num_chars]
+                    .iter()
+                    .map(|&style| self[style].row_height())
+                    .fold(min_height, f32::max);
+                char_index += num_chars;
+
+                row.y_min = cursor_y;
+                row.y_max = cursor_y + height;
+                cursor_y = self.round_to_pixel(row.y_max);
+            }
+            paragraph_rows.last_mut().unwrap().ends_with_newline = next_newline.is_some();
+            rows.append(&mut paragraph_rows);
+
+            paragraph_start = paragraph_end + 1;
+            while section_index + 1 < sections.len()
+                && sections[section_index].byte_range.end <= paragraph_start
+            {
+                section_index += 1;
+            }
+        }
+
+        if text.is_empty() || text.ends_with('\n') {
+            let font = &self[sections.last().unwrap().format.style];
+            rows.push(Row {
+                x_offsets: vec![0.0],
+                y_min: cursor_y,
+                y_max: cursor_y + font.row_height(),
+                ends_with_newline: false,
+                glyphs: vec![],
+            });
+        }
+
+        let mut widest_row = 0.0;
+        for row in &rows {
+            widest_row = row.max_x().max(widest_row);
+        }
+        let size = vec2(widest_row, rows.last().unwrap().y_max);
+
+        let galley = Galley {
+            text,
+            rows,
+            size,
+            sections,
+        };
+        galley.sanity_check();
+        galley
+    }
+
+    fn round_to_pixel(&self, point: f32) -> f32 {
+        (point * self.pixels_per_point).round() / self.pixels_per_point
+    }
+
+    /// How full the font atlas is.
+    pub fn atlas_stats(&self) -> FontAtlasStats {
+        let atlas = self.atlas.lock();
+        let usage = self.atlas_usage.lock();
+        FontAtlasStats {
+            width: atlas.texture().width,
+            height: atlas.texture().height,
+            used_height: atlas.used_height(),
+            num_glyphs: atlas.num_allocations().saturating_sub(1), // Don't count the white pixel
+            num_evicted: usage.num_evicted,
+            num_evictions: usage.num_evictions,
         }
     }
 
@@ -266,16 +606,30 @@ impl std::ops::Index<TextStyle> for Fonts {
     type Output = Font;
 
     fn index(&self, text_style: TextStyle) -> &Font {
-        &self.fonts[&text_style]
+        self.fonts.get(&text_style).unwrap_or_else(|| {
+            panic!(
+                "TextStyle::{:?} is not in FontDefinitions::family_and_size",
+                text_style
+            )
+        })
     }
 }
 
+/// Make the top left pixel fully white.
+fn allocate_white_pixel(atlas: &mut TextureAtlas) {
+    let pos = atlas.allocate((1, 1));
+    assert_eq!(pos, (0, 0));
+    atlas.texture_mut()[pos] = 255;
+}
+
 // ----------------------------------------------------------------------------
 
 struct FontImplCache {
     atlas: Arc<Mutex<TextureAtlas>>,
     pixels_per_point: f32,
     rusttype_fonts: std::collections::BTreeMap<String, Arc<rusttype::Font<'static>>>,
+    #[cfg(feature = "shaping")]
+    font_data: std::collections::BTreeMap<String, Arc<FontData>>,
 
     /// Map font names and size to the cached `FontImpl`.
     /// Can't have f32 in a HashMap or BTreeMap, so let's do a linear search
@@ -303,6 +657,12 @@ impl FontImplCache {
             atlas,
             pixels_per_point,
             rusttype_fonts,
+            #[cfg(feature = "shaping")]
+            font_data: definitions
+                .font_data
+                .iter()
+                .map(|(name, font_data)| (name.clone(), Arc::new(font_data.clone())))
+                .collect(),
             cache: Default::default(),
         }
     }
@@ -334,15 +694,226 @@ impl FontImplCache {
             scale_in_points
         };
 
-        let font_impl = Arc::new(FontImpl::new(
+        let font_impl = FontImpl::new(
             self.atlas.clone(),
             self.pixels_per_point,
+            font_name.to_owned(),
             self.rusttype_font(font_name),
             scale_in_points,
             y_offset,
-        ));
+        );
+        #[cfg(feature = "shaping")]
+        let font_impl = match self.font_data.get(font_name) {
+            Some(font_data) => font_impl.with_font_data(font_data.clone()),
+            None => font_impl,
+        };
+        let font_impl = Arc::new(font_impl);
         self.cache
             .push((font_name.to_owned(), scale_in_points, font_impl.clone()));
         font_impl
     }
 }
+
+#[test]
+fn test_custom_text_styles() {
+    const TITLE: TextStyle = TextStyle::Custom("title");
+    const CODE: FontFamily = FontFamily::Custom("code");
+
+    let mut definitions = FontDefinitions::default();
+    definitions
+        .fonts_for_family
+        .insert(CODE, vec!["ProggyClean".to_owned()]);
+    definitions.family_and_size.insert(TITLE, (CODE, 26.0));
+    let fonts = Fonts::from_definitions(1.0, definitions);
+
+    assert_eq!(fonts[TITLE].row_height(), 26.0);
+    assert_eq!(fonts[TextStyle::Heading].row_height(), 20.0); // Unchanged
+    assert_eq!(
+        fonts[TITLE].font_name_for_char('a').as_deref(),
+        Some("ProggyClean")
+    );
+}
+
+#[test]
+fn test_layout_job() {
+    use crate::text::LayoutSection;
+
+    let fonts = Fonts::from_definitions(1.0, FontDefinitions::default());
+    let body_height = fonts[TextStyle::Body].row_height();
+    let heading_height = fonts[TextStyle::Heading].row_height();
+    assert!(body_height < heading_height);
+
+    // Each row is as high as its highest text:
+    let mut job = LayoutJob::default();
+    job.append("small ", TextFormat::style(TextStyle::Body));
+    job.append("BIG", TextFormat::style(TextStyle::Heading));
+    job.append("\nsmall again", TextFormat::style(TextStyle::Body));
+    let galley = fonts.layout_job(job, f32::INFINITY);
+    assert_eq!(galley.rows.len(), 2);
+    assert!(galley.rows[0].ends_with_newline);
+    assert_eq!(galley.rows[0].y_max - galley.rows[0].y_min, heading_height);
+    assert_eq!(galley.rows[1].y_min, galley.rows[0].y_max);
+    assert_eq!(galley.rows[1].y_max - galley.rows[1].y_min, body_height);
+    assert_eq!(galley.size.y, heading_height + body_height);
+
+    // Wrapping where the text style changes:
+    let mut job = LayoutJob::default();
+    job.append("small text ", TextFormat::style(TextStyle::Body));
+    job.append("BIG", TextFormat::style(TextStyle::Heading));
+    let small_width = fonts.layout_job(job.clone(), f32::INFINITY).rows[0].x_offsets[11];
+    let galley = fonts.layout_job(job, small_width + 1.0);
+    assert_eq!(galley.rows.len(), 2);
+    assert_eq!(galley.rows[0].x_offsets.len(), "small text ".len() + 1);
+    assert_eq!(galley.rows[0].y_max - galley.rows[0].y_min, body_height);
+    assert_eq!(galley.rows[1].x_offsets.len(), "BIG".len() + 1);
+    assert_eq!(galley.rows[1].y_max - galley.rows[1].y_min, heading_height);
+
+    // Bold characters are wider:
+    let bold = TextFormat {
+        bold: true,
+        ..Default::default()
+    };
+    let plain_galley = fonts.layout_job(
+        LayoutJob::single_section("bold", TextFormat::default()),
+        f32::INFINITY,
+    );
+    let bold_galley = fonts.layout_job(LayoutJob::single_section("bold", bold), f32::INFINITY);
+    let plain_x = &plain_galley.rows[0].x_offsets;
+    let bold_x = &bold_galley.rows[0].x_offsets;
+    assert_eq!(plain_x[0], bold_x[0]);
+    for i in 1..plain_x.len() {
+        assert!(bold_x[i] - bold_x[i - 1] > plain_x[i] - plain_x[i - 1]);
+    }
+
+    // Gaps are filled, and bad ranges fixed:
+    let heading = TextFormat::style(TextStyle::Heading);
+    let job = LayoutJob {
+        text: "aé b".to_owned(), // 'é' is bytes 1..3
+        sections: vec![
+            LayoutSection {
+                byte_range: 2..4, // Starts inside 'é'
+                format: heading,
+            },
+            LayoutSection {
+                byte_range: 3..100, // Overlaps the previous section, and goes past the end
+                format: bold,
+            },
+        ],
+    };
+    let galley = fonts.layout_job(job, f32::INFINITY);
+    let sections: Vec<_> = galley
+        .sections
+        .iter()
+        .map(|section| (section.byte_range.clone(), section.format))
+        .collect();
+    assert_eq!(
+        sections,
+        vec![(0..1, TextFormat::default()), (1..4, heading), (4..5, bold)]
+    );
+}
+
+#[test]
+fn test_evict_unused_glyphs() {
+    let fonts = Fonts::from_definitions(4.0, FontDefinitions::default());
+    let font = &fonts[TextStyle::Heading];
+
+    // Lay out lots of emojis, growing the font texture past the eviction height:
+    fonts.begin_frame();
+    let emojis: String = (0x2600..0x27C0)
+        .chain(0x1F300..0x1F700)
+        .filter_map(std::char::from_u32)
+        .collect();
+    font.layout_single_line(emojis);
+    assert!(fonts.atlas_stats().height > EVICTION_HEIGHT);
+
+    // Then only paint the letter 'a':
+    for _ in 0..MAX_UNUSED_FRAMES {
+        fonts.begin_frame();
+        assert!(font.uv_rect('a').is_some());
+    }
+
+    let stats = fonts.atlas_stats();
+    assert_eq!(stats.num_evictions, 1);
+    assert!(stats.num_evicted > 500);
+    assert!(stats.height <= EVICTION_HEIGHT);
+    assert!(font.uv_rect('a').is_some());
+    assert!(
+        font.uv_rect('🌍').is_none(),
+        "painting doesn't add glyphs to the texture"
+    );
+    font.layout_single_line("🌍".to_owned());
+    assert!(
+        font.uv_rect('🌍').is_some(),
+        "evicted glyphs are added back when laid out"
+    );
+}
+
+#[cfg(feature = "shaping")]
+#[test]
+fn test_evict_unused_shaped_glyphs() {
+    use crate::{Color32, Shape, TessellationOptions};
+    use emath::{pos2, Rect};
+
+    let fonts = Fonts::from_definitions(4.0, FontDefinitions::default());
+    let font = &fonts[TextStyle::Heading];
+    let num_vertices = |galley: &Galley| {
+        let shape = Shape::Text {
+            pos: pos2(0.0, 0.0),
+            galley: galley.clone(),
+            text_style: TextStyle::Heading,
+            color: Color32::WHITE,
+        };
+        let triangles = crate::tessellator::tessellate_shapes(
+            vec![(Rect::everything(), shape)],
+            TessellationOptions::default(),
+            &fonts,
+        );
+        triangles[0].1.vertices.len()
+    };
+
+    fonts.begin_frame();
+    let emojis: String = (0x2600..0x27C0)
+        .chain(0x1F300..0x1F700)
+        .filter_map(std::char::from_u32)
+        .collect();
+    font.layout_single_line(emojis);
+    let painted = font.layout_single_line("Съешь же ещё".to_owned());
+    let unused = font.layout_single_line("שלום".to_owned());
+    assert!(!painted.rows[0].glyphs.is_empty() && !unused.rows[0].glyphs.is_empty());
+    assert!(fonts.atlas_stats().height > EVICTION_HEIGHT);
+
+    // Keep painting one galley, but not the other:
+    for _ in 0..MAX_UNUSED_FRAMES {
+        fonts.begin_frame();
+        assert!(num_vertices(&painted) > 0);
+    }
+    assert_eq!(fonts.atlas_stats().num_evictions, 1);
+
+    assert!(num_vertices(&painted) > 0, "painted glyphs are kept");
+    assert_eq!(
+        num_vertices(&unused),
+        0,
+        "evicted glyphs are not painted from where they used to be"
+    );
+    let unused = font.layout_single_line("שלום".to_owned());
+    assert!(num_vertices(&unused) > 0, "laying out adds them back");
+}
+
+#[test]
+fn test_no_eviction_while_all_glyphs_are_used() {
+    let fonts = Fonts::from_definitions(8.0, FontDefinitions::default());
+    let font = &fonts[TextStyle::Heading];
+    let emojis: String = (0x2600..0x27C0)
+        .chain(0x1F300..0x1F700)
+        .filter_map(std::char::from_u32)
+        .collect();
+
+    // Evicting wouldn't make the texture small enough, so the texture is left as is:
+    for _ in 0..=MAX_UNUSED_FRAMES + 1 {
+        fonts.begin_frame();
+        font.layout_single_line(emojis.clone());
+    }
+    let stats = fonts.atlas_stats();
+    assert!(stats.height > EVICTION_HEIGHT);
+    assert_eq!(stats.num_evictions, 0);
+}
@@ -19,7 +19,7 @@
 //! and the start of the second row.
 //! [`CCursor::prefer_next_row`] etc selects which.
 
-use super::cursor::*;
+use super::{cursor::*, LayoutSection};
 use emath::{pos2, NumExt, Rect, Vec2};
 
 /// A collection of text locked into place.
@@ -36,6 +36,11 @@ pub struct Galley {
 
     // Optimization: calculated once and reused.
     pub size: Vec2,
+
+    /// The formatting of the text, if it was laid out from a [`super::LayoutJob`].
+    /// If empty, all of the text has the same [`super::TextStyle`] and color,
+    /// given when painting the galley.
+    pub sections: Vec<LayoutSection>,
 }
 
 /// A typeset piece of text on a single row.
@@ -47,8 +52,16 @@ pub struct Row {
     /// Unit: points.
     ///
     /// `x_offsets.len() + (ends_with_newline as usize) == text.chars().count() + 1`
+    ///
+    /// These are where a text cursor before each character goes.
+    /// For shaped right-to-left text (see [`Self::glyphs`]) that is the right edge of the character,
+    /// so the offsets are not always increasing.
     pub x_offsets: Vec<f32>,
 
+    /// The glyphs to paint, if the row was shaped (with the `shaping` feature).
+    /// If empty, each character is painted on its own at its `x_offsets`.
+    pub glyphs: Vec<ShapedGlyph>,
+
     /// Top of the row, offset within the Galley.
     /// Unit: points.
     pub y_min: f32,
@@ -65,6 +78,21 @@ pub struct Row {
     pub ends_with_newline: bool,
 }
 
+/// A glyph placed by a text shaper, which may be a ligature of several characters,
+/// or one of several glyphs for a single character.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ShapedGlyph {
+    /// Which of the fonts in the [`super::Font`] (the primary font or a fallback) the glyph is from.
+    pub font_index: usize,
+
+    /// The id of the glyph in that font.
+    pub glyph_id: u16,
+
+    /// Where to put the glyph, relative to the left of the [`Row`] and its top.
+    /// Unit: points.
+    pub pos: Vec2,
+}
+
 impl Row {
     pub fn sanity_check(&self) {
         assert!(!self.x_offsets.is_empty());
@@ -82,11 +110,22 @@ impl Row {
     }
 
     pub fn min_x(&self) -> f32 {
-        *self.x_offsets.first().unwrap()
+        if self.glyphs.is_empty() {
+            *self.x_offsets.first().unwrap()
+        } else {
+            self.x_offsets.iter().copied().fold(f32::INFINITY, f32::min)
+        }
     }
 
     pub fn max_x(&self) -> f32 {
-        *self.x_offsets.last().unwrap()
+        if self.glyphs.is_empty() {
+            *self.x_offsets.last().unwrap()
+        } else {
+            self.x_offsets
+                .iter()
+                .copied()
+                .fold(f32::NEG_INFINITY, f32::max)
+        }
     }
 
     pub fn height(&self) -> f32 {
@@ -103,6 +142,17 @@ impl Row {
     /// Closest char at the desired x coordinate.
     /// Returns something in the range `[0, char_count_excluding_newline()]`.
     pub fn char_at(&self, desired_x: f32) -> usize {
+        if !self.glyphs.is_empty() {
+            // Shaped text can go in either direction, so find the closest cursor position:
+            let mut closest = 0;
+            for (i, x) in self.x_offsets.iter().enumerate() {
+                if (x - desired_x).abs() <= (self.x_offsets[closest] - desired_x).abs() {
+                    closest = i;
+                }
+            }
+            return closest;
+        }
+
         for (i, char_x_bounds) in self.x_offsets.windows(2).enumerate() {
             let char_center_x = 0.5 * (char_x_bounds[0] + char_x_bounds[1]);
             if desired_x < char_center_x {
@@ -197,7 +247,11 @@ impl Galley {
         let mut pcursor_it = PCursor::default();
 
         for (row_nr, row) in self.rows.iter().enumerate() {
-            let y_dist = (row.y_min - pos.y).abs().min((row.y_max - pos.y).abs());
+            let y_dist = if row.y_min <= pos.y && pos.y <= row.y_max {
+                0.0 // Rows can have different heights, so prefer the row we are in
+            } else {
+                (row.y_min - pos.y).abs().min((row.y_max - pos.y).abs())
+            };
             if y_dist < best_y_dist {
                 best_y_dist = y_dist;
                 let column = row.char_at(pos.x);
@@ -778,3 +832,32 @@ fn test_text_layout() {
         );
     }
 }
+
+#[test]
+fn test_rich_text_cursors() {
+    use crate::*;
+    use text::{LayoutJob, TextFormat};
+
+    let fonts = text::Fonts::from_definitions(1.0, text::FontDefinitions::default());
+    let mut job = LayoutJob::default();
+    job.append("small ", TextFormat::default());
+    job.append("BIG\n", TextFormat::style(TextStyle::Heading));
+    job.append("mixed ", TextFormat::style(TextStyle::Small));
+    job.append(
+        "bold",
+        TextFormat {
+            bold: true,
+            ..Default::default()
+        },
+    );
+    let galley = fonts.layout_job(job, 1024.0);
+    assert_eq!(galley.rows.len(), 2);
+
+    let num_chars = galley.text.chars().count();
+    for index in 0..=num_chars {
+        let cursor = galley.from_ccursor(CCursor::new(index));
+        let rect = galley.pos_from_cursor(&cursor);
+        let back = galley.cursor_from_pos(rect.center().to_vec2());
+        assert_eq!(back.ccursor.index, index, "Cursor {} at {:?}", index, rect);
+    }
+}
@@ -0,0 +1,171 @@
+//! Rich text: text with several sections of different formatting.
+
+use std::ops::Range;
+
+use super::TextStyle;
+use crate::{Color32, Stroke};
+
+/// Text with several sections of different formatting (text style, color, underline, …).
+///
+/// Lay it out into a [`super::Galley`] with [`super::Fonts::layout_job`].
+///
+/// ```
+/// use epaint::{text::{LayoutJob, TextFormat, TextStyle}, Color32};
+/// let mut job = LayoutJob::default();
+/// job.append("Hello ", TextFormat::default());
+/// job.append(
+///     "world",
+///     TextFormat {
+///         style: TextStyle::Monospace,
+///         color: Some(Color32::RED),
+///         ..Default::default()
+///     },
+/// );
+/// assert_eq!(job.text, "Hello world");
+/// assert_eq!(job.sections.len(), 2);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LayoutJob {
+    /// The complete text.
+    pub text: String,
+
+    /// The formatting of [`Self::text`].
+    /// The sections are in order, do not overlap, and cover all of the text.
+    pub sections: Vec<LayoutSection>,
+}
+
+impl LayoutJob {
+    /// The whole text with the same format.
+    pub fn single_section(text: impl Into<String>, format: TextFormat) -> Self {
+        let text = text.into();
+        Self {
+            sections: vec![LayoutSection {
+                byte_range: 0..text.len(),
+                format,
+            }],
+            text,
+        }
+    }
+
+    /// Add some text at the end, with the given format.
+    pub fn append(&mut self, text: &str, format: TextFormat) {
+        let start = self.text.len();
+        self.text += text;
+        self.sections.push(LayoutSection {
+            byte_range: start..self.text.len(),
+            format,
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Make sure the sections cover all of the text,
+    /// using the default [`TextFormat`] for any text that is not covered.
+    ///
+    /// Ranges past the end of the text are cut short,
+    /// and ranges that start or end inside a character are moved to the start of that character.
+    pub(crate) fn sanitize(&mut self) {
+        let text = &self.text;
+        let clamp = |byte: usize| {
+            let mut byte = byte.min(text.len());
+            while !text.is_char_boundary(byte) {
+                byte -= 1;
+            }
+            byte
+        };
+
+        let mut end = 0;
+        let mut sections = Vec::with_capacity(self.sections.len());
+        for section in std::mem::take(&mut self.sections) {
+            let byte_range =
+                clamp(section.byte_range.start).max(end)..clamp(section.byte_range.end);
+            if end < byte_range.start {
+                sections.push(LayoutSection {
+                    byte_range: end..byte_range.start,
+                    format: Default::default(),
+                });
+            }
+            if byte_range.start < byte_range.end {
+                end = byte_range.end;
+                sections.push(LayoutSection {
+                    byte_range,
+                    format: section.format,
+                });
+            }
+        }
+        if end < self.text.len() || sections.is_empty() {
+            sections.push(LayoutSection {
+                byte_range: end..self.text.len(),
+                format: Default::default(),
+            });
+        }
+        self.sections = sections;
+    }
+}
+
+/// A part of a [`LayoutJob`] with the same formatting.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayoutSection {
+    /// The range of bytes in [`LayoutJob::text`] this section covers.
+    pub byte_range: Range<usize>,
+    pub format: TextFormat,
+}
+
+/// How to format a [`LayoutSection`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TextFormat {
+    pub style: TextStyle,
+
+    /// `None` means the color the text is painted with, e.g. the default text color of a label.
+    pub color: Option<Color32>,
+
+    /// Make the text thicker by painting it twice, slightly offset.
+    pub bold: bool,
+
+    /// Slant the text to the right.
+    pub italics: bool,
+
+    /// Fill the background of the text with this color.
+    pub background: Color32,
+
+    /// A line under the text. Not painted if the width is zero.
+    pub underline: Stroke,
+
+    /// A line through the middle of the text. Not painted if the width is zero.
+    pub strikethrough: Stroke,
+}
+
+impl Default for TextFormat {
+    fn default() -> Self {
+        Self {
+            style: TextStyle::Body,
+            color: None,
+            bold: false,
+            italics: false,
+            background: Color32::TRANSPARENT,
+            underline: Stroke::none(),
+            strikethrough: Stroke::none(),
+        }
+    }
+}
+
+impl TextFormat {
+    /// Default format with the given [`TextStyle`].
+    pub fn style(style: TextStyle) -> Self {
+        Self {
+            style,
+            ..Default::default()
+        }
+    }
+
+    /// How far to the right bold text is painted a second time, in points.
+    /// This is also added to the width of each bold character.
+    pub(crate) fn bold_offset(row_height: f32) -> f32 {
+        row_height / 16.0
+    }
+
+    /// How far to the right the top of italic text is slanted, relative to its height.
+    pub(crate) const ITALICS_SKEW: f32 = 0.2;
+}
@@ -2,8 +2,16 @@ pub mod cursor;
 mod font;
 mod fonts;
 mod galley;
+mod layout_job;
+#[cfg(feature = "shaping")]
+mod shaping;
+#[cfg(feature = "system_fonts")]
+mod system_fonts;
+
+pub(crate) use font::UvRect;
 
 pub use {
     fonts::{FontDefinitions, FontFamily, Fonts, TextStyle},
-    galley::{Galley, Row},
+    galley::{Galley, Row, ShapedGlyph},
+    layout_job::{LayoutJob, LayoutSection, TextFormat},
 };
@@ -0,0 +1,412 @@
+//! Text shaping with `rustybuzz`, and reordering of right-to-left text with `unicode-bidi`.
+//!
+//! Only used with the `shaping` feature.
+//!
+//! A paragraph is first split into runs with the same font and direction, and each run is shaped.
+//! The resulting advances give the (logical) position of each character, which is used for wrapping.
+//! Each row is then placed in visual order: runs are reordered according to the Unicode Bidirectional Algorithm,
+//! and the glyphs are stored in [`Row::glyphs`].
+//! [`Row::x_offsets`] is then updated to where the text cursor goes before each character,
+//! so that [`super::cursor`] keeps working as before.
+
+use std::ops::Range;
+
+use emath::vec2;
+use unicode_bidi::{BidiInfo, Level};
+
+use super::{
+    font::Font,
+    galley::{Row, ShapedGlyph},
+};
+
+/// A glyph from shaping a run, in points.
+struct RunGlyph {
+    glyph_id: u16,
+    /// Index of the first character of the cluster this glyph belongs to.
+    cluster: usize,
+    x_advance: f32,
+    x_offset: f32,
+    y_offset: f32,
+}
+
+/// A piece of a paragraph with the same direction and font.
+struct Run {
+    /// Character indices into the paragraph.
+    chars: Range<usize>,
+    level: Level,
+    font_index: usize,
+    /// In visual order, i.e. right-to-left runs have their glyphs reversed.
+    glyphs: Vec<RunGlyph>,
+}
+
+/// A shaped paragraph (text without any `\n`).
+pub(crate) struct ShapedParagraph<'a> {
+    text: &'a str,
+    bidi_info: BidiInfo<'a>,
+    /// Byte offset of each character.
+    char_starts: Vec<usize>,
+    runs: Vec<Run>,
+    /// The start of each character in logical order, as if the paragraph was all on one row,
+    /// followed by the end of the last one.
+    pub x_offsets: Vec<f32>,
+}
+
+impl<'a> ShapedParagraph<'a> {
+    pub fn new(font: &Font, text: &'a str) -> Self {
+        let bidi_info = BidiInfo::new(text, None);
+        let char_starts: Vec<usize> = text.char_indices().map(|(i, _)| i).collect();
+        let font_indices: Vec<usize> = text.chars().map(|c| font.font_index(c)).collect();
+        let byte_range = |chars: &Range<usize>| {
+            char_starts[chars.start]..char_starts.get(chars.end).copied().unwrap_or(text.len())
+        };
+
+        // Split into runs:
+        let mut runs = vec![];
+        let mut run_start = 0;
+        for i in 1..=char_starts.len() {
+            let level = |i: usize| bidi_info.levels[char_starts[i]];
+            if i == char_starts.len()
+                || level(i) != level(run_start)
+                || font_indices[i] != font_indices[run_start]
+            {
+                runs.push(Run {
+                    chars: run_start..i,
+                    level: level(run_start),
+                    font_index: font_indices[run_start],
+                    glyphs: vec![],
+                });
+                run_start = i;
+            }
+        }
+
+        // Shape each run:
+        let font_impls = font.font_impls();
+        let mut faces: Vec<Option<Option<rustybuzz::Face<'_>>>> = vec![];
+        faces.resize_with(font_impls.len(), || None);
+        for run in &mut runs {
+            let font_impl = &font_impls[run.font_index];
+            let face = faces[run.font_index].get_or_insert_with(|| {
+                font_impl
+                    .font_data()
+                    .and_then(|data| rustybuzz::Face::from_slice(data, 0))
+            });
+            let face = match face {
+                Some(face) => face,
+                None => continue, // Leave the run without glyphs
+            };
+
+            let run_bytes = byte_range(&run.chars);
+            let mut buffer = rustybuzz::UnicodeBuffer::new();
+            buffer.push_str(&text[run_bytes.clone()]);
+            buffer.set_direction(if run.level.is_rtl() {
+                rustybuzz::Direction::RightToLeft
+            } else {
+                rustybuzz::Direction::LeftToRight
+            });
+            buffer.guess_segment_properties();
+            let glyph_buffer = rustybuzz::shape(face, &[], buffer);
+
+            let points_per_unit = font_impl.points_per_font_unit();
+            run.glyphs = glyph_buffer
+                .glyph_infos()
+                .iter()
+                .zip(glyph_buffer.glyph_positions())
+                .map(|(info, pos)| {
+                    let byte = run_bytes.start + info.cluster as usize;
+                    RunGlyph {
+                        glyph_id: info.glyph_id as u16,
+                        cluster: char_starts.partition_point(|&start| start <= byte) - 1,
+                        x_advance: pos.x_advance as f32 * points_per_unit,
+                        x_offset: pos.x_offset as f32 * points_per_unit,
+                        y_offset: -pos.y_offset as f32 * points_per_unit,
+                    }
+                })
+                .collect();
+        }
+
+        // Give each character its share of the advance of its cluster:
+        let mut char_advances = vec![0.0; char_starts.len()];
+        for run in &runs {
+            for (char_range, advance) in clusters(run) {
+                let share = advance / char_range.len() as f32;
+                for advance in &mut char_advances[char_range] {
+                    *advance = share;
+                }
+            }
+        }
+        let mut x_offsets = Vec::with_capacity(char_starts.len() + 1);
+        x_offsets.push(0.0);
+        let mut x = 0.0;
+        for advance in char_advances {
+            x = font.round_to_pixel(x + advance);
+            x_offsets.push(x);
+        }
+
+        Self {
+            text,
+            bidi_info,
+            char_starts,
+            runs,
+            x_offsets,
+        }
+    }
+
+    /// Place the glyphs of a row (as wrapped using [`Self::x_offsets`]) in visual order.
+    ///
+    /// `chars` is the range of characters in the row.
+    /// The row keeps its left edge (`row.x_offsets[0]`).
+    pub fn shape_row(&self, font: &Font, row: &mut Row, chars: Range<usize>) {
+        if chars.is_empty() {
+            return;
+        }
+
+        // Split the row into pieces of runs with the same (reordered) level.
+        // The reordered levels take care of e.g. trailing whitespace.
+        let byte_start = self.char_starts[chars.start];
+        let byte_end = self
+            .char_starts
+            .get(chars.end)
+            .copied()
+            .unwrap_or(self.text.len());
+        let levels = self
+            .bidi_info
+            .reordered_levels(&self.bidi_info.paragraphs[0], byte_start..byte_end);
+        let level = |c: usize| levels[self.char_starts[c]];
+
+        let mut pieces: Vec<(usize, Range<usize>, Level)> = vec![];
+        for (run_index, run) in self.runs.iter().enumerate() {
+            let start = run.chars.start.max(chars.start);
+            let end = run.chars.end.min(chars.end);
+            let mut piece_start = start;
+            for c in start..end {
+                if c + 1 == end || level(c + 1) != level(piece_start) {
+                    pieces.push((run_index, piece_start..c + 1, level(piece_start)));
+                    piece_start = c + 1;
+                }
+            }
+        }
+
+        // Lay out the pieces from left to right:
+        let piece_levels: Vec<Level> = pieces.iter().map(|(_, _, level)| *level).collect();
+        let mut starts = vec![0.0; chars.len()];
+        let mut ends = vec![0.0; chars.len()];
+        let mut glyphs = vec![];
+        let mut x = row.x_offsets[0];
+        for piece_index in visual_order(&piece_levels) {
+            let (run_index, piece_chars, _) = &pieces[piece_index];
+            let run = &self.runs[*run_index];
+
+            // The left edge of each cluster:
+            let mut cluster_lefts: Vec<(usize, f32)> = vec![];
+            for glyph in &run.glyphs {
+                if !piece_chars.contains(&glyph.cluster) {
+                    continue;
+                }
+                let (font_index, glyph_id) = if glyph.glyph_id == 0 {
+                    font.replacement_glyph() // Missing in the font
+                } else {
+                    (run.font_index, glyph.glyph_id)
+                };
+                font.load_shaped_glyph(font_index, glyph_id);
+                glyphs.push(ShapedGlyph {
+                    font_index,
+                    glyph_id,
+                    pos: vec2(font.round_to_pixel(x + glyph.x_offset), glyph.y_offset),
+                });
+                match cluster_lefts.iter_mut().find(|(c, _)| *c == glyph.cluster) {
+                    Some((_, left)) => *left = left.min(x),
+                    None => cluster_lefts.push((glyph.cluster, x)),
+                }
+                x += glyph.x_advance;
+            }
+
+            // Where the cursor goes before and after each character:
+            for (cluster_chars, advance) in clusters(run) {
+                if !piece_chars.contains(&cluster_chars.start) {
+                    continue;
+                }
+                let left = cluster_lefts
+                    .iter()
+                    .find(|(c, _)| *c == cluster_chars.start)
+                    .map_or(x, |(_, left)| *left);
+                let width = advance / cluster_chars.len() as f32;
+                for (k, c) in cluster_chars.enumerate() {
+                    let (start, end) = if run.level.is_rtl() {
+                        let right = left + advance;
+                        (right - k as f32 * width, right - (k + 1) as f32 * width)
+                    } else {
+                        (left + k as f32 * width, left + (k + 1) as f32 * width)
+                    };
+                    starts[c - chars.start] = font.round_to_pixel(start);
+                    ends[c - chars.start] = font.round_to_pixel(end);
+                }
+            }
+        }
+
+        row.x_offsets = starts;
+        row.x_offsets.push(*ends.last().unwrap());
+        row.glyphs = glyphs;
+    }
+}
+
+/// The clusters of a run in logical order: the characters in each, and their total advance.
+fn clusters(run: &Run) -> Vec<(Range<usize>, f32)> {
+    let mut starts: Vec<(usize, f32)> = vec![];
+    for glyph in &run.glyphs {
+        match starts.iter_mut().find(|(start, _)| *start == glyph.cluster) {
+            Some((_, advance)) => *advance += glyph.x_advance,
+            None => starts.push((glyph.cluster, glyph.x_advance)),
+        }
+    }
+    if starts.is_empty() {
+        // No glyphs (e.g. no font data), so give each character no width:
+        return run.chars.clone().map(|c| (c..c + 1, 0.0)).collect();
+    }
+    starts.sort_by_key(|(start, _)| *start);
+
+    let ends = starts.iter().skip(1).map(|(start, _)| *start);
+    let ends = ends.chain(std::iter::once(run.chars.end));
+    starts
+        .iter()
+        .zip(ends)
+        .map(|(&(start, advance), end)| (start..end, advance))
+        .collect()
+}
+
+/// The order in which to show things with the given bidi levels, from left to right (rule L2).
+fn visual_order(levels: &[Level]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..levels.len()).collect();
+    let max_level = levels.iter().map(Level::number).max().unwrap_or(0);
+    let lowest_odd_level = levels
+        .iter()
+        .map(Level::number)
+        .filter(|level| level % 2 == 1)
+        .min()
+        .unwrap_or(max_level + 1);
+
+    for level in (lowest_odd_level..=max_level).rev() {
+        let mut i = 0;
+        while i < order.len() {
+            if levels[order[i]].number() >= level {
+                let start = i;
+                while i < order.len() && levels[order[i]].number() >= level {
+                    i += 1;
+                }
+                order[start..i].reverse();
+            } else {
+                i += 1;
+            }
+        }
+    }
+    order
+}
+
+#[test]
+fn test_visual_order() {
+    let levels = Level::vec(&[0, 1, 1, 0, 2, 1]);
+    assert_eq!(visual_order(&levels), vec![0, 2, 1, 3, 5, 4]);
+    assert_eq!(visual_order(&Level::vec(&[1, 1, 1])), vec![2, 1, 0]);
+}
+
+#[test]
+fn test_bidi_cursors() {
+    use crate::text::{cursor::CCursor, FontDefinitions, Fonts, TextStyle};
+
+    let fonts = Fonts::from_definitions(1.0, FontDefinitions::default());
+
+    let galley = fonts[TextStyle::Body].layout_multiline("abc שלום def".to_owned(), 1024.0);
+    assert_eq!(galley.rows.len(), 1);
+    let row = &galley.rows[0];
+    assert!(!row.glyphs.is_empty());
+
+    // "abc " then "שלום" from right to left, then " def":
+    let x = &row.x_offsets;
+    assert_eq!(x[0], 0.0);
+    assert!(x[0] < x[1] && x[1] < x[2] && x[2] < x[3] && x[3] < x[4]);
+    assert!(x[4] > x[5] && x[5] > x[6] && x[6] > x[7]);
+    assert!(x[3] < x[7] && x[8] < x[9] && x[9] < x[10] && x[10] < x[11] && x[11] < x[12]);
+    assert_eq!(row.min_x(), 0.0);
+    assert_eq!(row.max_x(), x[12]);
+
+    // Where the cursor goes and where you click must agree:
+    let num_chars = galley.text.chars().count();
+    for index in 0..=num_chars {
+        let cursor = galley.from_ccursor(CCursor::new(index));
+        let rect = galley.pos_from_cursor(&cursor);
+        let back = galley.cursor_from_pos(rect.center().to_vec2());
+        assert_eq!(galley.pos_from_cursor(&back), rect, "Cursor {}", index);
+    }
+}
+
+#[test]
+fn test_shaped_wrapping() {
+    use crate::text::{FontDefinitions, Fonts, TextStyle};
+
+    let fonts = Fonts::from_definitions(1.0, FontDefinitions::default());
+    let font = &fonts[TextStyle::Body];
+    let text = "one two three four five six seven eight nine ten";
+    let galley = font.layout_multiline(text.to_owned(), 60.0);
+    assert!(galley.rows.len() > 1);
+    for row in &galley.rows {
+        assert!(row.max_x() <= 60.0);
+        assert!(row.x_offsets.windows(2).all(|w| w[0] <= w[1]));
+    }
+
+    // Left-to-right text is placed as without shaping:
+    let unshaped = font.layout_single_row_fragment(text);
+    let shaped = font.layout_single_line(text.to_owned());
+    assert_eq!(shaped.rows[0].x_offsets.len(), unshaped.len());
+}
+
+#[test]
+fn test_shaped_glyphs_are_allocated_when_laid_out() {
+    use crate::{
+        text::{FontDefinitions, Fonts, TextStyle},
+        Color32, Shape, TessellationOptions,
+    };
+    use emath::{pos2, Rect};
+
+    let fonts = Fonts::from_definitions(1.0, FontDefinitions::default());
+
+    // The glyphs of the preloaded ASCII characters are reused, not added again:
+    let num_glyphs = fonts.atlas_stats().num_glyphs;
+    fonts[TextStyle::Body].layout_single_line("Hello world".to_owned());
+    assert_eq!(fonts.atlas_stats().num_glyphs, num_glyphs);
+
+    let galley = fonts[TextStyle::Body].layout_multiline(
+        "Съешь же ещё этих мягких французских булок".repeat(4),
+        200.0,
+    );
+    assert!(galley.rows.iter().all(|row| !row.glyphs.is_empty()));
+
+    // Painting must not change the texture, or the UV:s would be off:
+    let version = fonts.texture().version;
+    let shape = Shape::Text {
+        pos: pos2(0.0, 0.0),
+        galley,
+        text_style: TextStyle::Body,
+        color: Color32::WHITE,
+    };
+    let triangles = crate::tessellator::tessellate_shapes(
+        vec![(Rect::everything(), shape)],
+        TessellationOptions::default(),
+        &fonts,
+    );
+    assert!(!triangles[0].1.vertices.is_empty());
+    assert_eq!(fonts.texture().version, version);
+}
+
+#[test]
+fn test_layout_job_is_not_shaped() {
+    use crate::text::{FontDefinitions, Fonts, LayoutJob, TextFormat};
+
+    let fonts = Fonts::from_definitions(1.0, FontDefinitions::default());
+    let job = LayoutJob::single_section("abc שלום", TextFormat::default());
+    let galley = fonts.layout_job(job, f32::INFINITY);
+
+    // Rich text is neither shaped nor reordered (yet),
+    // so right-to-left text is shown from left to right, in the order it is written:
+    let row = &galley.rows[0];
+    assert!(row.glyphs.is_empty());
+    assert!(row.x_offsets.windows(2).all(|w| w[0] < w[1]));
+}
@@ -0,0 +1,412 @@
+//! Finding fonts installed on the system, to use as fallbacks for characters
+//! missing in all the fonts of [`super::FontDefinitions`].
+//!
+//! Only used with the `system_fonts` feature.
+//!
+//! The font directories are the same ones `fontconfig` looks in by default on Linux:
+//! `$XDG_DATA_HOME/fonts`, `~/.fonts` and `fonts` in each of `$XDG_DATA_DIRS`.
+//! The fonts are shared by the whole process. Each font file is read the first time
+//! a missing character is looked for in it, but only kept if a character is shown with it.
+
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use ahash::AHashMap;
+
+use crate::mutex::Mutex;
+
+/// A font file found on the system.
+#[derive(Clone)]
+pub(crate) struct SystemFont {
+    /// The file name, e.g. `DejaVuSans.ttf`.
+    pub name: String,
+    pub rusttype_font: Arc<rusttype::Font<'static>>,
+    /// For the text shaper. The same bytes as `rusttype_font` uses.
+    #[cfg(feature = "shaping")]
+    pub font_data: Arc<super::fonts::FontData>,
+}
+
+/// A font file, and what we know about it so far.
+struct FontFile {
+    path: PathBuf,
+    /// Which characters the font has. `None` until the file is first read.
+    coverage: Mutex<Option<Arc<Coverage>>>,
+    /// Set once a character is shown with this font.
+    font: Mutex<Option<SystemFont>>,
+}
+
+/// The fonts installed on the system. Shared by all [`super::Font`]:s.
+pub(crate) struct SystemFonts {
+    dirs: Vec<PathBuf>,
+    /// The font files in `dirs`, in order of preference. Found on first use.
+    files: Mutex<Option<Arc<Vec<FontFile>>>>,
+    /// Which font has each character we looked for (`None` if no font has it).
+    lookups: Mutex<AHashMap<char, Option<usize>>>,
+}
+
+impl SystemFonts {
+    fn new(dirs: Vec<PathBuf>) -> Self {
+        Self {
+            dirs,
+            files: Default::default(),
+            lookups: Default::default(),
+        }
+    }
+
+    /// The fonts in the system font directories (see [`font_dirs`]),
+    /// shared by the whole process so that each file is only read once.
+    pub fn global() -> Arc<Self> {
+        static GLOBAL: once_cell::sync::Lazy<Arc<SystemFonts>> =
+            once_cell::sync::Lazy::new(|| Arc::new(SystemFonts::new(font_dirs())));
+        GLOBAL.clone()
+    }
+
+    /// The first font (in path order) that has the character `c`.
+    pub fn font_for_char(&self, c: char) -> Option<SystemFont> {
+        let files = self.files();
+        let found = self.lookups.lock().get(&c).copied();
+        let found = found.unwrap_or_else(|| {
+            let found = (0..files.len()).find(|&index| has_char(&files[index], c));
+            self.lookups.lock().insert(c, found);
+            found
+        });
+        found.and_then(|index| files[index].font.lock().clone())
+    }
+
+    fn files(&self) -> Arc<Vec<FontFile>> {
+        if let Some(files) = &*self.files.lock() {
+            return files.clone();
+        }
+
+        // Search the directories without holding the lock:
+        let files = find_font_files(&self.dirs)
+            .into_iter()
+            .map(|path| FontFile {
+                path,
+                coverage: Default::default(),
+                font: Default::default(),
+            })
+            .collect();
+        self.files
+            .lock()
+            .get_or_insert_with(|| Arc::new(files))
+            .clone()
+    }
+}
+
+/// Does the font in this file have the character `c`? If so, the font is kept in `file.font`.
+///
+/// Files are read without holding any lock.
+fn has_char(file: &FontFile, c: char) -> bool {
+    let mut bytes = None;
+
+    let coverage = file.coverage.lock().clone();
+    let coverage = coverage.unwrap_or_else(|| {
+        let read = std::fs::read(&file.path).ok();
+        let coverage = Arc::new(read.as_deref().map(Coverage::of_font).unwrap_or_default());
+        bytes = read;
+        file.coverage.lock().get_or_insert_with(|| coverage).clone()
+    });
+    if !coverage.contains(c) {
+        return false;
+    }
+
+    let font = file.font.lock().clone();
+    let font = font.or_else(|| {
+        let bytes = bytes.or_else(|| std::fs::read(&file.path).ok())?;
+        let mut font = file.font.lock();
+        if font.is_none() {
+            *font = load_font(&file.path, bytes);
+        }
+        font.clone()
+    });
+    font.is_some_and(|font| font.rusttype_font.glyph(c).id().0 != 0)
+}
+
+/// The characters a font has, as sorted and non-overlapping ranges of code points.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Coverage(Vec<(u32, u32)>);
+
+impl Coverage {
+    /// Read the character map (`cmap`) of a font file.
+    /// Only the common formats 4 and 12 are supported. Empty if the file can't be read.
+    fn of_font(data: &[u8]) -> Self {
+        let mut ranges = cmap_ranges(data).unwrap_or_default();
+        ranges.sort_unstable();
+        let mut merged: Vec<(u32, u32)> = Vec::with_capacity(ranges.len());
+        for (start, end) in ranges {
+            match merged.last_mut() {
+                Some(last) if start <= last.1.saturating_add(1) => last.1 = last.1.max(end),
+                _ => merged.push((start, end)),
+            }
+        }
+        Self(merged)
+    }
+
+    fn contains(&self, c: char) -> bool {
+        let c = c as u32;
+        let index = self.0.partition_point(|&(_, end)| end < c);
+        self.0.get(index).is_some_and(|&(start, _)| start <= c)
+    }
+}
+
+/// The ranges of characters in the Unicode subtables of the `cmap` table
+/// of a font file (or of the first font in a collection).
+fn cmap_ranges(data: &[u8]) -> Option<Vec<(u32, u32)>> {
+    let u16_at = |offset: usize| -> Option<u16> {
+        let bytes = data.get(offset..offset + 2)?;
+        Some(u16::from_be_bytes([bytes[0], bytes[1]]))
+    };
+    let u32_at = |offset: usize| -> Option<u32> {
+        let bytes = data.get(offset..offset + 4)?;
+        Some(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    };
+
+    let font_offset = if data.get(0..4)? == b"ttcf" {
+        u32_at(12)? as usize
+    } else {
+        0
+    };
+    let num_tables = u16_at(font_offset + 4)? as usize;
+    let cmap = (0..num_tables)
+        .map(|i| font_offset + 12 + 16 * i)
+        .find(|&record| data.get(record..record + 4) == Some(b"cmap"))
+        .map(|record| u32_at(record + 8))?? as usize;
+
+    let mut ranges = vec![];
+    let num_subtables = u16_at(cmap + 2)? as usize;
+    for i in 0..num_subtables {
+        let record = cmap + 4 + 8 * i;
+        let (platform, encoding) = (u16_at(record)?, u16_at(record + 2)?);
+        let is_unicode = platform == 0 || (platform == 3 && (encoding == 1 || encoding == 10));
+        if !is_unicode {
+            continue;
+        }
+        let subtable = cmap + u32_at(record + 4)? as usize;
+        match u16_at(subtable)? {
+            4 => {
+                let num_segments = u16_at(subtable + 6)? as usize / 2;
+                let end_codes = subtable + 14;
+                let start_codes = end_codes + 2 * num_segments + 2;
+                for segment in 0..num_segments {
+                    let start = u16_at(start_codes + 2 * segment)?;
+                    let end = u16_at(end_codes + 2 * segment)?;
+                    if start <= end && start != 0xFFFF {
+                        ranges.push((start as u32, end as u32));
+                    }
+                }
+            }
+            12 => {
+                let num_groups = u32_at(subtable + 12)? as usize;
+                for group in 0..num_groups {
+                    let group = subtable + 16 + 12 * group;
+                    let (start, end) = (u32_at(group)?, u32_at(group + 4)?);
+                    if start <= end {
+                        ranges.push((start, end));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Some(ranges)
+}
+
+/// The directories to look for fonts in, in order of preference.
+pub fn font_dirs() -> Vec<PathBuf> {
+    let home = std::env::var_os("HOME").map(PathBuf::from);
+
+    let data_home = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| home.as_ref().map(|home| home.join(".local/share")));
+    let data_dirs = std::env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|dirs| !dirs.is_empty())
+        .unwrap_or_else(|| "/usr/local/share:/usr/share".to_owned());
+
+    let mut dirs = vec![];
+    dirs.extend(data_home.map(|dir| dir.join("fonts")));
+    dirs.extend(home.map(|home| home.join(".fonts")));
+    dirs.extend(data_dirs.split(':').map(|dir| Path::new(dir).join("fonts")));
+    dirs
+}
+
+/// All `.ttf`, `.otf` and `.ttc` files in the given directories and their subdirectories.
+/// Sorted by path within each directory, but with bold, italic etc last,
+/// so that a regular font is preferred as a fallback.
+pub fn find_font_files(dirs: &[PathBuf]) -> Vec<PathBuf> {
+    fn visit(dir: &Path, visited: &mut HashSet<PathBuf>, files: &mut Vec<PathBuf>) {
+        // Don't go around in circles if there are symbolic links back up the tree:
+        let is_new = dir
+            .canonicalize()
+            .is_ok_and(|canonical| visited.insert(canonical));
+        if !is_new {
+            return;
+        }
+        let mut entries: Vec<PathBuf> = match std::fs::read_dir(dir) {
+            Ok(entries) => entries
+                .filter_map(|entry| Some(entry.ok()?.path()))
+                .collect(),
+            Err(_) => return, // Missing directory, or no permission
+        };
+        entries.sort();
+        for path in entries {
+            if path.is_dir() {
+                visit(&path, visited, files);
+            } else if is_font_file(&path) && !files.contains(&path) {
+                files.push(path);
+            }
+        }
+    }
+
+    let mut visited = HashSet::new();
+    let mut files = vec![];
+    for dir in dirs {
+        visit(dir, &mut visited, &mut files);
+    }
+    files.sort_by_key(|path| is_styled(path));
+    files
+}
+
+/// Is this e.g. a bold or italic variant of a font?
+fn is_styled(path: &Path) -> bool {
+    const STYLES: &[&str] = &[
+        "bold",
+        "italic",
+        "oblique",
+        "condensed",
+        "light",
+        "thin",
+        "black",
+        "heavy",
+    ];
+    let stem = path.file_stem().map(|s| s.to_string_lossy().to_lowercase());
+    let stem = stem.unwrap_or_default();
+    STYLES.iter().any(|style| stem.contains(style))
+}
+
+fn is_font_file(path: &Path) -> bool {
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    ["ttf", "otf", "ttc"]
+        .iter()
+        .any(|e| extension.eq_ignore_ascii_case(e))
+}
+
+/// The bytes are kept for as long as the program runs, since the fonts are shared by the whole process.
+/// That way `rusttype` and the text shaper can share them.
+fn load_font(path: &Path, bytes: Vec<u8>) -> Option<SystemFont> {
+    let name = path.file_name()?.to_string_lossy().into_owned();
+    rusttype::Font::try_from_bytes(&bytes)?; // Check before keeping the bytes
+    let bytes: &'static [u8] = Box::leak(bytes.into_boxed_slice());
+    // The first font of a collection (`.ttc`) is good enough as a fallback.
+    let rusttype_font = rusttype::Font::try_from_bytes(bytes)?;
+    Some(SystemFont {
+        name,
+        rusttype_font: Arc::new(rusttype_font),
+        #[cfg(feature = "shaping")]
+        font_data: Arc::new(super::fonts::FontData::Borrowed(bytes)),
+    })
+}
+
+#[test]
+fn test_find_font_files() {
+    let dir = std::env::temp_dir().join(format!("epaint_test_fonts_{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("b/nested")).unwrap();
+    let ubuntu = include_bytes!("../../fonts/Ubuntu-Light.ttf");
+    std::fs::write(dir.join("b/nested/Ubuntu-Light.ttf"), &ubuntu[..]).unwrap();
+    std::fs::write(dir.join("a.OTF"), b"not really a font").unwrap();
+    std::fs::write(dir.join("A-Bold.ttf"), b"not really a font").unwrap();
+    std::fs::write(dir.join("readme.txt"), b"hello").unwrap();
+
+    let files = find_font_files(&[dir.clone(), dir.join("missing")]);
+    assert_eq!(
+        files,
+        vec![
+            dir.join("a.OTF"),
+            dir.join("A-Bold.ttf"),
+            dir.join("b/nested/Ubuntu-Light.ttf")
+        ]
+    );
+    assert!(load_font(&files[0], b"not really a font".to_vec()).is_none());
+    let font = load_font(&files[2], ubuntu.to_vec()).unwrap();
+    assert_eq!(font.name, "Ubuntu-Light.ttf");
+    assert!(font.rusttype_font.glyph('a').id().0 != 0);
+
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(&dir, dir.join("b/nested/loop")).unwrap();
+        assert_eq!(find_font_files(&[dir.clone(), dir.join("missing")]), files);
+    }
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_fonts_are_loaded_lazily() {
+    let dir = std::env::temp_dir().join(format!("epaint_test_lazy_fonts_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let emoji = include_bytes!("../../fonts/NotoEmoji-Regular.ttf");
+    std::fs::write(dir.join("NotoEmoji-Regular.ttf"), &emoji[..]).unwrap();
+    let ubuntu = include_bytes!("../../fonts/Ubuntu-Light.ttf");
+    std::fs::write(dir.join("Ubuntu-Light.ttf"), &ubuntu[..]).unwrap(); // Styled, so last
+
+    let system_fonts = SystemFonts::new(vec![dir.clone()]);
+    assert_eq!(
+        system_fonts.font_for_char('🌟').unwrap().name,
+        "NotoEmoji-Regular.ttf"
+    );
+    let files = system_fonts.files();
+    assert_eq!(files.len(), 2);
+    assert!(
+        files[1].coverage.lock().is_none(),
+        "Ubuntu-Light is never read"
+    );
+    assert!(files[1].font.lock().is_none());
+
+    let font = system_fonts.font_for_char('a').unwrap();
+    assert_eq!(font.name, "Ubuntu-Light.ttf");
+    assert!(files[0].font.lock().is_some());
+
+    // Everything needed is in memory now:
+    std::fs::remove_dir_all(&dir).unwrap();
+    let again = system_fonts.font_for_char('a').unwrap();
+    assert!(Arc::ptr_eq(&font.rusttype_font, &again.rusttype_font));
+    assert!(Arc::ptr_eq(&files, &system_fonts.files()));
+    assert!(system_fonts.font_for_char('\u{10FFFD}').is_none()); // Private use, in no font
+}
+
+#[test]
+fn test_coverage() {
+    let coverage = Coverage::of_font(include_bytes!("../../fonts/Ubuntu-Light.ttf"));
+    assert!(coverage.contains('a'));
+    assert!(coverage.contains('é'));
+    assert!(!coverage.contains('🌟'));
+    assert!(!coverage.contains('\u{10FFFD}'));
+    assert_eq!(Coverage::of_font(b"not really a font"), Coverage::default());
+}
+
+#[test]
+fn test_font_name_for_char() {
+    use crate::text::{FontDefinitions, Fonts, TextStyle};
+
+    let fonts = Fonts::from_definitions(1.0, FontDefinitions::default());
+    let font = &fonts[TextStyle::Body];
+    assert_eq!(
+        font.font_name_for_char('a').as_deref(),
+        Some("Ubuntu-Light")
+    );
+    assert_eq!(
+        font.font_name_for_char('🌟').as_deref(),
+        Some("NotoEmoji-Regular")
+    );
+
+    // Hebrew is not in the default fonts, so it can only come from the system:
+    if let Some(name) = font.font_name_for_char('ש') {
+        assert!(is_font_file(Path::new(&name)), "{}", name);
+        assert!(font.glyph_width('ש') > 0.0);
+        assert_eq!(font.font_name_for_char('ל'), Some(name));
+    }
+}
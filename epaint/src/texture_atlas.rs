@@ -44,13 +44,21 @@ impl std::ops::IndexMut<(usize, usize)> for Texture {
 /// Contains font data in an atlas, where each character occupied a small rectangle.
 ///
 /// More characters can be added, possibly expanding the texture.
+/// When the texture gets too big, the atlas can be [`Self::clear`]:ed
+/// and the glyphs that are still in use added again.
 #[derive(Clone, Default)]
 pub struct TextureAtlas {
     texture: Texture,
 
+    /// The height of the texture when the atlas is empty.
+    initial_height: usize,
+
     /// Used for when allocating new rectangles.
     cursor: (usize, usize),
     row_height: usize,
+
+    /// Number of rectangles allocated since the atlas was created or cleared.
+    num_allocations: usize,
 }
 
 impl TextureAtlas {
@@ -62,6 +70,7 @@ impl TextureAtlas {
                 height,
                 pixels: vec![0; width * height],
             },
+            initial_height: height,
             ..Default::default()
         }
     }
@@ -75,8 +84,66 @@ impl TextureAtlas {
         &mut self.texture
     }
 
+    /// Number of rectangles allocated since the atlas was created or cleared.
+    pub fn num_allocations(&self) -> usize {
+        self.num_allocations
+    }
+
+    /// How many rows of pixels at the top of the texture are in use.
+    pub fn used_height(&self) -> usize {
+        self.cursor.1 + self.row_height
+    }
+
+    /// Remove everything, and shrink the texture back to its initial size.
+    /// Anything pointing into the old texture is invalid after this.
+    pub fn clear(&mut self) {
+        let width = self.texture.width;
+        self.texture.height = self.initial_height;
+        self.texture.pixels.clear();
+        self.texture.pixels.resize(width * self.initial_height, 0);
+        self.texture.version += 1;
+        self.cursor = (0, 0);
+        self.row_height = 0;
+        self.num_allocations = 0;
+    }
+
     /// Returns the coordinates of where the rect ended up.
+    ///
+    /// The texture will grow taller if needed, bumping [`Texture::version`].
     pub fn allocate(&mut self, (w, h): (usize, usize)) -> (usize, usize) {
+        let pos = self.place((w, h));
+
+        if self.texture.width * self.texture.height > self.texture.pixels.len() {
+            self.texture
+                .pixels
+                .resize(self.texture.width * self.texture.height, 0);
+        }
+
+        self.num_allocations += 1;
+        self.texture.version += 1;
+        pos
+    }
+
+    /// How tall the texture would be if the atlas was [`Self::clear`]:ed
+    /// and then rectangles of the given sizes were allocated, in order.
+    pub fn height_after_clear(&self, sizes: impl IntoIterator<Item = (usize, usize)>) -> usize {
+        let mut atlas = Self {
+            texture: Texture {
+                width: self.texture.width,
+                height: self.initial_height,
+                ..Default::default()
+            },
+            initial_height: self.initial_height,
+            ..Default::default()
+        };
+        for size in sizes {
+            atlas.place(size);
+        }
+        atlas.texture.height
+    }
+
+    /// Find room for a rectangle, making the texture taller if needed (without touching its pixels).
+    fn place(&mut self, (w, h): (usize, usize)) -> (usize, usize) {
         /// On some low-precision GPUs (my old iPad) characters get muddled up
         /// if we don't add some empty pixels between the characters.
         /// On modern high-precision GPUs this is not needed.
@@ -95,15 +162,8 @@ impl TextureAtlas {
             self.texture.height *= 2;
         }
 
-        if self.texture.width * self.texture.height > self.texture.pixels.len() {
-            self.texture
-                .pixels
-                .resize(self.texture.width * self.texture.height, 0);
-        }
-
         let pos = self.cursor;
         self.cursor.0 += w + PADDING;
-        self.texture.version += 1;
-        (pos.0 as usize, pos.1 as usize)
+        pos
     }
 }
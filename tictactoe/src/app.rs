@@ -3,6 +3,9 @@ use eframe::{egui, epi};
 use egui::{pos2, vec2, Color32, Pos2, Rect, Stroke, Widget};
 use rand::seq::SliceRandom;
 
+/// The big title, without changing `TextStyle::Heading` for everything else.
+const TITLE: egui::TextStyle = egui::TextStyle::Custom("title");
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 enum Player {
   User,
@@ -51,10 +54,9 @@ impl epi::App for TicTacToeApp {
     font_definitions
       .fonts_for_family
       .insert(egui::FontFamily::Proportional, vec!["Inter".to_owned()]);
-    font_definitions.family_and_size.insert(
-      egui::TextStyle::Heading,
-      (egui::FontFamily::Proportional, 48.0),
-    );
+    font_definitions
+      .family_and_size
+      .insert(TITLE, (egui::FontFamily::Proportional, 48.0));
     font_definitions.family_and_size.insert(
       egui::TextStyle::Button,
       (egui::FontFamily::Proportional, 32.0),
@@ -83,7 +85,7 @@ impl epi::App for TicTacToeApp {
         style.visuals.hyperlink_color = Color32::BLACK;
 
         ui.vertical_centered(|ui| {
-          ui.heading("tictactoe");
+          ui.add(egui::Label::new("tictactoe").text_style(TITLE));
           ui.advance_cursor(24.);
           let _board_response = self.show_board(ui, frame);
           ui.advance_cursor(16.);
@@ -0,0 +1,37 @@
+//! Snapshot tests of the app. Run with `UPDATE_SNAPSHOTS=1` to update the images after a change.
+
+use eframe::egui::{self, MouseButton, RawInput};
+
+fn harness() -> egui_snapshot::Harness {
+    egui_snapshot::Harness::new(egui::vec2(400.0, 600.0))
+}
+
+#[test]
+fn tictactoe_start_screen() {
+    let mut app = tictactoe::TicTacToeApp::default();
+    let mut harness = harness();
+    for _ in 0..2 {
+        harness.step_app(&mut app, Default::default()); // The first frame is still laying out
+    }
+    harness.assert_snapshot("tests/snapshots/start_screen.png");
+}
+
+#[test]
+fn tictactoe_first_move() {
+    let mut app = tictactoe::TicTacToeApp::default();
+    let mut harness = harness();
+    harness.step_app(&mut app, Default::default());
+
+    // Click the center cell. The computer waits a bit before it answers.
+    let mut input = RawInput {
+        mouse_pos: Some(egui::pos2(200.0, 250.0)),
+        ..Default::default()
+    };
+    harness.step_app(&mut app, input.clone());
+    input.mouse_down[MouseButton::Primary as usize] = true;
+    harness.step_app(&mut app, input.clone());
+    input.mouse_down[MouseButton::Primary as usize] = false;
+    harness.step_app(&mut app, input.clone());
+    harness.step_app(&mut app, input);
+    harness.assert_snapshot("tests/snapshots/first_move.png");
+}